@@ -1,10 +1,12 @@
 use crate::{
     adjustable_variable::{
-        types::{Context, ExpressionF32, Pt2, VecF32, VecPt2, VecU32, F32, U32},
-        AdjustableVariable, UpdateVariableParams,
+        types::{Context, ExpressionF32, Pt2, Rgba, VecF32, VecPt2, VecU32, F32, U32},
+        AdjustableVariable, Changes, UpdateVariableParams,
     },
+    animation::Animation,
     ui::ui_color,
 };
+use anyhow::{bail, Context as _};
 use bevy_reflect::{Reflect, TypeInfo};
 use nannou::prelude::*;
 
@@ -14,7 +16,7 @@ pub trait AdjustableDessin: Reflect + GetField {
         ui: &mut egui::Ui,
         osc_ctx: &Context,
         time: Time<Virtual>,
-    ) -> (bool, Option<Color>)
+    ) -> (Changes, Option<Color>)
     where
         Self: Sized,
     {
@@ -26,9 +28,9 @@ pub trait AdjustableDessin: Reflect + GetField {
 
         ui.separator();
 
-        let changed = self.update_variables(ui, osc_ctx, time);
+        let changes = self.update_variables(ui, osc_ctx, time);
 
-        (changed, color)
+        (changes, color)
     }
 
     fn update_variables(
@@ -36,7 +38,7 @@ pub trait AdjustableDessin: Reflect + GetField {
         ui: &mut egui::Ui,
         osc_ctx: &Context,
         time: Time<Virtual>,
-    ) -> bool
+    ) -> Changes
     where
         Self: Sized,
     {
@@ -59,8 +61,8 @@ pub fn update_from_reflect<T: AdjustableDessin>(
     ui: &mut egui::Ui,
     osc_ctx: &Context,
     time: Time<Virtual>,
-) -> bool {
-    let mut changed = false;
+) -> Changes {
+    let mut changes = Changes::default();
 
     for field_name in get_field_names(data) {
         let params = UpdateVariableParams {
@@ -69,20 +71,8 @@ pub fn update_from_reflect<T: AdjustableDessin>(
             time,
             name: field_name.to_string(),
         };
-        if let Some(inner) = data.get_field_mut::<U32>(field_name) {
-            changed |= inner.update(params);
-        } else if let Some(inner) = data.get_field_mut::<F32>(field_name) {
-            changed |= inner.update(params);
-        } else if let Some(inner) = data.get_field_mut::<ExpressionF32>(field_name) {
-            changed |= inner.update(params);
-        } else if let Some(inner) = data.get_field_mut::<Pt2>(field_name) {
-            changed |= inner.update(params);
-        } else if let Some(inner) = data.get_field_mut::<VecF32>(field_name) {
-            changed |= inner.update(params);
-        } else if let Some(inner) = data.get_field_mut::<VecU32>(field_name) {
-            changed |= inner.update(params);
-        } else if let Some(inner) = data.get_field_mut::<VecPt2>(field_name) {
-            changed |= inner.update(params);
+        if let Some(inner) = get_variable_mut(data, field_name) {
+            changes |= inner.update(params);
         } else {
             let type_name = std::any::type_name::<T>();
             todo!("unsupported field type: {field_name} in {type_name}");
@@ -91,5 +81,114 @@ pub fn update_from_reflect<T: AdjustableDessin>(
         ui.separator();
     }
 
-    changed
+    changes
+}
+
+// `name` is a field name, optionally followed by a path into it (e.g. "p0.x" or "positions[2].y")
+pub fn set_from_reflect<T: AdjustableDessin>(
+    data: &mut T,
+    name: &str,
+    value: &str,
+) -> anyhow::Result<()> {
+    let field_name_end = name.find(['.', '[']).unwrap_or(name.len());
+    let (field_name, path) = name.split_at(field_name_end);
+
+    match get_variable_mut(data, field_name) {
+        Some(inner) => inner
+            .set(path, value)
+            .with_context(|| format!("could not set {name}")),
+        None => bail!("no adjustable variable named {field_name}"),
+    }
+}
+
+pub fn animate_from_reflect<T: AdjustableDessin>(data: &mut T, time: Time<Virtual>) {
+    for field_name in get_field_names(data) {
+        if let Some(inner) = get_variable_mut(data, field_name) {
+            inner.animate(time);
+        }
+    }
+}
+
+pub fn animations_from_reflect<T: AdjustableDessin>(data: &T) -> Vec<&Animation> {
+    get_field_names(data)
+        .into_iter()
+        .filter_map(|field_name| get_variable(data, field_name))
+        .flat_map(|inner| inner.animations())
+        .collect()
+}
+
+// the fields that are adjustable variables, as `set_from_reflect` takes them
+pub fn variable_names_from_reflect<T: AdjustableDessin>(data: &T) -> Vec<&'static str> {
+    get_field_names(data)
+        .into_iter()
+        .filter(|field_name| get_variable(data, field_name).is_some())
+        .collect()
+}
+
+pub fn values_from_reflect<T: AdjustableDessin>(data: &T) -> Vec<(String, String)> {
+    get_field_names(data)
+        .into_iter()
+        .filter_map(|field_name| {
+            get_variable(data, field_name).map(|inner| inner.values(field_name))
+        })
+        .flatten()
+        .collect()
+}
+
+fn get_variable<'a, T: AdjustableDessin>(
+    data: &'a T,
+    field_name: &str,
+) -> Option<&'a dyn AdjustableVariable> {
+    if let Some(inner) = data.get_field::<U32>(field_name) {
+        Some(inner)
+    } else if let Some(inner) = data.get_field::<F32>(field_name) {
+        Some(inner)
+    } else if let Some(inner) = data.get_field::<ExpressionF32>(field_name) {
+        Some(inner)
+    } else if let Some(inner) = data.get_field::<Pt2>(field_name) {
+        Some(inner)
+    } else if let Some(inner) = data.get_field::<VecF32>(field_name) {
+        Some(inner)
+    } else if let Some(inner) = data.get_field::<VecU32>(field_name) {
+        Some(inner)
+    } else if let Some(inner) = data.get_field::<VecPt2>(field_name) {
+        Some(inner)
+    } else if let Some(inner) = data.get_field::<Rgba>(field_name) {
+        Some(inner)
+    } else {
+        None
+    }
+}
+
+fn get_variable_mut<'a, T: AdjustableDessin>(
+    data: &'a mut T,
+    field_name: &str,
+) -> Option<&'a mut dyn AdjustableVariable> {
+    if data.get_field::<U32>(field_name).is_some() {
+        data.get_field_mut::<U32>(field_name)
+            .map(|inner| inner as &mut dyn AdjustableVariable)
+    } else if data.get_field::<F32>(field_name).is_some() {
+        data.get_field_mut::<F32>(field_name)
+            .map(|inner| inner as &mut dyn AdjustableVariable)
+    } else if data.get_field::<ExpressionF32>(field_name).is_some() {
+        data.get_field_mut::<ExpressionF32>(field_name)
+            .map(|inner| inner as &mut dyn AdjustableVariable)
+    } else if data.get_field::<Pt2>(field_name).is_some() {
+        data.get_field_mut::<Pt2>(field_name)
+            .map(|inner| inner as &mut dyn AdjustableVariable)
+    } else if data.get_field::<VecF32>(field_name).is_some() {
+        data.get_field_mut::<VecF32>(field_name)
+            .map(|inner| inner as &mut dyn AdjustableVariable)
+    } else if data.get_field::<VecU32>(field_name).is_some() {
+        data.get_field_mut::<VecU32>(field_name)
+            .map(|inner| inner as &mut dyn AdjustableVariable)
+    } else if data.get_field::<VecPt2>(field_name).is_some() {
+        data.get_field_mut::<VecPt2>(field_name)
+            .map(|inner| inner as &mut dyn AdjustableVariable)
+    } else if data.get_field::<Rgba>(field_name).is_some() {
+        data.get_field_mut::<Rgba>(field_name)
+            .map(|inner| inner as &mut dyn AdjustableVariable)
+    } else {
+        None
+    }
 }
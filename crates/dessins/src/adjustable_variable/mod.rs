@@ -1,6 +1,11 @@
-use crate::adjustable_variable::types::Context;
+use crate::{
+    adjustable_variable::types::{Context, ExpressionF32},
+    animation::Animation,
+};
+use anyhow::{anyhow, bail};
 use bevy_reflect::Reflect;
 use nannou::prelude::*;
+use std::ops::{BitOr, BitOrAssign};
 
 pub mod types;
 
@@ -11,6 +16,82 @@ pub struct UpdateVariableParams<'a> {
     pub time: Time<Virtual>,
 }
 
+// what an update did: `changed` anything the drawing depends on, as running animations and osc
+// do every frame, and whether the user `edited` it through the widgets
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Changes {
+    pub changed: bool,
+    pub edited: bool,
+}
+
 pub trait AdjustableVariable: Reflect {
-    fn update(&mut self, params: UpdateVariableParams) -> bool;
+    fn update(&mut self, params: UpdateVariableParams) -> Changes;
+
+    // `path` addresses a part of the variable (e.g. ".x" or "[2].y", empty for the whole of it),
+    // `value` is written in the same unit as the variable's slider
+    fn set(&mut self, path: &str, value: &str) -> anyhow::Result<()>;
+
+    // every `name + path` with its current value, as accepted by `set`
+    fn values(&self, name: &str) -> Vec<(String, String)>;
+
+    // moves animated values to `time` without drawing any ui
+    fn animate(&mut self, _time: Time<Virtual>) {}
+
+    fn animations(&self) -> Vec<&Animation> {
+        vec![]
+    }
+}
+
+impl Changes {
+    // an edit through the widgets also changes the drawing
+    pub fn edited(edited: bool) -> Self {
+        Self {
+            changed: edited,
+            edited,
+        }
+    }
+}
+
+impl BitOr for Changes {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self {
+            changed: self.changed | other.changed,
+            edited: self.edited | other.edited,
+        }
+    }
+}
+
+impl BitOrAssign for Changes {
+    fn bitor_assign(&mut self, other: Self) {
+        *self = *self | other;
+    }
+}
+
+pub(crate) fn parse_number(value: &str) -> anyhow::Result<f32> {
+    let ctx = Context::new(&[("pi".to_string(), PI)]);
+    evalexpr::eval_number_with_context(value, &ExpressionF32::evaluatable_ctx(&ctx))
+        .map(|value| value as f32)
+        .map_err(|err| anyhow!("invalid number {value}: {err}"))
+}
+
+pub(crate) fn split_index(path: &str) -> anyhow::Result<(usize, &str)> {
+    let (index, rest) = path
+        .strip_prefix('[')
+        .and_then(|path| path.split_once(']'))
+        .ok_or_else(|| anyhow!("expected an index like [0] instead of {path:?}"))?;
+
+    let index = index
+        .parse()
+        .map_err(|_| anyhow!("invalid index {index:?}"))?;
+
+    Ok((index, rest))
+}
+
+pub(crate) fn expect_end(path: &str) -> anyhow::Result<()> {
+    if !path.is_empty() {
+        bail!("unexpected {path:?} after variable name");
+    }
+    Ok(())
 }
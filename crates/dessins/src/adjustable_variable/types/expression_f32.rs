@@ -1,13 +1,15 @@
-use crate::adjustable_variable::{AdjustableVariable, UpdateVariableParams};
+use crate::adjustable_variable::{expect_end, AdjustableVariable, Changes, UpdateVariableParams};
+use anyhow::anyhow;
 use bevy::reflect::Reflect;
 use evalexpr::{ContextWithMutableVariables, HashMapContext};
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, ops::RangeInclusive};
 
-#[derive(Clone, Debug, PartialEq, Reflect)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
 pub struct Context(HashMap<String, f32>);
 
-#[derive(Clone, Debug, PartialEq, Reflect)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
 pub struct ExpressionF32 {
     expr: String,
     default_expr: String,
@@ -87,8 +89,7 @@ impl ExpressionF32 {
         ui: &mut egui::Ui,
         osc_ctx: &Context,
         label: &str,
-    ) -> bool {
-        let mut changed = false;
+    ) -> Changes {
         ui.label(label);
 
         let response = egui::TextEdit::singleline(&mut self.expr)
@@ -100,6 +101,11 @@ impl ExpressionF32 {
 
         let new_expr_input = lost_focus && ui.input(|i| i.key_pressed(egui::Key::Enter));
         let possible_new_osc_ctx = !lost_focus && !has_focus;
+        // new osc values change the drawing, only a new expression is an edit
+        let mut changes = Changes {
+            changed: false,
+            edited: new_expr_input,
+        };
 
         if new_expr_input || possible_new_osc_ctx {
             let mut new_osc_ctx = false;
@@ -122,7 +128,7 @@ impl ExpressionF32 {
                 let ctx = Self::evaluatable_ctx(&self.ctx);
                 if let Ok(value) = evalexpr::eval_number_with_context(&self.expr, &ctx) {
                     self.value = value as f32;
-                    changed = true;
+                    changes.changed = true;
                 }
             }
         }
@@ -145,12 +151,12 @@ impl ExpressionF32 {
             });
         });
 
-        changed
+        changes
     }
 }
 
 impl AdjustableVariable for ExpressionF32 {
-    fn update(&mut self, params: UpdateVariableParams) -> bool {
+    fn update(&mut self, params: UpdateVariableParams) -> Changes {
         let UpdateVariableParams {
             ui,
             osc_ctx,
@@ -160,6 +166,27 @@ impl AdjustableVariable for ExpressionF32 {
 
         self.add_textedit_with_label(ui, osc_ctx, &name)
     }
+
+    fn set(&mut self, path: &str, value: &str) -> anyhow::Result<()> {
+        expect_end(path)?;
+        let mut ctx = Self::evaluatable_ctx(&self.ctx);
+        // the per point variables only get their values while drawing
+        for key in self.ctx_ext.keys() {
+            if !self.ctx.contains_key(key) {
+                ctx.set_value(key.to_string(), evalexpr::Value::Float(0.0))
+                    .expect("context must be valid");
+            }
+        }
+        let evaluated = evalexpr::eval_number_with_context(value, &ctx)
+            .map_err(|err| anyhow!("invalid expression {value}: {err}"))?;
+        self.expr = value.to_string();
+        self.value = evaluated as f32;
+        Ok(())
+    }
+
+    fn values(&self, name: &str) -> Vec<(String, String)> {
+        vec![(name.to_string(), self.expr.clone())]
+    }
 }
 
 impl Context {
@@ -1,21 +1,25 @@
 use crate::{
-    adjustable_variable::{types::Context, AdjustableVariable, UpdateVariableParams},
+    adjustable_variable::{
+        expect_end, parse_number, types::Context, AdjustableVariable, Changes, UpdateVariableParams,
+    },
     animation::{Animation, AnimationVariant},
+    osc::binding::learn_button,
     shapes::NP,
     ui::{add_float_length, add_float_pi, add_float_position, float},
 };
 use bevy::reflect::Reflect;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::ops::RangeInclusive;
 
-#[derive(Clone, Debug, PartialEq, Reflect)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
 pub struct F32 {
     value: f32,
     variant: F32Variant,
     animation: Option<Animation>,
 }
 
-#[derive(Clone, Debug, PartialEq, Reflect)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
 pub enum F32Variant {
     None(RangeInclusive<f32>),
     Angle,
@@ -25,11 +29,16 @@ pub enum F32Variant {
 
 impl F32 {
     pub fn new(value: f32, variant: F32Variant) -> Self {
-        let value = match variant {
-            F32Variant::Length | F32Variant::Position => value * NP as f32,
-            F32Variant::Angle => value * PI,
-            _ => value,
-        };
+        let value = value * variant.get_scale();
+        Self {
+            value,
+            variant,
+            animation: None,
+        }
+    }
+
+    // from the value as the params take it instead of the units of the slider
+    pub fn from_raw(value: f32, variant: F32Variant) -> Self {
         Self {
             value,
             variant,
@@ -47,10 +56,33 @@ impl F32 {
 }
 
 impl AdjustableVariable for F32 {
-    fn update(&mut self, params: UpdateVariableParams) -> bool {
+    fn update(&mut self, params: UpdateVariableParams) -> Changes {
         self.variant
             .update(&mut self.value, &mut self.animation, params)
     }
+
+    fn set(&mut self, path: &str, value: &str) -> anyhow::Result<()> {
+        expect_end(path)?;
+        let range = self.variant.get_value_range();
+        let value = parse_number(value)? * self.variant.get_scale();
+        self.value = value.clamp(*range.start(), *range.end());
+        Ok(())
+    }
+
+    fn values(&self, name: &str) -> Vec<(String, String)> {
+        let value = self.value / self.variant.get_scale();
+        vec![(name.to_string(), value.to_string())]
+    }
+
+    fn animate(&mut self, time: Time<Virtual>) {
+        if let Some(ref mut animation) = self.animation {
+            self.value = animation.calculate(time);
+        }
+    }
+
+    fn animations(&self) -> Vec<&Animation> {
+        self.animation.iter().collect()
+    }
 }
 
 impl F32Variant {
@@ -63,6 +95,15 @@ impl F32Variant {
             Self::Position => -1.0 * np..=1.0 * np,
         }
     }
+
+    // factor between the value shown on the slider and the stored value
+    pub fn get_scale(&self) -> f32 {
+        match self {
+            Self::None(_) => 1.0,
+            Self::Angle => PI,
+            Self::Length | Self::Position => NP as f32,
+        }
+    }
 }
 
 impl F32Variant {
@@ -71,7 +112,7 @@ impl F32Variant {
         value: &mut f32,
         animation: &mut Option<Animation>,
         params: UpdateVariableParams,
-    ) -> bool {
+    ) -> Changes {
         let UpdateVariableParams {
             ui,
             osc_ctx,
@@ -82,26 +123,32 @@ impl F32Variant {
         let initial_animate = animate;
 
         // add slider
-        let mut changed = self.add_with_label(ui, &osc_ctx, &name, value);
+        let mut changes = Changes::edited(self.add_with_label(ui, &osc_ctx, &name, value));
 
         // add animate checkbox
-        ui.checkbox(&mut animate, "animate");
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut animate, "animate");
+            learn_button(ui, &name);
+        });
 
         if let Some(ref mut animation) = animation {
             // animate and...
             *value = animation.calculate(time);
 
             // ... add animation params UI elements
+            let previous = animation.clone();
             animation.update_ui(ui, *value, &name);
-            changed |= true
+            changes.edited |= *animation != previous;
+            changes.changed = true;
         }
 
         // maybe toggle animate
         if animate != initial_animate {
-            self.toggle_animation(*value, animation, time);
+            self.toggle_animation(*value, animation);
+            changes |= Changes::edited(true);
         }
 
-        changed
+        changes
     }
 }
 
@@ -121,15 +168,17 @@ impl F32Variant {
         }
     }
 
-    fn toggle_animation(&self, value: f32, animation: &mut Option<Animation>, time: Time<Virtual>) {
+    fn toggle_animation(&self, value: f32, animation: &mut Option<Animation>) {
         *animation = match animation {
             Some(_) => None,
             None => {
                 let range = self.get_value_range();
-                let animation = Animation::new(
-                    time,
-                    AnimationVariant::new_sin(value, 0.1, *range.start(), *range.end()),
-                );
+                let animation = Animation::new(AnimationVariant::new_sin(
+                    value,
+                    0.1,
+                    *range.start(),
+                    *range.end(),
+                ));
                 Some(animation)
             }
         }
@@ -1,6 +1,7 @@
 pub use expression_f32::{Context, ExpressionF32};
 pub use f32::{F32Variant, F32};
 pub use pt2::Pt2;
+pub use rgba::{ColorSpace, Rgba};
 pub use u32::U32;
 pub use vec_f32::VecF32;
 pub use vec_pt2::VecPt2;
@@ -9,6 +10,7 @@ pub use vec_u32::VecU32;
 pub mod expression_f32;
 pub mod f32;
 pub mod pt2;
+pub mod rgba;
 pub mod u32;
 pub mod vec_f32;
 pub mod vec_pt2;
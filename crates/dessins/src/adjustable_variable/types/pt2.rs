@@ -1,21 +1,24 @@
 use crate::{
-    adjustable_variable::{AdjustableVariable, UpdateVariableParams},
+    adjustable_variable::{parse_number, AdjustableVariable, Changes, UpdateVariableParams},
     animation::{Animation, AnimationVariant},
+    osc::binding::learn_button,
     shapes::NP,
     ui::add_float_position,
 };
+use anyhow::bail;
 use bevy::reflect::Reflect;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use super::Context;
 
-#[derive(Clone, Debug, PartialEq, Reflect)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
 pub struct Pt2 {
     value: Point2,
     animation: Pt2Animation,
 }
 
-#[derive(Clone, Debug, PartialEq, Reflect)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
 pub struct Pt2Animation {
     x: Option<Animation>,
     y: Option<Animation>,
@@ -39,7 +42,7 @@ impl Pt2 {
 }
 
 impl AdjustableVariable for Pt2 {
-    fn update(&mut self, params: UpdateVariableParams) -> bool {
+    fn update(&mut self, params: UpdateVariableParams) -> Changes {
         let UpdateVariableParams {
             ui,
             osc_ctx,
@@ -49,7 +52,7 @@ impl AdjustableVariable for Pt2 {
         let name_x = format!("{}.x", name);
         let name_y = format!("{}.y", name);
 
-        let x_changed = update(
+        let x_changes = update(
             &mut self.value.x,
             ui,
             &osc_ctx,
@@ -57,7 +60,7 @@ impl AdjustableVariable for Pt2 {
             time,
             &mut self.animation.x,
         );
-        let y_changed = update(
+        let y_changes = update(
             &mut self.value.y,
             ui,
             &osc_ctx,
@@ -66,18 +69,51 @@ impl AdjustableVariable for Pt2 {
             &mut self.animation.y,
         );
 
-        x_changed | y_changed
+        x_changes | y_changes
+    }
+
+    fn set(&mut self, path: &str, value: &str) -> anyhow::Result<()> {
+        let value = parse_number(value)? * NP as f32;
+        match path {
+            ".x" => self.value.x = value,
+            ".y" => self.value.y = value,
+            _ => bail!("expected .x or .y instead of {path:?}"),
+        }
+        Ok(())
+    }
+
+    fn values(&self, name: &str) -> Vec<(String, String)> {
+        let value = self.value / NP as f32;
+        vec![
+            (format!("{name}.x"), value.x.to_string()),
+            (format!("{name}.y"), value.y.to_string()),
+        ]
+    }
+
+    fn animate(&mut self, time: Time<Virtual>) {
+        if let Some(ref mut animation) = self.animation.x {
+            self.value.x = animation.calculate(time);
+        }
+        if let Some(ref mut animation) = self.animation.y {
+            self.value.y = animation.calculate(time);
+        }
+    }
+
+    fn animations(&self) -> Vec<&Animation> {
+        self.animation.x.iter().chain(&self.animation.y).collect()
     }
 }
 
-fn toggle_animation(value: f32, animation: &mut Option<Animation>, time: Time<Virtual>) {
+fn toggle_animation(value: f32, animation: &mut Option<Animation>) {
     *animation = match animation {
         Some(_) => None,
         None => {
-            let animation = Animation::new(
-                time,
-                AnimationVariant::new_sin(value, 0.1, -1.0 * NP as f32, 1.0 * NP as f32),
-            );
+            let animation = Animation::new(AnimationVariant::new_sin(
+                value,
+                0.1,
+                -1.0 * NP as f32,
+                1.0 * NP as f32,
+            ));
             Some(animation)
         }
     }
@@ -90,30 +126,36 @@ fn update(
     name: &str,
     time: Time<Virtual>,
     animation: &mut Option<Animation>,
-) -> bool {
+) -> Changes {
     let mut animate_ = animation.is_some();
     let initial_animate = animate_;
 
     // add slider
     ui.label(name);
-    let mut changed = add_float_position(ui, osc_ctx, value);
+    let mut changes = Changes::edited(add_float_position(ui, osc_ctx, value));
 
     // add animate checkbox
-    ui.checkbox(&mut animate_, "animate");
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut animate_, "animate");
+        learn_button(ui, name);
+    });
 
     if let Some(ref mut animation) = animation {
         // animate and...
         *value = animation.calculate(time);
 
         // ... add animation params UI elements
+        let previous = animation.clone();
         animation.update_ui(ui, *value, name);
-        changed |= true
+        changes.edited |= *animation != previous;
+        changes.changed = true;
     }
 
     // maybe toggle animate
     if animate_ != initial_animate {
-        toggle_animation(*value, animation, time);
+        toggle_animation(*value, animation);
+        changes |= Changes::edited(true);
     }
 
-    changed
+    changes
 }
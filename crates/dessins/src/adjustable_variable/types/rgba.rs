@@ -0,0 +1,224 @@
+use crate::{
+    adjustable_variable::{
+        parse_number, types::F32Variant, AdjustableVariable, Changes, UpdateVariableParams,
+    },
+    animation::Animation,
+};
+use anyhow::{anyhow, bail};
+use bevy::{
+    color::{Hsva, Srgba},
+    reflect::Reflect,
+};
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct Rgba {
+    space: ColorSpace,
+    channels: [f32; 4], // in `space`, alpha last
+    animations: [Option<Animation>; 4],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum ColorSpace {
+    Hsv, // hue in degrees
+    Rgb, // srgb
+}
+
+impl Rgba {
+    pub fn new(color: Color) -> Self {
+        let space = ColorSpace::Hsv;
+        Self {
+            space,
+            channels: space.channels(color),
+            animations: Default::default(),
+        }
+    }
+
+    pub fn get_value(&self) -> Color {
+        self.space.color(self.channels)
+    }
+
+    pub fn set_value(&mut self, color: Color) {
+        self.channels = self.space.channels(color);
+    }
+
+    fn hex(&self) -> String {
+        self.get_value().to_srgba().to_hex()
+    }
+
+    // the channels of the other space do not animate the same way, so animations stop
+    fn set_space(&mut self, space: ColorSpace) {
+        let color = self.get_value();
+        self.space = space;
+        self.channels = space.channels(color);
+        self.animations = Default::default();
+    }
+
+    fn add_hex(&mut self, ui: &mut egui::Ui, name: &str) -> bool {
+        let mut changed = false;
+
+        // the text being typed is kept apart until it is a valid colour
+        let id = ui.make_persistent_id(("hex", name));
+        let mut hex = ui
+            .data(|data| data.get_temp::<String>(id))
+            .unwrap_or_else(|| self.hex());
+
+        let response = ui.add(egui::TextEdit::singleline(&mut hex).desired_width(80.0));
+
+        if response.lost_focus() {
+            if let Ok(color) = Srgba::hex(hex.trim()) {
+                self.set_value(color.into());
+                changed = true;
+            }
+            ui.data_mut(|data| data.remove::<String>(id));
+        } else if response.has_focus() {
+            ui.data_mut(|data| data.insert_temp(id, hex));
+        }
+
+        changed
+    }
+}
+
+impl ColorSpace {
+    const ALL: &'static [(Self, &'static str)] = &[(Self::Hsv, "hsv"), (Self::Rgb, "rgb")];
+
+    fn names(&self) -> [&'static str; 4] {
+        match self {
+            Self::Hsv => ["h", "s", "v", "a"],
+            Self::Rgb => ["r", "g", "b", "a"],
+        }
+    }
+
+    fn range(&self, index: usize) -> RangeInclusive<f32> {
+        match (self, index) {
+            (Self::Hsv, 0) => 0.0..=360.0,
+            _ => 0.0..=1.0,
+        }
+    }
+
+    fn channels(&self, color: Color) -> [f32; 4] {
+        match self {
+            Self::Hsv => {
+                let hsva = Hsva::from(color);
+                [hsva.hue, hsva.saturation, hsva.value, hsva.alpha]
+            }
+            Self::Rgb => color.to_srgba().to_f32_array(),
+        }
+    }
+
+    fn color(&self, [first, second, third, alpha]: [f32; 4]) -> Color {
+        match self {
+            Self::Hsv => Color::hsva(first, second, third, alpha),
+            Self::Rgb => Color::srgba(first, second, third, alpha),
+        }
+    }
+}
+
+impl AdjustableVariable for Rgba {
+    fn update(&mut self, params: UpdateVariableParams) -> Changes {
+        let UpdateVariableParams {
+            ui,
+            osc_ctx,
+            name,
+            time,
+        } = params;
+        let mut changes = Changes::default();
+
+        ui.horizontal(|ui| {
+            ui.label(&name);
+
+            let mut rgba = self.get_value().to_srgba().to_f32_array();
+            if ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed() {
+                let [red, green, blue, alpha] = rgba;
+                self.set_value(Color::srgba(red, green, blue, alpha));
+                changes = Changes::edited(true);
+            }
+
+            changes |= Changes::edited(self.add_hex(ui, &name));
+
+            let mut space = self.space;
+            for (variant, label) in ColorSpace::ALL {
+                ui.radio_value(&mut space, *variant, *label);
+            }
+            // the same colour in other channels
+            if space != self.space {
+                self.set_space(space);
+                changes.edited = true;
+            }
+        });
+
+        // each channel is a slider like `F32`, with its own animation
+        let names = self.space.names();
+        for (index, channel) in names.iter().enumerate() {
+            let variant = F32Variant::None(self.space.range(index));
+            changes |= variant.update(
+                &mut self.channels[index],
+                &mut self.animations[index],
+                UpdateVariableParams {
+                    ui: &mut *ui,
+                    osc_ctx,
+                    name: format!("{}.{}", name, channel),
+                    time,
+                },
+            );
+        }
+
+        changes
+    }
+
+    // a hex colour for the whole of it, or a number for a channel of either space (e.g. ".h")
+    fn set(&mut self, path: &str, value: &str) -> anyhow::Result<()> {
+        if path.is_empty() {
+            let color =
+                Srgba::hex(value.trim()).map_err(|err| anyhow!("invalid color {value}: {err}"))?;
+            self.set_value(color.into());
+            return Ok(());
+        }
+
+        let channel = path
+            .strip_prefix('.')
+            .ok_or_else(|| anyhow!("expected a channel like .h instead of {path:?}"))?;
+        let space = match channel {
+            "a" => self.space,
+            "h" | "s" | "v" => ColorSpace::Hsv,
+            "r" | "g" | "b" => ColorSpace::Rgb,
+            _ => bail!("unknown channel {channel:?}, expected one of h, s, v, r, g, b or a"),
+        };
+        let index = space
+            .names()
+            .iter()
+            .position(|name| *name == channel)
+            .expect("every channel has a name");
+
+        let range = space.range(index);
+        let value = parse_number(value)?.clamp(*range.start(), *range.end());
+
+        if space == self.space {
+            self.channels[index] = value;
+        } else {
+            let mut channels = space.channels(self.get_value());
+            channels[index] = value;
+            self.set_value(space.color(channels));
+        }
+
+        Ok(())
+    }
+
+    fn values(&self, name: &str) -> Vec<(String, String)> {
+        vec![(name.to_string(), self.hex())]
+    }
+
+    fn animate(&mut self, time: Time<Virtual>) {
+        for (value, animation) in self.channels.iter_mut().zip(&mut self.animations) {
+            if let Some(animation) = animation {
+                *value = animation.calculate(time);
+            }
+        }
+    }
+
+    fn animations(&self) -> Vec<&Animation> {
+        self.animations.iter().flatten().collect()
+    }
+}
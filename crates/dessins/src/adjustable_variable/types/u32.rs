@@ -1,13 +1,17 @@
 use crate::{
-    adjustable_variable::{AdjustableVariable, UpdateVariableParams},
+    adjustable_variable::{
+        expect_end, parse_number, AdjustableVariable, Changes, UpdateVariableParams,
+    },
     animation::{Animation, AnimationVariant},
+    osc::binding::learn_button,
     ui::add_numeric,
 };
 use bevy::reflect::Reflect;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::ops::RangeInclusive;
 
-#[derive(Clone, Debug, PartialEq, Reflect)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
 pub struct U32 {
     value: u32,
     range: RangeInclusive<u32>,
@@ -31,19 +35,16 @@ impl U32 {
         self.value = value;
     }
 
-    fn toggle_animation(&mut self, time: Time<Virtual>) {
+    pub fn toggle_animation(&mut self) {
         self.animation = match self.animation {
             Some(_) => None,
             None => {
-                let animation = Animation::new(
-                    time,
-                    AnimationVariant::new_sin(
-                        self.value as f32,
-                        0.1,
-                        *self.range.start() as f32,
-                        *self.range.end() as f32,
-                    ),
-                );
+                let animation = Animation::new(AnimationVariant::new_sin(
+                    self.value as f32,
+                    0.1,
+                    *self.range.start() as f32,
+                    *self.range.end() as f32,
+                ));
                 Some(animation)
             }
         }
@@ -55,7 +56,7 @@ impl U32 {
 }
 
 impl AdjustableVariable for U32 {
-    fn update(&mut self, params: UpdateVariableParams) -> bool {
+    fn update(&mut self, params: UpdateVariableParams) -> Changes {
         let UpdateVariableParams {
             ui,
             osc_ctx,
@@ -66,25 +67,58 @@ impl AdjustableVariable for U32 {
         let initial_animate = animate;
 
         // add slider
-        let mut changed = add_numeric(ui, &osc_ctx, &name, &mut self.value, self.range.clone());
+        let mut changes = Changes::edited(add_numeric(
+            ui,
+            &osc_ctx,
+            &name,
+            &mut self.value,
+            self.range.clone(),
+        ));
 
         // add animate checkbox
-        ui.checkbox(&mut animate, "animate");
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut animate, "animate");
+            learn_button(ui, &name);
+        });
 
         if let Some(ref mut animation) = self.animation {
             // animate and...
             self.value = animation.calculate(time).round() as u32;
 
             // ... add animation params UI elements
+            let previous = animation.clone();
             animation.update_ui(ui, self.value as f32, &name);
-            changed |= true
+            changes.edited |= *animation != previous;
+            changes.changed = true;
         }
 
         // maybe toggle animate
         if animate != initial_animate {
-            self.toggle_animation(time);
+            self.toggle_animation();
+            changes |= Changes::edited(true);
+        }
+
+        changes
+    }
+
+    fn set(&mut self, path: &str, value: &str) -> anyhow::Result<()> {
+        expect_end(path)?;
+        let value = parse_number(value)?.round().max(0.0) as u32;
+        self.value = value.clamp(*self.range.start(), *self.range.end());
+        Ok(())
+    }
+
+    fn values(&self, name: &str) -> Vec<(String, String)> {
+        vec![(name.to_string(), self.value.to_string())]
+    }
+
+    fn animate(&mut self, time: Time<Virtual>) {
+        if let Some(ref mut animation) = self.animation {
+            self.value = animation.calculate(time).round() as u32;
         }
+    }
 
-        changed
+    fn animations(&self) -> Vec<&Animation> {
+        self.animation.iter().collect()
     }
 }
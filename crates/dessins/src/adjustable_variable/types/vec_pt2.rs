@@ -1,9 +1,14 @@
 use super::pt2::Pt2;
-use crate::adjustable_variable::{AdjustableVariable, UpdateVariableParams};
+use crate::{
+    adjustable_variable::{split_index, AdjustableVariable, Changes, UpdateVariableParams},
+    animation::Animation,
+};
+use anyhow::anyhow;
 use bevy::reflect::Reflect;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Reflect)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
 pub struct VecPt2 {
     value: Vec<Pt2>,
 }
@@ -28,7 +33,7 @@ impl VecPt2 {
 }
 
 impl AdjustableVariable for VecPt2 {
-    fn update(&mut self, params: UpdateVariableParams) -> bool {
+    fn update(&mut self, params: UpdateVariableParams) -> Changes {
         let UpdateVariableParams {
             ui,
             osc_ctx,
@@ -36,15 +41,43 @@ impl AdjustableVariable for VecPt2 {
             time,
         } = params;
 
-        let mut changed = false;
+        let mut changes = Changes::default();
         for (index, value) in self.value.iter_mut().enumerate() {
-            changed |= value.update(UpdateVariableParams {
+            changes |= value.update(UpdateVariableParams {
                 ui,
                 osc_ctx,
                 name: format!("{}[{}]", name, index).to_string(),
                 time,
             })
         }
-        changed
+        changes
+    }
+
+    fn set(&mut self, path: &str, value: &str) -> anyhow::Result<()> {
+        let (index, path) = split_index(path)?;
+        let len = self.value.len();
+        self.value
+            .get_mut(index)
+            .ok_or_else(|| anyhow!("index {index} out of bounds (length {len})"))?
+            .set(path, value)
+    }
+
+    fn values(&self, name: &str) -> Vec<(String, String)> {
+        self.value
+            .iter()
+            .enumerate()
+            .flat_map(|(index, value)| value.values(&format!("{name}[{index}]")))
+            .collect()
+    }
+
+    fn animate(&mut self, time: Time<Virtual>) {
+        self.value.iter_mut().for_each(|value| value.animate(time));
+    }
+
+    fn animations(&self) -> Vec<&Animation> {
+        self.value
+            .iter()
+            .flat_map(|value| value.animations())
+            .collect()
     }
 }
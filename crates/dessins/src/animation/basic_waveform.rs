@@ -1,10 +1,11 @@
 use crate::{adjustable_variable::types::Context, ui::add_numeric};
 use bevy::reflect::Reflect;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use wavegen::{sawtooth, sine, square, wf, PeriodicFunction, Waveform};
 
-#[derive(Clone, Debug, PartialEq, Reflect)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
 pub struct BasicWaveform {
     variant: Variant,
     frequency: f32,
@@ -13,7 +14,7 @@ pub struct BasicWaveform {
     phase_offset: f32,
 }
 
-#[derive(Clone, Debug, PartialEq, Reflect)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
 pub enum Variant {
     Sin,
     Sawtooth,
@@ -65,8 +66,8 @@ impl BasicWaveform {
         Self::new(variant, start_value, *frequency, *min, *max)
     }
 
-    pub(crate) fn calculate(&self, time: Time<Virtual>, start_time: f64) -> f32 {
-        let dt = (time.elapsed_secs_f64() - start_time) as f32;
+    pub(crate) fn calculate(&self, time: Time<Virtual>) -> f32 {
+        let dt = time.elapsed_secs();
         let amplitude = (self.max - self.min) / 2.0;
         let sample_rate = 1.0 / time.delta_secs();
         let waveform = match self.variant {
@@ -92,6 +93,14 @@ impl BasicWaveform {
         self.min + amplitude * (1.0 + value)
     }
 
+    pub(crate) fn period(&self) -> f32 {
+        if self.frequency > 0.0 {
+            1.0 / self.frequency
+        } else {
+            0.0
+        }
+    }
+
     pub(crate) fn update_ui(&mut self, ui: &mut egui::Ui, values: &mut HashMap<String, f32>) {
         add_numeric(
             ui,
@@ -0,0 +1,169 @@
+use super::hash;
+use crate::{adjustable_variable::types::Context, ui::add_numeric};
+use bevy::reflect::Reflect;
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// the walk advances in fixed steps so that it does not depend on the frame rate
+const STEPS_PER_SECOND: f32 = 60.0;
+
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
+pub struct BrownianMotion {
+    step_variance: f32,
+    seed: u32,
+    min: f32,
+    max: f32,
+    start_value: f32,
+    // last computed (step, value), saves replaying the walk from the start every frame, it is
+    // left out of comparisons and presets
+    #[reflect(ignore)]
+    #[serde(skip)]
+    walk: Option<(u64, f32)>,
+}
+
+impl BrownianMotion {
+    pub fn new(start_value: f32, step_variance: f32, seed: u32, min: f32, max: f32) -> Self {
+        Self {
+            step_variance,
+            seed,
+            min,
+            max,
+            start_value: start_value.clamp(min.min(max), max.max(min)),
+            walk: None,
+        }
+    }
+
+    pub fn new_from_values(start_value: f32, values: &HashMap<String, f32>) -> Self {
+        let step_variance = values.get("step variance").unwrap_or(&0.1);
+        let seed = values.get("seed").unwrap_or(&0.0);
+        let min = values.get("min").unwrap_or(&0.0);
+        let max = values.get("max").unwrap_or(&1.0);
+        Self::new(start_value, *step_variance, *seed as u32, *min, *max)
+    }
+
+    pub(crate) fn calculate(&mut self, time: Time<Virtual>) -> f32 {
+        let dt = time.elapsed_secs();
+        let target = (dt * STEPS_PER_SECOND) as u64;
+
+        let (mut step, mut value) = match self.walk {
+            Some((step, value)) if step <= target => (step, value),
+            _ => (0, self.start_value),
+        };
+
+        // variance is given per second, relative to the range
+        let range = self.max - self.min;
+        let deviation = (self.step_variance / STEPS_PER_SECOND).sqrt() * range;
+
+        while step < target {
+            value = self.reflect(value + deviation * self.gaussian(step));
+            step += 1;
+        }
+
+        self.walk = Some((step, value));
+        value
+    }
+
+    // approximately normal, mean 0 and variance 1
+    fn gaussian(&self, step: u64) -> f32 {
+        let sum: f32 = (0..4).map(|i| hash(self.seed, (step * 4 + i) as i64)).sum();
+        (sum - 2.0) * 3.0_f32.sqrt()
+    }
+
+    // bounces off min and max
+    fn reflect(&self, value: f32) -> f32 {
+        let range = self.max - self.min;
+        if range <= 0.0 {
+            return self.min;
+        }
+
+        let offset = (value - self.min).rem_euclid(2.0 * range);
+        if offset > range {
+            self.min + 2.0 * range - offset
+        } else {
+            self.min + offset
+        }
+    }
+
+    pub(crate) fn update_ui(&mut self, ui: &mut egui::Ui, values: &mut HashMap<String, f32>) {
+        let previous = (self.step_variance, self.seed);
+        let ctx = Context::new(Default::default());
+        add_numeric(
+            ui,
+            &ctx,
+            "step variance",
+            &mut self.step_variance,
+            0.0..=1.0,
+        );
+        add_numeric(ui, &ctx, "seed", &mut self.seed, 0..=999);
+        values.insert("step variance".to_string(), self.step_variance);
+        values.insert("seed".to_string(), self.seed as f32);
+
+        // another walk
+        if (self.step_variance, self.seed) != previous {
+            self.walk = None;
+        }
+    }
+
+    pub(crate) fn values_map(&self) -> HashMap<String, f32> {
+        HashMap::from([
+            ("step variance".to_string(), self.step_variance),
+            ("seed".to_string(), self.seed as f32),
+            ("min".to_string(), self.min),
+            ("max".to_string(), self.max),
+        ])
+    }
+
+    pub(crate) fn name(&self) -> String {
+        "brownian".to_string()
+    }
+}
+
+impl PartialEq for BrownianMotion {
+    fn eq(&self, other: &Self) -> bool {
+        self.step_variance == other.step_variance
+            && self.seed == other.seed
+            && self.min == other.min
+            && self.max == other.max
+            && self.start_value == other.start_value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn at(seconds: f32) -> Time<Virtual> {
+        let mut time = Time::default();
+        time.advance_to(Duration::from_secs_f32(seconds));
+        time
+    }
+
+    #[test]
+    fn the_walk_is_left_out_of_comparisons_and_presets() {
+        let fresh = BrownianMotion::new(0.5, 0.1, 7, 0.0, 1.0);
+        let mut walked = fresh.clone();
+        walked.calculate(at(2.0));
+
+        assert!(walked.walk.is_some());
+        assert_eq!(walked, fresh);
+        assert_eq!(
+            ron::to_string(&walked).unwrap(),
+            ron::to_string(&fresh).unwrap()
+        );
+    }
+
+    #[test]
+    fn the_cached_walk_gives_the_replayed_values() {
+        let mut cached = BrownianMotion::new(0.5, 0.1, 7, 0.0, 1.0);
+        cached.calculate(at(1.0));
+        let value = cached.calculate(at(2.0));
+
+        let mut replayed = BrownianMotion::new(0.5, 0.1, 7, 0.0, 1.0);
+        assert_eq!(replayed.calculate(at(2.0)), value);
+
+        // going back in time replays the walk from the start
+        assert_eq!(cached.calculate(at(0.0)), 0.5);
+    }
+}
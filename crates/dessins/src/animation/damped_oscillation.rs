@@ -0,0 +1,93 @@
+use crate::{adjustable_variable::types::Context, ui::add_numeric};
+use bevy::reflect::Reflect;
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// released from the start value at rest, settles in the middle of min..=max
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct DampedOscillation {
+    decay_rate: f32,
+    natural_frequency: f32,
+    min: f32,
+    max: f32,
+    displacement: f32,
+}
+
+impl DampedOscillation {
+    pub fn new(
+        start_value: f32,
+        decay_rate: f32,
+        natural_frequency: f32,
+        min: f32,
+        max: f32,
+    ) -> Self {
+        let mid = (min + max) / 2.0;
+        let start_value = start_value.clamp(min.min(max), max.max(min));
+
+        Self {
+            decay_rate,
+            natural_frequency,
+            min,
+            max,
+            displacement: start_value - mid,
+        }
+    }
+
+    pub fn new_from_values(start_value: f32, values: &HashMap<String, f32>) -> Self {
+        let decay_rate = values.get("decay rate").unwrap_or(&0.3);
+        let natural_frequency = values.get("natural frequency").unwrap_or(&0.5);
+        let min = values.get("min").unwrap_or(&0.0);
+        let max = values.get("max").unwrap_or(&1.0);
+        Self::new(start_value, *decay_rate, *natural_frequency, *min, *max)
+    }
+
+    pub(crate) fn calculate(&self, time: Time<Virtual>) -> f32 {
+        let dt = time.elapsed_secs();
+        let mid = (self.min + self.max) / 2.0;
+
+        let decay = self.decay_rate;
+        let omega = TAU * self.natural_frequency;
+
+        // solutions of x'' + 2 decay x' + omega² x = 0 with x(0) = 1 and x'(0) = 0
+        let factor = if decay < omega {
+            let damped = (omega * omega - decay * decay).sqrt();
+            (-decay * dt).exp() * ((damped * dt).cos() + decay / damped * (damped * dt).sin())
+        } else if decay == omega {
+            (-decay * dt).exp() * (1.0 + decay * dt)
+        } else {
+            let root = (decay * decay - omega * omega).sqrt();
+            let (slow, fast) = (-decay + root, -decay - root);
+            (fast * (slow * dt).exp() - slow * (fast * dt).exp()) / (fast - slow)
+        };
+
+        (mid + self.displacement * factor).clamp(self.min.min(self.max), self.max.max(self.min))
+    }
+
+    pub(crate) fn update_ui(&mut self, ui: &mut egui::Ui, values: &mut HashMap<String, f32>) {
+        let ctx = Context::new(Default::default());
+        add_numeric(ui, &ctx, "decay rate", &mut self.decay_rate, 0.0..=2.0);
+        add_numeric(
+            ui,
+            &ctx,
+            "natural frequency",
+            &mut self.natural_frequency,
+            0.0..=2.0,
+        );
+        values.insert("decay rate".to_string(), self.decay_rate);
+        values.insert("natural frequency".to_string(), self.natural_frequency);
+    }
+
+    pub(crate) fn values_map(&self) -> HashMap<String, f32> {
+        HashMap::from([
+            ("decay rate".to_string(), self.decay_rate),
+            ("natural frequency".to_string(), self.natural_frequency),
+            ("min".to_string(), self.min),
+            ("max".to_string(), self.max),
+        ])
+    }
+
+    pub(crate) fn name(&self) -> String {
+        "damped".to_string()
+    }
+}
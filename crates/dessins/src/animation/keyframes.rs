@@ -0,0 +1,385 @@
+use bevy::reflect::Reflect;
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct Keyframes {
+    keyframes: Vec<Keyframe>,
+    looping: bool,
+    min: f32,
+    max: f32,
+}
+
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct Keyframe {
+    time: f32,
+    value: f32,
+    // how the value moves from this keyframe to the next one
+    easing: Easing,
+}
+
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+pub enum Easing {
+    Linear,
+    EaseInOut,
+    // control points (x1, y1, x2, y2) as in css
+    CubicBezier(f32, f32, f32, f32),
+    Step,
+}
+
+impl Keyframes {
+    pub fn new(keyframes: Vec<Keyframe>, looping: bool, min: f32, max: f32) -> Self {
+        let mut keyframes = Self {
+            keyframes,
+            looping,
+            min,
+            max,
+        };
+        keyframes.sort();
+        keyframes
+    }
+
+    pub fn new_from_values(start_value: f32, values: &HashMap<String, f32>) -> Self {
+        let min = *values.get("min").unwrap_or(&0.0);
+        let max = *values.get("max").unwrap_or(&1.0);
+        let start_value = start_value.clamp(min.min(max), max.max(min));
+
+        Self::new(
+            vec![
+                Keyframe::new(0.0, start_value, Easing::EaseInOut),
+                Keyframe::new(1.0, max, Easing::EaseInOut),
+                Keyframe::new(2.0, start_value, Easing::Linear),
+            ],
+            true,
+            min,
+            max,
+        )
+    }
+
+    // keyframe times are positions on the shared timeline
+    pub(crate) fn calculate(&self, time: Time<Virtual>) -> f32 {
+        let (Some(first), Some(last)) = (self.keyframes.first(), self.keyframes.last()) else {
+            return self.min;
+        };
+
+        let mut t = time.elapsed_secs();
+        if self.looping && last.time > 0.0 {
+            t = t.rem_euclid(last.time);
+        }
+
+        if t <= first.time {
+            return first.value;
+        }
+        if t >= last.time {
+            return last.value;
+        }
+
+        let value = self
+            .keyframes
+            .windows(2)
+            .find(|pair| t < pair[1].time)
+            .map(|pair| {
+                let (from, to) = (&pair[0], &pair[1]);
+                let progress = (t - from.time) / (to.time - from.time);
+                from.value + (to.value - from.value) * from.easing.apply(progress)
+            })
+            .unwrap_or(last.value);
+
+        value.clamp(self.min.min(self.max), self.max.max(self.min))
+    }
+
+    pub(crate) fn period(&self) -> Option<f32> {
+        match self.keyframes.last() {
+            Some(last) if self.looping => Some(last.time),
+            // a single keyframe, or none, holds one value
+            _ if self.keyframes.len() < 2 => Some(0.0),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn update_ui(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.looping, "loop");
+
+        let mut remove = None;
+        let mut dragging = false;
+
+        egui::Grid::new("keyframes").num_columns(4).show(ui, |ui| {
+            for (index, keyframe) in self.keyframes.iter_mut().enumerate() {
+                ui.push_id(index, |ui| {
+                    dragging |= ui
+                        .add(
+                            egui::DragValue::new(&mut keyframe.time)
+                                .speed(0.05)
+                                .range(0.0..=f32::MAX)
+                                .suffix("s"),
+                        )
+                        .dragged();
+                    ui.add(egui::Slider::new(&mut keyframe.value, self.min..=self.max));
+                    keyframe.easing.update_ui(ui);
+                    if ui.button("remove").clicked() {
+                        remove = Some(index);
+                    }
+                });
+                ui.end_row();
+            }
+        });
+
+        if let Some(index) = remove {
+            self.keyframes.remove(index);
+        }
+
+        if ui.button("add keyframe").clicked() {
+            let keyframe = match self.keyframes.last() {
+                Some(last) => Keyframe::new(last.time + 1.0, last.value, last.easing.clone()),
+                None => Keyframe::new(0.0, self.min, Easing::Linear),
+            };
+            self.keyframes.push(keyframe);
+        }
+
+        // reordering rows mid drag would hand the drag over to another keyframe
+        if !dragging {
+            self.sort();
+        }
+    }
+
+    pub(crate) fn values_map(&self) -> HashMap<String, f32> {
+        HashMap::from([("min".to_string(), self.min), ("max".to_string(), self.max)])
+    }
+
+    pub(crate) fn name(&self) -> String {
+        "keyframes".to_string()
+    }
+
+    fn sort(&mut self) {
+        self.keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+    }
+}
+
+impl Keyframe {
+    pub fn new(time: f32, value: f32, easing: Easing) -> Self {
+        Self {
+            time,
+            value,
+            easing,
+        }
+    }
+}
+
+impl Easing {
+    // maps progress between two keyframes (0..=1) to the eased progress
+    pub fn apply(&self, progress: f32) -> f32 {
+        let t = progress.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Self::CubicBezier(x1, y1, x2, y2) => cubic_bezier(*x1, *y1, *x2, *y2, t),
+            Self::Step => 0.0,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Linear => "linear",
+            Self::EaseInOut => "ease in out",
+            Self::CubicBezier(..) => "cubic bezier",
+            Self::Step => "step",
+        }
+    }
+
+    fn update_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("easing")
+                .selected_text(self.name())
+                .show_ui(ui, |ui| {
+                    for easing in [
+                        Self::Linear,
+                        Self::EaseInOut,
+                        Self::CubicBezier(0.25, 0.1, 0.25, 1.0),
+                        Self::Step,
+                    ] {
+                        let name = easing.name();
+                        let selected =
+                            std::mem::discriminant(self) == std::mem::discriminant(&easing);
+                        if ui.selectable_label(selected, name).clicked() && !selected {
+                            *self = easing;
+                        }
+                    }
+                });
+
+            if let Self::CubicBezier(x1, y1, x2, y2) = self {
+                // x stays within 0..=1 so that the curve is a function of time
+                ui.add(egui::DragValue::new(x1).speed(0.01).range(0.0..=1.0));
+                ui.add(egui::DragValue::new(y1).speed(0.01));
+                ui.add(egui::DragValue::new(x2).speed(0.01).range(0.0..=1.0));
+                ui.add(egui::DragValue::new(y2).speed(0.01));
+            }
+        });
+    }
+}
+
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    let bezier = |a: f32, b: f32, s: f32| {
+        let r = 1.0 - s;
+        3.0 * r * r * s * a + 3.0 * r * s * s * b + s * s * s
+    };
+
+    // x(s) is monotonic for x1, x2 in 0..=1, so bisect for the s with x(s) = x
+    let (mut low, mut high) = (0.0, 1.0);
+    for _ in 0..32 {
+        let mid = (low + high) / 2.0;
+        if bezier(x1, x2, mid) < x {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    bezier(y1, y2, (low + high) / 2.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    fn at(seconds: f32) -> Time<Virtual> {
+        let mut time = Time::default();
+        time.advance_to(Duration::from_secs_f32(seconds));
+        time
+    }
+
+    #[test]
+    fn easings_start_at_0_and_end_at_1() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseInOut,
+            Easing::CubicBezier(0.25, 0.1, 0.25, 1.0),
+        ] {
+            assert!(close(easing.apply(0.0), 0.0), "{easing:?}");
+            assert!(close(easing.apply(1.0), 1.0), "{easing:?}");
+        }
+    }
+
+    #[test]
+    fn easings_clamp_the_progress() {
+        assert_eq!(Easing::Linear.apply(-0.5), 0.0);
+        assert_eq!(Easing::Linear.apply(1.5), 1.0);
+        assert_eq!(Easing::EaseInOut.apply(2.0), 1.0);
+    }
+
+    #[test]
+    fn ease_in_out_is_slow_at_the_ends_and_symmetric() {
+        let easing = Easing::EaseInOut;
+
+        assert!(close(easing.apply(0.5), 0.5));
+        assert!(close(easing.apply(0.25), 0.0625));
+        assert!(close(easing.apply(0.75), 1.0 - 0.0625));
+    }
+
+    #[test]
+    fn step_holds_the_value_until_the_next_keyframe() {
+        assert_eq!(Easing::Step.apply(0.0), 0.0);
+        assert_eq!(Easing::Step.apply(0.99), 0.0);
+    }
+
+    #[test]
+    fn cubic_bezier_with_diagonal_control_points_is_linear() {
+        for x in [0.0, 0.1, 0.5, 0.9, 1.0] {
+            assert!(close(cubic_bezier(0.0, 0.0, 1.0, 1.0, x), x));
+            assert!(close(cubic_bezier(0.25, 0.25, 0.75, 0.75, x), x));
+        }
+    }
+
+    #[test]
+    fn cubic_bezier_follows_the_css_curves() {
+        // css ease-in-out, symmetric around the middle
+        let ease_in_out = |x| cubic_bezier(0.42, 0.0, 0.58, 1.0, x);
+        assert!(close(ease_in_out(0.5), 0.5));
+        assert!(close(ease_in_out(0.25) + ease_in_out(0.75), 1.0));
+        assert!(ease_in_out(0.25) < 0.25);
+
+        // css ease-in starts slower than linear
+        assert!(cubic_bezier(0.42, 0.0, 1.0, 1.0, 0.5) < 0.5);
+    }
+
+    #[test]
+    fn interpolates_between_keyframes() {
+        let keyframes = Keyframes::new(
+            vec![
+                Keyframe::new(3.0, 10.0, Easing::Step),
+                Keyframe::new(1.0, 0.0, Easing::Linear),
+                Keyframe::new(2.0, 4.0, Easing::EaseInOut),
+            ],
+            false,
+            0.0,
+            10.0,
+        );
+
+        // held before the first keyframe and after the last one
+        assert_eq!(keyframes.calculate(at(0.0)), 0.0);
+        assert_eq!(keyframes.calculate(at(5.0)), 10.0);
+
+        assert!(close(keyframes.calculate(at(1.5)), 2.0));
+        assert!(close(keyframes.calculate(at(2.5)), 7.0));
+        assert_eq!(keyframes.period(), None);
+    }
+
+    #[test]
+    fn step_keyframes_jump_at_the_next_keyframe() {
+        let keyframes = Keyframes::new(
+            vec![
+                Keyframe::new(0.0, 1.0, Easing::Step),
+                Keyframe::new(1.0, 5.0, Easing::Step),
+            ],
+            false,
+            0.0,
+            10.0,
+        );
+
+        assert_eq!(keyframes.calculate(at(0.9)), 1.0);
+        assert_eq!(keyframes.calculate(at(1.0)), 5.0);
+    }
+
+    #[test]
+    fn looping_keyframes_repeat_from_the_timeline_origin() {
+        let keyframes = Keyframes::new(
+            vec![
+                Keyframe::new(0.0, 0.0, Easing::Linear),
+                Keyframe::new(2.0, 8.0, Easing::Linear),
+            ],
+            true,
+            0.0,
+            10.0,
+        );
+
+        assert_eq!(keyframes.period(), Some(2.0));
+        assert!(close(keyframes.calculate(at(0.5)), 2.0));
+        assert!(close(keyframes.calculate(at(4.5)), 2.0));
+    }
+
+    #[test]
+    fn values_are_clamped_to_the_range() {
+        let keyframes = Keyframes::new(
+            vec![
+                Keyframe::new(0.0, 0.0, Easing::CubicBezier(0.5, 2.0, 0.5, 2.0)),
+                Keyframe::new(1.0, 10.0, Easing::Linear),
+            ],
+            false,
+            0.0,
+            10.0,
+        );
+
+        assert_eq!(keyframes.calculate(at(0.5)), 10.0);
+    }
+}
@@ -1,55 +1,77 @@
 use basic_waveform::BasicWaveform;
 use bevy::reflect::Reflect;
+pub use brownian_motion::BrownianMotion;
+pub use damped_oscillation::DampedOscillation;
+pub use keyframes::{Easing, Keyframe, Keyframes};
 use nannou::prelude::*;
+pub use perlin_noise::PerlinNoise;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+pub use timeline::Timeline;
 
 mod basic_waveform;
-
-#[derive(Clone, Debug, PartialEq, Reflect)]
+mod brownian_motion;
+mod damped_oscillation;
+mod keyframes;
+mod perlin_noise;
+mod timeline;
+
+// animations measure time from the timeline's origin, so that restarting, seeking and looping
+// the timeline replay them
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
 pub struct Animation {
-    start_time: f64,
     variant: AnimationVariant,
     values: HashMap<String, f32>,
 }
 
-#[derive(Clone, Debug, PartialEq, Reflect)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
 pub enum AnimationVariant {
     BasicWaveform(BasicWaveform),
     Perlin(PerlinNoise),
     Brownian(BrownianMotion),
     Damped(DampedOscillation),
+    Keyframes(Keyframes),
 }
 
-#[derive(Clone, Debug, PartialEq, Reflect)]
-pub struct PerlinNoise {}
-#[derive(Clone, Debug, PartialEq, Reflect)]
-pub struct BrownianMotion {}
-#[derive(Clone, Debug, PartialEq, Reflect)]
-pub struct DampedOscillation {}
-
 impl Animation {
-    pub fn new(time: Time<Virtual>, variant: AnimationVariant) -> Self {
-        let start_time = time.elapsed_secs_f64();
-
+    pub fn new(variant: AnimationVariant) -> Self {
         let values = match variant {
             AnimationVariant::BasicWaveform(ref wf) => wf.values_map(),
-            _ => todo!("only basic waveform supported for now"),
+            AnimationVariant::Perlin(ref noise) => noise.values_map(),
+            AnimationVariant::Brownian(ref motion) => motion.values_map(),
+            AnimationVariant::Damped(ref oscillation) => oscillation.values_map(),
+            AnimationVariant::Keyframes(ref keyframes) => keyframes.values_map(),
         };
 
-        Animation {
-            start_time,
-            variant,
-            values,
+        Animation { variant, values }
+    }
+
+    pub fn calculate(&mut self, time: Time<Virtual>) -> f32 {
+        match &mut self.variant {
+            AnimationVariant::BasicWaveform(wf) => wf.calculate(time),
+            AnimationVariant::Perlin(noise) => noise.calculate(time),
+            AnimationVariant::Brownian(motion) => motion.calculate(time),
+            AnimationVariant::Damped(oscillation) => oscillation.calculate(time),
+            AnimationVariant::Keyframes(keyframes) => keyframes.calculate(time),
         }
     }
 
-    pub fn calculate(&self, time: Time<Virtual>) -> f32 {
+    // seconds after which the animation repeats itself, 0 if it is constant and none if it
+    // never repeats
+    pub fn period(&self) -> Option<f32> {
         match &self.variant {
-            AnimationVariant::BasicWaveform(wf) => wf.calculate(time, self.start_time),
-            _ => todo!("only basic waveform supported for now"),
+            AnimationVariant::BasicWaveform(wf) => Some(wf.period()),
+            AnimationVariant::Keyframes(keyframes) => keyframes.period(),
+            AnimationVariant::Perlin(_)
+            | AnimationVariant::Brownian(_)
+            | AnimationVariant::Damped(_) => None,
         }
     }
 
+    pub fn name(&self) -> String {
+        self.variant.name()
+    }
+
     pub fn update_ui(&mut self, ui: &mut egui::Ui, value: f32, id: &str) {
         ui.push_id(id, |ui| {
             egui::ComboBox::from_label("animation curve")
@@ -83,17 +105,50 @@ impl Animation {
                     ));
                     let triangle_name = triangle.name();
 
+                    let perlin =
+                        AnimationVariant::Perlin(PerlinNoise::new_from_values(&self.values));
+                    let perlin_name = perlin.name();
+
+                    let brownian = AnimationVariant::Brownian(BrownianMotion::new_from_values(
+                        value,
+                        &self.values,
+                    ));
+                    let brownian_name = brownian.name();
+
+                    let damped = AnimationVariant::Damped(DampedOscillation::new_from_values(
+                        value,
+                        &self.values,
+                    ));
+                    let damped_name = damped.name();
+
+                    let keyframes = AnimationVariant::Keyframes(Keyframes::new_from_values(
+                        value,
+                        &self.values,
+                    ));
+                    let keyframes_name = keyframes.name();
+
                     ui.selectable_value(&mut self.variant, sin, sin_name);
                     ui.selectable_value(&mut self.variant, sawtooth, sawtooth_name);
                     ui.selectable_value(&mut self.variant, square, square_name);
                     ui.selectable_value(&mut self.variant, triangle, triangle_name);
-                })
+                    ui.selectable_value(&mut self.variant, perlin, perlin_name);
+                    ui.selectable_value(&mut self.variant, brownian, brownian_name);
+                    ui.selectable_value(&mut self.variant, damped, damped_name);
+                    ui.selectable_value(&mut self.variant, keyframes, keyframes_name);
+                });
+
+            match self.variant {
+                AnimationVariant::BasicWaveform(ref mut wf) => wf.update_ui(ui, &mut self.values),
+                AnimationVariant::Perlin(ref mut noise) => noise.update_ui(ui, &mut self.values),
+                AnimationVariant::Brownian(ref mut motion) => {
+                    motion.update_ui(ui, &mut self.values)
+                }
+                AnimationVariant::Damped(ref mut oscillation) => {
+                    oscillation.update_ui(ui, &mut self.values)
+                }
+                AnimationVariant::Keyframes(ref mut keyframes) => keyframes.update_ui(ui),
+            }
         });
-
-        match self.variant {
-            AnimationVariant::BasicWaveform(ref mut wf) => wf.update_ui(ui, &mut self.values),
-            _ => todo!("only basic waveform supported for now"),
-        }
     }
 }
 
@@ -111,7 +166,19 @@ impl AnimationVariant {
     pub fn name(&self) -> String {
         match self {
             Self::BasicWaveform(wf) => wf.name(),
-            _ => todo!("only basic waveform supported for now"),
+            Self::Perlin(noise) => noise.name(),
+            Self::Brownian(motion) => motion.name(),
+            Self::Damped(oscillation) => oscillation.name(),
+            Self::Keyframes(keyframes) => keyframes.name(),
         }
     }
 }
+
+// deterministic pseudo random value in 0..1 for a seed and an index
+fn hash(seed: u32, index: i64) -> f32 {
+    let mut x = (index as u64) ^ ((seed as u64) << 32);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^= x >> 31;
+    (x >> 40) as f32 / (1u64 << 24) as f32
+}
@@ -0,0 +1,96 @@
+use super::hash;
+use crate::{adjustable_variable::types::Context, ui::add_numeric};
+use bevy::reflect::Reflect;
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+pub struct PerlinNoise {
+    octaves: u32,
+    scale: f32,
+    seed: u32,
+    min: f32,
+    max: f32,
+}
+
+impl PerlinNoise {
+    pub fn new(octaves: u32, scale: f32, seed: u32, min: f32, max: f32) -> Self {
+        Self {
+            octaves,
+            scale,
+            seed,
+            min,
+            max,
+        }
+    }
+
+    pub fn new_from_values(values: &HashMap<String, f32>) -> Self {
+        let octaves = values.get("octaves").unwrap_or(&3.0);
+        let scale = values.get("scale").unwrap_or(&0.5);
+        let seed = values.get("seed").unwrap_or(&0.0);
+        let min = values.get("min").unwrap_or(&0.0);
+        let max = values.get("max").unwrap_or(&1.0);
+        Self::new(*octaves as u32, *scale, *seed as u32, *min, *max)
+    }
+
+    pub(crate) fn calculate(&self, time: Time<Virtual>) -> f32 {
+        let dt = time.elapsed_secs();
+        let amplitude = (self.max - self.min) / 2.0;
+
+        let mut value = 0.0;
+        let mut octave_amplitude = 1.0;
+        let mut total_amplitude = 0.0;
+        let mut x = dt * self.scale;
+
+        for octave in 0..self.octaves.max(1) {
+            value += octave_amplitude * self.noise(x, octave);
+            total_amplitude += octave_amplitude;
+            octave_amplitude *= 0.5;
+            x *= 2.0;
+        }
+
+        let value = (value / total_amplitude).clamp(-1.0, 1.0);
+        self.min + amplitude * (1.0 + value)
+    }
+
+    // 1d gradient noise in -1..=1
+    fn noise(&self, x: f32, octave: u32) -> f32 {
+        let cell = x.floor();
+        let offset = x - cell;
+        let seed = self.seed.wrapping_add(octave.wrapping_mul(0x9e37_79b9));
+
+        let gradient = |cell: f32| hash(seed, cell as i64) * 2.0 - 1.0;
+        let left = gradient(cell) * offset;
+        let right = gradient(cell + 1.0) * (offset - 1.0);
+
+        let fade = offset * offset * offset * (offset * (offset * 6.0 - 15.0) + 10.0);
+
+        // 1d perlin noise stays within -0.5..=0.5
+        2.0 * (left + fade * (right - left))
+    }
+
+    pub(crate) fn update_ui(&mut self, ui: &mut egui::Ui, values: &mut HashMap<String, f32>) {
+        let ctx = Context::new(Default::default());
+        add_numeric(ui, &ctx, "octaves", &mut self.octaves, 1..=8);
+        add_numeric(ui, &ctx, "scale", &mut self.scale, 0.0..=2.0);
+        add_numeric(ui, &ctx, "seed", &mut self.seed, 0..=999);
+        values.insert("octaves".to_string(), self.octaves as f32);
+        values.insert("scale".to_string(), self.scale);
+        values.insert("seed".to_string(), self.seed as f32);
+    }
+
+    pub(crate) fn values_map(&self) -> HashMap<String, f32> {
+        HashMap::from([
+            ("octaves".to_string(), self.octaves as f32),
+            ("scale".to_string(), self.scale),
+            ("seed".to_string(), self.seed as f32),
+            ("min".to_string(), self.min),
+            ("max".to_string(), self.max),
+        ])
+    }
+
+    pub(crate) fn name(&self) -> String {
+        "perlin".to_string()
+    }
+}
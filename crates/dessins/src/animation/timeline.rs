@@ -0,0 +1,86 @@
+use nannou::prelude::*;
+use std::time::Duration;
+
+// shared clock of all animations, it can be paused and scrubbed unlike the app's virtual time
+pub struct Timeline {
+    time: Time<Virtual>,
+    playing: bool,
+    looping: bool,
+    duration: f32,
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self {
+            time: Time::default(),
+            playing: true,
+            looping: false,
+            duration: 10.0,
+        }
+    }
+}
+
+impl Timeline {
+    pub fn time(&self) -> Time<Virtual> {
+        self.time
+    }
+
+    pub fn tick(&mut self, delta: Duration) {
+        if !self.playing {
+            return;
+        }
+
+        self.time.advance_by(delta);
+
+        let elapsed = self.time.elapsed_secs();
+        if self.looping && self.duration > 0.0 && elapsed > self.duration {
+            self.seek(elapsed % self.duration);
+        }
+    }
+
+    pub fn seek(&mut self, position: f32) {
+        // keep the last frame's delta, waveforms derive their sample rate from it
+        let delta = self.time.delta();
+        let position = Duration::from_secs_f32(position.max(0.0));
+
+        let mut time = Time::default();
+        time.advance_to(position.saturating_sub(delta));
+        time.advance_to(position);
+        self.time = time;
+    }
+
+    pub fn update(&mut self, ui: &mut egui::Ui) {
+        ui.label("timeline");
+
+        ui.horizontal(|ui| {
+            if ui
+                .button(if self.playing { "pause" } else { "play" })
+                .clicked()
+            {
+                self.playing = !self.playing;
+            }
+            if ui.button("restart").clicked() {
+                self.seek(0.0);
+            }
+            ui.checkbox(&mut self.looping, "loop");
+        });
+
+        let mut position = self.time.elapsed_secs().min(self.duration);
+        if ui
+            .add(egui::Slider::new(&mut position, 0.0..=self.duration).suffix("s"))
+            .changed()
+        {
+            self.seek(position);
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("duration");
+            ui.add(
+                egui::DragValue::new(&mut self.duration)
+                    .speed(0.1)
+                    .range(0.1..=600.0)
+                    .suffix("s"),
+            );
+        });
+    }
+}
@@ -0,0 +1,353 @@
+use crate::shapes::{Shapes, NP};
+use bevy::color::{Alpha, Hsla, Hue, Mix, Oklaba};
+use evalexpr::{ContextWithMutableVariables, DefaultNumericTypes, HashMapContext, Value};
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::{PI, TAU};
+
+// one colour per point, nested like the shapes
+pub type Colors = Vec<Vec<Vec<Color>>>;
+
+// how the layer's colour spreads over its points
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColorMap {
+    pub mode: ColorMode,
+    pub by: ColorBy, // what the gradient and the palette follow
+    pub end: Color,  // of the gradient, which starts from the layer's colour
+    pub palette: Palette,
+    pub turns: f32,         // of the hue for one turn around the centre
+    pub expression: String, // hue in turns, added to the layer's
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorMode {
+    Solid,
+    Gradient,
+    Palette,
+    Angle,
+    Expression,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorBy {
+    Length, // along the path, from the first point to the last
+    Shape,
+    Segment,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Palette {
+    Rainbow,
+    Viridis,
+    Magma,
+    Ocean,
+    Pastel,
+}
+
+impl ColorMode {
+    pub const ALL: &'static [(Self, &'static str)] = &[
+        (Self::Solid, "solid"),
+        (Self::Gradient, "gradient"),
+        (Self::Palette, "palette"),
+        (Self::Angle, "hue by angle"),
+        (Self::Expression, "expression"),
+    ];
+}
+
+impl ColorBy {
+    pub const ALL: &'static [(Self, &'static str)] = &[
+        (Self::Length, "length"),
+        (Self::Shape, "shape"),
+        (Self::Segment, "segment"),
+    ];
+}
+
+impl Palette {
+    pub const ALL: &'static [(Self, &'static str)] = &[
+        (Self::Rainbow, "rainbow"),
+        (Self::Viridis, "viridis"),
+        (Self::Magma, "magma"),
+        (Self::Ocean, "ocean"),
+        (Self::Pastel, "pastel"),
+    ];
+
+    fn colors(&self) -> &'static [(u8, u8, u8)] {
+        match self {
+            Self::Rainbow => &[
+                (228, 26, 28),
+                (255, 127, 0),
+                (255, 221, 51),
+                (77, 175, 74),
+                (55, 126, 184),
+                (152, 78, 163),
+            ],
+            Self::Viridis => &[
+                (68, 1, 84),
+                (59, 82, 139),
+                (33, 145, 140),
+                (94, 201, 98),
+                (253, 231, 37),
+            ],
+            Self::Magma => &[
+                (0, 0, 4),
+                (81, 18, 124),
+                (183, 55, 121),
+                (252, 137, 97),
+                (252, 253, 191),
+            ],
+            Self::Ocean => &[
+                (8, 29, 88),
+                (37, 52, 148),
+                (29, 145, 192),
+                (127, 205, 187),
+                (237, 248, 177),
+            ],
+            Self::Pastel => &[
+                (251, 180, 174),
+                (179, 205, 227),
+                (204, 235, 197),
+                (222, 203, 228),
+                (254, 217, 166),
+            ],
+        }
+    }
+
+    fn color(&self, index: usize) -> Color {
+        let colors = self.colors();
+        let (red, green, blue) = colors[index % colors.len()];
+        Color::srgb_u8(red, green, blue)
+    }
+
+    // t from 0 to 1 goes through every colour once
+    fn sample(&self, t: f32) -> Color {
+        let last = self.colors().len() - 1;
+        let position = t.clamp(0.0, 1.0) * last as f32;
+        let index = (position.floor() as usize).min(last - 1);
+        mix(
+            self.color(index),
+            self.color(index + 1),
+            position - index as f32,
+        )
+    }
+}
+
+impl ColorMap {
+    pub fn is_solid(&self) -> bool {
+        self.mode == ColorMode::Solid
+    }
+
+    pub fn colors(&self, base: Color, shapes: &Shapes) -> Colors {
+        let lengths = length_fractions(shapes);
+        let shape_count = shapes.len();
+        let segment_count = shapes.iter().map(Vec::len).sum::<usize>();
+
+        let expression = match self.mode {
+            ColorMode::Expression => {
+                evalexpr::build_operator_tree::<DefaultNumericTypes>(&self.expression).ok()
+            }
+            _ => None,
+        };
+        let mut ctx = HashMapContext::<DefaultNumericTypes>::new();
+        set(&mut ctx, "pi", PI);
+
+        let mut colors = Colors::with_capacity(shape_count);
+        let mut segment_index = 0;
+        for (shape_index, (shape, shape_lengths)) in shapes.iter().zip(&lengths).enumerate() {
+            let mut shape_colors = Vec::with_capacity(shape.len());
+            for (segment, segment_lengths) in shape.iter().zip(shape_lengths) {
+                let (t, index) = match self.by {
+                    ColorBy::Length => (None, None),
+                    ColorBy::Shape => (Some(fraction(shape_index, shape_count)), Some(shape_index)),
+                    ColorBy::Segment => (
+                        Some(fraction(segment_index, segment_count)),
+                        Some(segment_index),
+                    ),
+                };
+
+                let segment_colors = segment
+                    .iter()
+                    .zip(segment_lengths)
+                    .map(|(point, length)| {
+                        let t = t.unwrap_or(*length);
+                        match self.mode {
+                            ColorMode::Solid => base,
+                            ColorMode::Gradient => mix(base, self.end, t),
+                            ColorMode::Palette => index
+                                .map(|index| self.palette.color(index))
+                                .unwrap_or_else(|| self.palette.sample(t))
+                                .with_alpha(base.alpha()),
+                            ColorMode::Angle => {
+                                let angle = point.y.atan2(point.x).rem_euclid(TAU);
+                                rotate_hue(base, self.turns * angle / TAU)
+                            }
+                            ColorMode::Expression => {
+                                let Some(expression) = &expression else {
+                                    return base;
+                                };
+                                let point = *point / (NP as f32 / 2.0);
+                                set(&mut ctx, "x", point.x);
+                                set(&mut ctx, "y", point.y);
+                                set(&mut ctx, "r", point.length());
+                                set(&mut ctx, "θ", point.y.atan2(point.x));
+                                set(&mut ctx, "theta", point.y.atan2(point.x));
+                                set(&mut ctx, "t", *length);
+                                set(&mut ctx, "shape", shape_index as f32);
+                                set(&mut ctx, "segment", segment_index as f32);
+                                match expression.eval_number_with_context(&ctx) {
+                                    Ok(turns) => rotate_hue(base, turns as f32),
+                                    Err(_) => base,
+                                }
+                            }
+                        }
+                    })
+                    .collect();
+
+                shape_colors.push(segment_colors);
+                segment_index += 1;
+            }
+            colors.push(shape_colors);
+        }
+
+        colors
+    }
+
+    // returns whether the colours have to be computed again
+    pub fn update(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+
+        ui.label("color mode");
+        ui.horizontal_wrapped(|ui| {
+            for (mode, name) in ColorMode::ALL {
+                changed |= ui.radio_value(&mut self.mode, *mode, *name).changed();
+            }
+        });
+
+        if matches!(self.mode, ColorMode::Gradient | ColorMode::Palette) {
+            ui.horizontal(|ui| {
+                ui.label("by");
+                for (by, name) in ColorBy::ALL {
+                    changed |= ui.radio_value(&mut self.by, *by, *name).changed();
+                }
+            });
+        }
+
+        match self.mode {
+            ColorMode::Solid => {}
+            ColorMode::Gradient => {
+                ui.horizontal(|ui| {
+                    ui.label("to");
+                    let mut end = self.end.to_srgba().to_f32_array();
+                    if ui.color_edit_button_rgba_unmultiplied(&mut end).changed() {
+                        let [red, green, blue, alpha] = end;
+                        self.end = Color::srgba(red, green, blue, alpha);
+                        changed = true;
+                    }
+                });
+            }
+            ColorMode::Palette => {
+                ui.horizontal_wrapped(|ui| {
+                    for (palette, name) in Palette::ALL {
+                        changed |= ui.radio_value(&mut self.palette, *palette, *name).changed();
+                    }
+                });
+            }
+            ColorMode::Angle => {
+                ui.label("turns of the hue");
+                changed |= ui
+                    .add(egui::Slider::new(&mut self.turns, -6.0..=6.0))
+                    .changed();
+            }
+            ColorMode::Expression => {
+                ui.label("hue (turns)");
+                let response = ui.text_edit_singleline(&mut self.expression);
+                changed |= response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                response.on_hover_text("of x, y, r, θ, t (along the path), shape and segment");
+                if evalexpr::build_operator_tree::<DefaultNumericTypes>(&self.expression).is_err() {
+                    ui.label("invalid expression");
+                }
+            }
+        }
+
+        changed
+    }
+}
+
+impl Default for ColorMap {
+    fn default() -> Self {
+        Self {
+            mode: ColorMode::Solid,
+            by: ColorBy::Length,
+            end: Color::WHITE,
+            palette: Palette::Rainbow,
+            turns: 1.0,
+            expression: "r + t".to_string(),
+        }
+    }
+}
+
+// the pieces of a segment drawn with a single colour, each line taking its first point's
+pub fn runs<'a>(segment: &'a [Vec2], colors: &[Color]) -> Vec<(Color, &'a [Vec2])> {
+    let mut runs = vec![];
+    let mut start = 0;
+
+    for end in 1..segment.len() {
+        if end + 1 == segment.len() || colors[end] != colors[start] {
+            runs.push((colors[start], &segment[start..=end]));
+            start = end;
+        }
+    }
+
+    runs
+}
+
+// the length travelled along the path to each point, as a fraction of the whole
+fn length_fractions(shapes: &Shapes) -> Vec<Vec<Vec<f32>>> {
+    let mut total = 0.0;
+    let mut lengths = shapes
+        .iter()
+        .map(|shape| {
+            shape
+                .iter()
+                .map(|segment| {
+                    let mut previous = segment.first().copied();
+                    segment
+                        .iter()
+                        .map(|point| {
+                            if let Some(previous) = previous {
+                                total += previous.distance(*point);
+                            }
+                            previous = Some(*point);
+                            total
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    if total > 0.0 {
+        lengths
+            .iter_mut()
+            .flatten()
+            .flatten()
+            .for_each(|length| *length /= total);
+    }
+    lengths
+}
+
+fn fraction(index: usize, count: usize) -> f32 {
+    index as f32 / count.saturating_sub(1).max(1) as f32
+}
+
+fn mix(from: Color, to: Color, t: f32) -> Color {
+    Oklaba::from(from).mix(&Oklaba::from(to), t).into()
+}
+
+fn rotate_hue(color: Color, turns: f32) -> Color {
+    Hsla::from(color).rotate_hue(turns * 360.0).into()
+}
+
+fn set(ctx: &mut HashMapContext<DefaultNumericTypes>, key: &str, value: f32) {
+    ctx.set_value(key.to_string(), Value::Float(value as f64))
+        .expect("setting to value of same type each time");
+}
@@ -1,13 +1,14 @@
-use super::{Polygon, Star};
 use crate::{
     adjustable_dessin::AdjustableDessin,
     adjustable_variable::types::{F32Variant, F32, U32},
-    shapes::{Segment, Shape, Shapes},
+    shapes::Shapes,
 };
 use adjustable_dessin_derive::DefaultAdjustableDessin;
+use dessins_core::dessin_variant::{composition_1, polygon, star};
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Reflect, DefaultAdjustableDessin)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize, DefaultAdjustableDessin)]
 #[reflect(Default)]
 pub struct Composition1 {
     pub polygon_k: U32,
@@ -21,53 +22,37 @@ pub struct Composition1 {
 
 impl Composition1 {
     pub fn calculate_shapes(&mut self) -> Shapes {
-        let mut shapes = Shapes::new();
-        let mut shape = Shape::new();
-
-        let polygon = Polygon {
-            k: self.polygon_k.clone(),
-            r: self.polygon_r.clone(),
-            ad: self.polygon_ad.clone(),
-        };
-
-        let star = Star {
-            k: self.star_k.clone(),
-            h: self.star_h.clone(),
-            r: self.star_r.clone(),
-            ad: self.star_ad.clone(),
-        };
-
-        for i in 0..polygon.k.get_value() {
-            let polygon_point = polygon.calculate_point(i);
-
-            let mut segment = Segment::new();
-
-            for j in 0..star.k.get_value() {
-                let star_point = star.calculate_point(j);
-                let point = star_point + polygon_point;
-                segment.push(point);
-            }
-
-            segment.push(segment[0]);
+        self.params().calculate_shapes()
+    }
 
-            shape.push(segment);
+    pub fn params(&self) -> composition_1::Composition1 {
+        composition_1::Composition1 {
+            polygon: polygon::Polygon {
+                k: self.polygon_k.get_value(),
+                r: self.polygon_r.get_value(),
+                ad: self.polygon_ad.get_value(),
+            },
+            star: star::Star {
+                k: self.star_k.get_value(),
+                h: self.star_h.get_value(),
+                r: self.star_r.get_value(),
+                ad: self.star_ad.get_value(),
+            },
         }
-
-        shapes.push(shape);
-        shapes
     }
 }
 
 impl Default for Composition1 {
     fn default() -> Self {
+        let composition_1::Composition1 { polygon, star } = composition_1::Composition1::default();
         Self {
-            polygon_k: U32::new(5, 3..=20),
-            polygon_r: F32::new(0.27, F32Variant::Length),
-            polygon_ad: F32::new(0.5, F32Variant::Angle),
-            star_k: U32::new(25, 5..=100),
-            star_h: U32::new(12, 3..=5),
-            star_r: F32::new(0.22, F32Variant::Length),
-            star_ad: F32::new(0.5, F32Variant::Angle),
+            polygon_k: U32::new(polygon.k, 3..=20),
+            polygon_r: F32::from_raw(polygon.r, F32Variant::Length),
+            polygon_ad: F32::from_raw(polygon.ad, F32Variant::Angle),
+            star_k: U32::new(star.k, 5..=100),
+            star_h: U32::new(star.h, 3..=5),
+            star_r: F32::from_raw(star.r, F32Variant::Length),
+            star_ad: F32::from_raw(star.ad, F32Variant::Angle),
         }
     }
 }
@@ -1,13 +1,15 @@
 use crate::{
     adjustable_dessin::AdjustableDessin,
     adjustable_variable::types::{Context, ExpressionF32, F32Variant, F32, U32},
-    shapes::{Segment, Shape, Shapes},
+    shapes::Shapes,
 };
 use adjustable_dessin_derive::DefaultAdjustableDessin;
+use dessins_core::dessin_variant::curve::orbital;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Clone, Debug, PartialEq, Reflect, DefaultAdjustableDessin)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize, DefaultAdjustableDessin)]
 #[reflect(Default)]
 pub struct Orbital {
     pub n: U32,  // # segments
@@ -26,47 +28,40 @@ pub struct R2Orbital {
 
 impl Orbital {
     pub fn calculate_shapes(&mut self) -> Shapes {
-        let mut shapes = Shapes::new();
-        let mut shape = Shape::new();
-        let mut segment = Segment::new();
+        self.r2.insert_ctx_entry("n", self.n.get_value() as f32);
 
-        let n = self.n.get_value() as f32;
-        let t1 = self.t1.get_value() as f32;
-        let t2 = self.t2.get_value() as f32;
-        let r1 = self.r1.get_value();
-        let k1 = self.k1.get_value() as f32;
-        let k2 = self.k2.get_value() as f32;
-
-        for i in 0..=n as usize {
-            let i = i as f32;
-
-            self.r2.insert_ctx_entry("n", self.n.get_value() as f32);
+        self.params().calculate_shapes(|i| {
             self.r2.insert_ctx_entry("i", i);
-            let r2 = self.r2.eval_expr();
-
-            let a1 = 2.0 * PI * i / n * t1;
-            let a2 = 2.0 * PI * i / n * t2;
-
-            let x = r1 * (k1 * a1).cos() + r2 * a2.cos();
-            let y = r1 * (k2 * a1).sin() + r2 * a2.sin();
+            self.r2.eval_expr()
+        })
+    }
 
-            segment.push(pt2(x, y));
+    pub fn params(&self) -> orbital::Orbital {
+        orbital::Orbital {
+            n: self.n.get_value(),
+            t1: self.t1.get_value(),
+            t2: self.t2.get_value(),
+            r1: self.r1.get_value(),
+            k1: self.k1.get_value(),
+            k2: self.k2.get_value(),
         }
-
-        shape.push(segment);
-        shapes.push(shape);
-
-        shapes
     }
 
     fn default_r2_expr() -> String {
-        "96.0 * (1 - i / n)".to_string()
+        orbital::Orbital::R2.to_string()
     }
 }
 
 impl Default for Orbital {
     fn default() -> Self {
-        let n = 2000;
+        let orbital::Orbital {
+            n,
+            t1,
+            t2,
+            r1,
+            k1,
+            k2,
+        } = orbital::Orbital::default();
         let ctx = Context::new(&[("n".to_string(), n as f32)]);
         let r2 = ExpressionF32::new(
             Orbital::default_r2_expr(),
@@ -79,11 +74,11 @@ impl Default for Orbital {
         );
         Self {
             n: U32::new(n, 1000..=6000),
-            t1: U32::new(2, 1..=600),
-            t2: U32::new(100, 1..=600),
-            r1: F32::new(0.25, F32Variant::Length),
-            k1: U32::new(1, 1..=4),
-            k2: U32::new(1, 1..=4),
+            t1: U32::new(t1, 1..=600),
+            t2: U32::new(t2, 1..=600),
+            r1: F32::from_raw(r1, F32Variant::Length),
+            k1: U32::new(k1, 1..=4),
+            k2: U32::new(k2, 1..=4),
             r2,
         }
     }
@@ -1,13 +1,15 @@
 use crate::{
     adjustable_dessin::AdjustableDessin,
     adjustable_variable::types::{Context, ExpressionF32, F32Variant, F32, U32},
-    shapes::{Segment, Shape, Shapes},
+    shapes::Shapes,
 };
 use adjustable_dessin_derive::DefaultAdjustableDessin;
+use dessins_core::dessin_variant::curve::rotating;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Clone, Debug, PartialEq, Reflect, DefaultAdjustableDessin)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize, DefaultAdjustableDessin)]
 #[reflect(Default)]
 pub struct Rotating {
     pub n: U32,  // # segments
@@ -24,53 +26,46 @@ pub struct Rotating {
 
 impl Rotating {
     pub fn calculate_shapes(&mut self) -> Shapes {
-        let mut shapes = Shapes::new();
-        let mut shape = Shape::new();
-        let mut segment = Segment::new();
+        self.s.insert_ctx_entry("n", self.n.get_value() as f32);
 
-        let n = self.n.get_value() as f32;
-        let t1 = self.t1.get_value();
-        let t2 = self.t2.get_value();
-        let r1 = self.r1.get_value();
-        let k1 = self.k1.get_value() as f32;
-        let k2 = self.k2.get_value() as f32;
-        let r2 = self.r2.get_value();
-        let h1 = self.h1.get_value() as f32;
-        let h2 = self.h2.get_value() as f32;
-
-        for i in 0..=n as usize {
-            let i = i as f32;
-
-            self.s.insert_ctx_entry("n", self.n.get_value() as f32);
+        self.params().calculate_shapes(|i| {
             self.s.insert_ctx_entry("i", i);
-            let s = self.s.eval_expr();
-
-            let an = 2.0 * PI * i / n;
-            let c1 = (h1 * an * t1).cos();
-            let s1 = (h2 * an * t1).sin();
-            let c2 = s * (k1 * an * t2).cos();
-            let s2 = s * (k2 * an * t2).sin();
-
-            let x = r1 * c1 + r2 * (c1 * c2 - s1 * s2);
-            let y = r1 * s1 + r2 * (s1 * c2 + c1 * s2);
+            self.s.eval_expr()
+        })
+    }
 
-            segment.push(pt2(x, y));
+    pub fn params(&self) -> rotating::Rotating {
+        rotating::Rotating {
+            n: self.n.get_value(),
+            t1: self.t1.get_value(),
+            t2: self.t2.get_value(),
+            r1: self.r1.get_value(),
+            k1: self.k1.get_value(),
+            k2: self.k2.get_value(),
+            r2: self.r2.get_value(),
+            h1: self.h1.get_value(),
+            h2: self.h2.get_value(),
         }
-
-        shape.push(segment);
-        shapes.push(shape);
-
-        shapes
     }
 
     fn default_s_expr() -> String {
-        "math::cos(4.0 * pi * i / n) * 0.4 + 0.6".to_string()
+        rotating::Rotating::S.to_string()
     }
 }
 
 impl Default for Rotating {
     fn default() -> Self {
-        let n = 2000;
+        let rotating::Rotating {
+            n,
+            t1,
+            t2,
+            r1,
+            k1,
+            k2,
+            r2,
+            h1,
+            h2,
+        } = rotating::Rotating::default();
         let ctx = Context::new(&[("n".to_string(), n as f32), ("pi".to_string(), PI)]);
         let s = ExpressionF32::new(
             Rotating::default_s_expr(),
@@ -83,14 +78,14 @@ impl Default for Rotating {
         );
         Self {
             n: U32::new(n, 1000..=6000),
-            t1: F32::new(1.0, F32Variant::None(0.5..=600.0)),
-            t2: F32::new(100.0, F32Variant::None(0.5..=600.0)),
-            r1: F32::new(1.0 / 6.0, F32Variant::Length),
-            k1: U32::new(1, 1..=4),
-            k2: U32::new(1, 1..=4),
-            r2: F32::new(1.0 / 4.0, F32Variant::Length),
-            h1: U32::new(1, 1..=4),
-            h2: U32::new(1, 1..=4),
+            t1: F32::from_raw(t1, F32Variant::None(0.5..=600.0)),
+            t2: F32::from_raw(t2, F32Variant::None(0.5..=600.0)),
+            r1: F32::from_raw(r1, F32Variant::Length),
+            k1: U32::new(k1, 1..=4),
+            k2: U32::new(k2, 1..=4),
+            r2: F32::from_raw(r2, F32Variant::Length),
+            h1: U32::new(h1, 1..=4),
+            h2: U32::new(h2, 1..=4),
             s,
         }
     }
@@ -1,12 +1,14 @@
 use crate::{
     adjustable_dessin::AdjustableDessin,
     adjustable_variable::types::{F32Variant, F32, U32},
-    shapes::{Segment, Shape, Shapes, NP},
+    shapes::Shapes,
 };
 use adjustable_dessin_derive::DefaultAdjustableDessin;
+use dessins_core::dessin_variant::curve::spiral;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Reflect, DefaultAdjustableDessin)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize, DefaultAdjustableDessin)]
 #[reflect(Default)]
 pub struct Spiral {
     pub n: U32, // # segments
@@ -18,53 +20,35 @@ pub struct Spiral {
 
 impl Spiral {
     pub fn calculate_shapes(&mut self) -> Shapes {
-        let mut shapes = Shapes::new();
-        let mut shape = Shape::new();
-        let mut segment = Segment::new();
-
-        let np = NP as f32;
-        let n = self.n.get_value() as f32;
-        let t = self.t.get_value() as f32;
-        let r = self.r.get_value();
-        let l = self.l.get_value();
-        let an_factor = self.an_factor.get_value();
-
-        for i in 0..=n as usize {
-            let i = i as f32;
-
-            let rr = l.powf(i / n);
-            let an = 2.0 * PI * i / n * an_factor;
-
-            let x = rr * (t * an).cos();
-            let y = rr * r * (t * an).sin();
-
-            let co = an.cos();
-            let si = an.sin();
-
-            let xx = x * co - y * si;
-            let yy = x * si + y * co;
-
-            let x = xx * np / 2.0;
-            let y = yy * np / 2.0;
+        self.params().calculate_shapes()
+    }
 
-            segment.push(pt2(x, y));
+    pub fn params(&self) -> spiral::Spiral {
+        spiral::Spiral {
+            n: self.n.get_value(),
+            t: self.t.get_value(),
+            r: self.r.get_value(),
+            l: self.l.get_value(),
+            an_factor: self.an_factor.get_value(),
         }
-
-        shape.push(segment);
-        shapes.push(shape);
-
-        shapes
     }
 }
 
 impl Default for Spiral {
     fn default() -> Self {
+        let spiral::Spiral {
+            n,
+            t,
+            r,
+            l,
+            an_factor,
+        } = spiral::Spiral::default();
         Self {
-            n: U32::new(2000, 1000..=9000),
-            t: U32::new(40, 40..=60),
-            r: F32::new(0.8, F32Variant::None(0.1..=2.0)),
-            l: F32::new(0.1, F32Variant::None(0.1..=2.0)),
-            an_factor: F32::new(1.0, F32Variant::None(1.0..=4.0)),
+            n: U32::new(n, 1000..=9000),
+            t: U32::new(t, 40..=60),
+            r: F32::from_raw(r, F32Variant::None(0.1..=2.0)),
+            l: F32::from_raw(l, F32Variant::None(0.1..=2.0)),
+            an_factor: F32::from_raw(an_factor, F32Variant::None(1.0..=4.0)),
         }
     }
 }
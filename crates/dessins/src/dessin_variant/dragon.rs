@@ -1,12 +1,14 @@
 use crate::{
     adjustable_dessin::AdjustableDessin,
     adjustable_variable::types::{Context, ExpressionF32, Pt2, VecU32, U32},
-    shapes::{Segment, Shape, Shapes, NP},
+    shapes::Shapes,
 };
 use adjustable_dessin_derive::DefaultAdjustableDessin;
+use dessins_core::dessin_variant::dragon;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Reflect, DefaultAdjustableDessin)]
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize, DefaultAdjustableDessin)]
 #[reflect(Default)]
 pub struct Dragon {
     pub n: U32,            // depth of recursion
@@ -18,10 +20,6 @@ pub struct Dragon {
 
 impl Dragon {
     pub fn calculate_shapes(&mut self) -> Shapes {
-        let mut shapes = Shapes::new();
-        let mut shape = Shape::new();
-        let mut segment = Segment::new();
-
         let n = self.n.get_value() as usize;
 
         if n != self.rules.get_value().len() - 1 {
@@ -32,83 +30,42 @@ impl Dragon {
             self.a0.eval_expr();
         }
 
-        let p0 = self.p0.get_value();
-        let l0 = self.l0.get_value();
-        let a0 = self.a0.get_value();
-
-        segment.push(p0);
-
-        let mut p0 = p0;
-        let mut p1 = p0;
-        let mut p2 = p0;
-
-        let mut current_angle = a0;
-
-        let nn = 2_i32.pow(n as u32) - 1;
-
-        fn step_segment(p0: &mut Point2, p1: &mut Point2, p2: &mut Point2, step: Point2) {
-            *p0 = *p1;
-            *p1 = *p2;
-            *p2 += step;
-        }
-
-        for i in 0..=nn {
-            if i == 0 {
-                step_segment(
-                    &mut p0,
-                    &mut p1,
-                    &mut p2,
-                    pt2(l0 * current_angle.cos(), l0 * current_angle.sin()),
-                );
-            } else {
-                let mut ii = i;
-                let mut j = 0;
-
-                while ii % 2 == 0 {
-                    ii /= 2;
-                    j += 1;
-                }
-
-                let aa = (self.rules.get_value()[n - j].get_value() as i32 * 2 - 1) as f32
-                    * ((((ii - 1) / 2) % 2) * 2 - 1) as f32
-                    * PI
-                    / 2.0;
-                current_angle += aa;
-
-                step_segment(
-                    &mut p0,
-                    &mut p1,
-                    &mut p2,
-                    pt2(l0 * current_angle.cos(), l0 * current_angle.sin()),
-                );
-            }
+        self.params().calculate_shapes()
+    }
 
-            segment.push((p0 + pt2(3.0, 3.0) * p1) / pt2(4.0, 4.0));
-            segment.push((p2 + pt2(3.0, 3.0) * p1) / pt2(4.0, 4.0));
+    pub fn params(&self) -> dragon::Dragon {
+        dragon::Dragon {
+            n: self.n.get_value(),
+            l0: self.l0.get_value(),
+            a0: self.a0.get_value(),
+            p0: self.p0.get_value(),
+            rules: self
+                .rules
+                .get_value()
+                .iter()
+                .map(|rule| rule.get_value())
+                .collect(),
         }
-
-        shape.push(segment);
-        shapes.push(shape);
-
-        shapes
     }
 
     fn default_l0_expr() -> String {
-        "480 / (math::sqrt(2.0) ^ n)".to_string()
+        dragon::Dragon::L0.to_string()
     }
 
     fn default_a0_expr() -> String {
-        "-pi / 4 * (n - 2)".to_string()
+        dragon::Dragon::A0.to_string()
     }
 }
 
 impl Default for Dragon {
     fn default() -> Self {
-        let n = 6;
-        let rules = vec![0; n as usize + 1];
-        let l0_fn = |n: u32| NP as f32 / (2.0_f32.sqrt().powf(n as f32));
-        let a0_fn = |n: u32| (n - 2) as f32 * -PI / 4.0;
-        let p0_fn = || pt2(-(NP as f32) / 6.0, -(NP as f32) / 2.5);
+        let dragon::Dragon {
+            n,
+            l0,
+            a0,
+            p0,
+            rules,
+        } = dragon::Dragon::default();
 
         let ctx = Context::new(&[("n".to_string(), n as f32)]);
         let l0 = ExpressionF32::new(
@@ -116,7 +73,7 @@ impl Default for Dragon {
             Self::default_l0_expr(),
             ctx,
             Default::default(),
-            l0_fn(n),
+            l0,
             0.0..=300.0,
             1.0,
         );
@@ -124,10 +81,10 @@ impl Default for Dragon {
         let ctx = Context::new(&[("n".to_string(), n as f32), ("pi".to_string(), PI)]);
         let a0 = ExpressionF32::new(
             Self::default_a0_expr(),
-            Self::default_l0_expr(),
+            Self::default_a0_expr(),
             ctx,
             Default::default(),
-            a0_fn(n),
+            a0,
             -180.0..=180.0,
             1.0,
         );
@@ -136,7 +93,7 @@ impl Default for Dragon {
             n: U32::new(n, 2..=14),
             l0,
             a0,
-            p0: Pt2::new(p0_fn()),
+            p0: Pt2::new(p0),
             rules: VecU32::new(rules, 0..=1),
         }
     }
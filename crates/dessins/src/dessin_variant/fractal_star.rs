@@ -1,12 +1,14 @@
 use crate::{
     adjustable_dessin::AdjustableDessin,
     adjustable_variable::types::{F32Variant, Pt2, F32, U32},
-    shapes::{Segment, Shape, Shapes},
+    shapes::Shapes,
 };
 use adjustable_dessin_derive::DefaultAdjustableDessin;
+use dessins_core::dessin_variant::fractal_star;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Reflect, DefaultAdjustableDessin)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize, DefaultAdjustableDessin)]
 #[reflect(Default)]
 pub struct FractalStar {
     pub n: U32,
@@ -20,60 +22,41 @@ pub struct FractalStar {
 
 impl FractalStar {
     pub fn calculate_shapes(&mut self) -> Shapes {
-        let mut shapes = Shapes::new();
-        let mut shape = Shape::new();
-        let mut segment = Segment::new();
-
-        let mut p0 = self.p0.get_value();
-        let mut a0 = self.a0.get_value();
-
-        let n = self.n.get_value();
-        let k = self.k.get_value();
-
-        let nn = n * (n - 1).pow(k - 1) - 1;
-
-        for i in 0..=nn {
-            let mut i1 = i;
-            let mut h = 0;
-
-            while i1 % (n - 1) == 0 && h < (k - 1) {
-                i1 /= n - 1;
-                h += 1;
-            }
-
-            let l0 = self.ll.get_value() * self.ra.get_value().powf((k - 1 - h) as f32);
-            a0 += self.aa.get_value();
-
-            let point = p0 + pt2(l0 * a0.cos(), l0 * a0.sin());
+        self.params().calculate_shapes()
+    }
 
-            segment.push(point);
-            p0 = point;
+    pub fn params(&self) -> fractal_star::FractalStar {
+        fractal_star::FractalStar {
+            n: self.n.get_value(),
+            k: self.k.get_value(),
+            ra: self.ra.get_value(),
+            ll: self.ll.get_value(),
+            aa: self.aa.get_value(),
+            p0: self.p0.get_value(),
+            a0: self.a0.get_value(),
         }
-
-        shape.push(segment);
-        shapes.push(shape);
-
-        shapes
     }
 }
 
 impl Default for FractalStar {
     fn default() -> Self {
-        let aa = F32::new(4.0 / 5.0, F32Variant::Angle);
-        let ll = F32::new(1.0, F32Variant::Length);
-
-        let mut a0 = aa.clone();
-        a0.set_value(-a0.get_value());
-        let p0 = Pt2::new(pt2((-ll.get_value()) / 2.0, 0.0));
-
-        Self {
-            n: U32::new(5, 3..=20),
-            k: U32::new(5, 2..=12),
-            ra: F32::new(0.35, F32Variant::None(0.2..=1.8)),
+        let fractal_star::FractalStar {
+            n,
+            k,
+            ra,
             ll,
             aa,
-            a0,
             p0,
+            a0,
+        } = fractal_star::FractalStar::default();
+        Self {
+            n: U32::new(n, 3..=20),
+            k: U32::new(k, 2..=12),
+            ra: F32::from_raw(ra, F32Variant::None(0.2..=1.8)),
+            ll: F32::from_raw(ll, F32Variant::Length),
+            aa: F32::from_raw(aa, F32Variant::Angle),
+            p0: Pt2::new(p0),
+            a0: F32::from_raw(a0, F32Variant::Angle),
         }
     }
 }
@@ -1,12 +1,14 @@
 use crate::{
     adjustable_dessin::AdjustableDessin,
     adjustable_variable::types::{F32Variant, F32, U32},
-    shapes::{Segment, Shape, Shapes},
+    shapes::Shapes,
 };
 use adjustable_dessin_derive::DefaultAdjustableDessin;
+use dessins_core::dessin_variant::jolygon;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Reflect, DefaultAdjustableDessin)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize, DefaultAdjustableDessin)]
 #[reflect(Default)]
 pub struct Jolygon {
     pub k: U32,  // # segments
@@ -18,62 +20,29 @@ pub struct Jolygon {
 
 impl Jolygon {
     pub fn calculate_shapes(&mut self) -> Shapes {
-        let mut shapes = Shapes::new();
-        let mut shape = Shape::new();
-        let mut segment = Segment::new();
-
-        let mut current_length = self.rr.get_value();
-        let mut current_pos = pt2(0.0, 0.0);
-        segment.push(current_pos);
-
-        let mut min_x: f32 = 0.0;
-        let mut max_x: f32 = 0.0;
-        let mut min_y: f32 = 0.0;
-        let mut max_y: f32 = 0.0;
-
-        for i in 0..self.k.get_value() {
-            let angle = self.aa.get_value() + i as f32 * self.an.get_value();
-
-            let dx = current_length * angle.cos();
-            let dy = current_length * angle.sin();
-            let d = pt2(dx, dy);
-            let point = current_pos + d;
-
-            // update bounds
-            min_x = min_x.min(point.x);
-            max_x = max_x.max(point.x);
-            min_y = min_y.min(point.y);
-            max_y = max_y.max(point.y);
-
-            segment.push(point);
-            current_pos = point;
-            current_length *= self.ra.get_value();
-        }
-
-        // calculate center offset
-        let center_offset_x = (min_x + max_x) / 2.0;
-        let center_offset_y = (min_y + max_y) / 2.0;
+        self.params().calculate_shapes()
+    }
 
-        // make segments centered
-        for point in &mut segment {
-            *point -= pt2(center_offset_x, center_offset_y);
+    pub fn params(&self) -> jolygon::Jolygon {
+        jolygon::Jolygon {
+            k: self.k.get_value(),
+            an: self.an.get_value(),
+            ra: self.ra.get_value(),
+            aa: self.aa.get_value(),
+            rr: self.rr.get_value(),
         }
-
-        shape.push(segment);
-        shapes.push(shape);
-
-        shapes
     }
 }
 
 impl Default for Jolygon {
     fn default() -> Self {
+        let jolygon::Jolygon { k, an, ra, aa, rr } = jolygon::Jolygon::default();
         Self {
-            k: U32::new(200, 1..=2500),
-            an: F32::new(15.0 / 31.0, F32Variant::Angle),
-            ra: F32::new(0.98, F32Variant::None(0.9..=1.0)),
-            aa: F32::new(0.0, F32Variant::Angle),
-            rr: F32::new(0.8, F32Variant::Length),
+            k: U32::new(k, 1..=2500),
+            an: F32::from_raw(an, F32Variant::Angle),
+            ra: F32::from_raw(ra, F32Variant::None(0.9..=1.0)),
+            aa: F32::from_raw(aa, F32Variant::Angle),
+            rr: F32::from_raw(rr, F32Variant::Length),
         }
     }
 }
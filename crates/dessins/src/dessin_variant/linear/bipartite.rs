@@ -1,15 +1,14 @@
 use crate::{
     adjustable_dessin::AdjustableDessin,
     adjustable_variable::types::{Pt2, U32},
-    shapes::{Segment, Shape, Shapes, NP},
+    shapes::Shapes,
 };
-use nannou::prelude::*;
-
-pub type OuterSegment = Segment;
-pub type InnerSegment = Segment;
 use adjustable_dessin_derive::DefaultAdjustableDessin;
+use dessins_core::dessin_variant::linear::bipartite;
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Reflect, DefaultAdjustableDessin)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize, DefaultAdjustableDessin)]
 #[reflect(Default)]
 pub struct Bipartite {
     pub n: U32,
@@ -27,60 +26,29 @@ pub struct YBipartite {
 
 impl Bipartite {
     pub fn calculate_shapes(&mut self) -> Shapes {
-        let mut shapes = Shapes::new();
-        let mut shape = Shape::new();
-
-        let (outer_points, inner_points) = self.calculate_points();
-
-        for outer in &outer_points {
-            for inner in &inner_points {
-                let segment = vec![*outer, *inner];
-                shape.push(segment);
-            }
-        }
-
-        shapes.push(shape);
-
-        shapes
+        self.params().calculate_shapes()
     }
 
-    pub fn calculate_points(&self) -> (OuterSegment, InnerSegment) {
-        let mut outer_segment = vec![];
-        let mut inner_segment = vec![];
-
-        let n = self.n.get_value() as f32;
-        let a = self.a.get_value();
-        let b = self.b.get_value();
-        let c = self.c.get_value();
-        let d = self.d.get_value();
-
-        for i in 0..=self.n.get_value() {
-            let i = i as f32;
-            let x1 = (i * a.x + (n - i) * b.x) / n;
-            let y1 = (i * a.y + (n - i) * b.y) / n;
-            outer_segment.push(pt2(x1, y1));
-
-            for j in 0..=self.n.get_value() {
-                let j = j as f32;
-
-                let x2 = (j * c.x + (n - j) * d.x) / n;
-                let y2 = (j * c.y + (n - j) * d.y) / n;
-                inner_segment.push(pt2(x2, y2));
-            }
+    pub fn params(&self) -> bipartite::Bipartite {
+        bipartite::Bipartite {
+            n: self.n.get_value(),
+            a: self.a.get_value(),
+            b: self.b.get_value(),
+            c: self.c.get_value(),
+            d: self.d.get_value(),
         }
-
-        (outer_segment, inner_segment)
     }
 }
 
 impl Default for Bipartite {
     fn default() -> Self {
+        let bipartite::Bipartite { n, a, b, c, d } = bipartite::Bipartite::default();
         Self {
-            n: U32::new(10, 10..=20),
-            a: Pt2::new(pt2((NP as f32) / -2.0, (NP as f32) / -2.0)),
-            b: Pt2::new(pt2((NP as f32) / -2.0, (NP as f32) / 2.0)),
-            c: Pt2::new(pt2((NP as f32) / 2.0, (NP as f32) / -2.0)),
-            d: Pt2::new(pt2((NP as f32) / 2.0, (NP as f32) / 2.0)),
+            n: U32::new(n, 10..=20),
+            a: Pt2::new(a),
+            b: Pt2::new(b),
+            c: Pt2::new(c),
+            d: Pt2::new(d),
         }
     }
 }
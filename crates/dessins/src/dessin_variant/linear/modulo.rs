@@ -1,13 +1,15 @@
 use crate::{
     adjustable_dessin::AdjustableDessin,
     adjustable_variable::types::{Context, ExpressionF32, F32Variant, F32, U32},
-    shapes::{Segment, Shape, Shapes, NP},
+    shapes::Shapes,
 };
 use adjustable_dessin_derive::DefaultAdjustableDessin;
+use dessins_core::dessin_variant::linear::modulo;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Clone, Debug, PartialEq, Reflect, DefaultAdjustableDessin)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize, DefaultAdjustableDessin)]
 #[reflect(Default)]
 pub struct Modulo {
     pub n: U32,
@@ -21,55 +23,40 @@ pub struct Modulo {
 
 impl Modulo {
     pub fn calculate_shapes(&mut self) -> Shapes {
-        let mut shapes = Shapes::new();
-        let mut shape = Shape::new();
+        self.y.insert_ctx_entry("n", self.n.get_value() as f32);
+        self.y.insert_ctx_entry("k2", self.k2.get_value());
 
-        let points = self.calculate_points();
-
-        for i in 0..=self.m.get_value() {
-            let start_index = ((self.i1_factor.get_value() * i) % self.n.get_value()) as usize;
-            let end_index = ((self.h.get_value() * i) % self.n.get_value()) as usize;
-
-            let segment = vec![points[start_index], points[end_index]];
-            shape.push(segment);
-        }
-
-        shapes.push(shape);
-
-        shapes
-    }
-
-    fn calculate_points(&mut self) -> Segment {
-        let mut points = vec![];
-
-        let n = self.n.get_value() as f32;
-        let k1 = self.k1.get_value();
-
-        for i in 0..=n as usize {
-            let i = i as f32;
-
-            let x = NP as f32 * 0.5 * (k1 * i * PI / n).sin();
-
-            self.y.insert_ctx_entry("n", self.n.get_value() as f32);
-            self.y.insert_ctx_entry("k2", self.k2.get_value());
+        self.params().calculate_shapes(|i| {
             self.y.insert_ctx_entry("i", i);
-            let y = self.y.eval_expr();
+            self.y.eval_expr()
+        })
+    }
 
-            points.push(pt2(x, y));
+    pub fn params(&self) -> modulo::Modulo {
+        modulo::Modulo {
+            n: self.n.get_value(),
+            m: self.m.get_value(),
+            k1: self.k1.get_value(),
+            h: self.h.get_value(),
+            i1_factor: self.i1_factor.get_value(),
         }
-
-        points
     }
 
     fn default_y_expr() -> String {
-        "360 * math::cos(k2 * i * pi / n)".to_string()
+        modulo::Modulo::Y.to_string()
     }
 }
 
 impl Default for Modulo {
     fn default() -> Self {
-        let n = 400;
-        let k2 = 5.0;
+        let modulo::Modulo {
+            n,
+            m,
+            k1,
+            h,
+            i1_factor,
+        } = modulo::Modulo::default();
+        let k2 = modulo::Modulo::K2;
 
         let ctx = Context::new(&[
             ("n".to_string(), n as f32),
@@ -89,11 +76,11 @@ impl Default for Modulo {
 
         Self {
             n: U32::new(n, 10..=400),
-            m: U32::new(400, 10..=400),
-            k1: F32::new(4.0, F32Variant::None(1.0..=5.0)),
-            k2: F32::new(5.0, F32Variant::None(1.0..=5.0)),
-            h: U32::new(2, 1..=10),
-            i1_factor: U32::new(1, 1..=8),
+            m: U32::new(m, 10..=400),
+            k1: F32::from_raw(k1, F32Variant::None(1.0..=5.0)),
+            k2: F32::from_raw(k2, F32Variant::None(1.0..=5.0)),
+            h: U32::new(h, 1..=10),
+            i1_factor: U32::new(i1_factor, 1..=8),
             y,
         }
     }
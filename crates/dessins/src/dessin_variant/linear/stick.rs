@@ -1,13 +1,15 @@
 use crate::{
     adjustable_dessin::AdjustableDessin,
     adjustable_variable::types::{Context, ExpressionF32, U32},
-    shapes::{Shape, Shapes},
+    shapes::Shapes,
 };
 use adjustable_dessin_derive::DefaultAdjustableDessin;
+use dessins_core::dessin_variant::linear::stick;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Clone, Debug, PartialEq, Reflect, DefaultAdjustableDessin)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize, DefaultAdjustableDessin)]
 #[reflect(Default)]
 pub struct Stick {
     pub n: U32,
@@ -19,62 +21,41 @@ pub struct Stick {
 
 impl Stick {
     pub fn calculate_shapes(&mut self) -> Shapes {
-        let mut shapes = Shapes::new();
-        let mut shape = Shape::new();
-
-        let n = self.n.get_value();
-        let k = self.k.get_value();
-
-        for i in 0..=self.m.get_value() {
-            self.r1.insert_ctx_entry("n", self.n.get_value() as f32);
-            self.r1.insert_ctx_entry("k", self.k.get_value() as f32);
-            self.r2.insert_ctx_entry("n", self.n.get_value() as f32);
-            self.r2.insert_ctx_entry("k", self.k.get_value() as f32);
-
-            self.r1.insert_ctx_entry("i", i as f32);
-            let r1 = self.r1.eval_expr();
-
-            self.r2.insert_ctx_entry("i", i as f32);
-            let r2 = self.r2.eval_expr();
-
-            for j in 0..n {
-                let j = j as f32;
-                let n = n as f32;
-                let k = k as f32;
-
-                let an = 2.0 * j * PI / n;
-
-                let x = r1 * an.cos() + r2 * (k * an).cos();
-                let y = r1 * an.sin() + r2 * (k * an).sin();
-                let d = pt2(x, y);
-
-                let x = r1 * an.cos() + r2 * (k * an + PI).cos();
-                let y = r1 * an.sin() + r2 * (k * an + PI).sin();
-                let a = pt2(x, y);
-
-                let segment = vec![d, a];
-                shape.push(segment);
-            }
+        for expr in [&mut self.r1, &mut self.r2] {
+            expr.insert_ctx_entry("n", self.n.get_value() as f32);
+            expr.insert_ctx_entry("k", self.k.get_value() as f32);
         }
 
-        shapes.push(shape);
-
-        shapes
+        let (r1, r2) = (&mut self.r1, &mut self.r2);
+        stick::Stick {
+            n: self.n.get_value(),
+            m: self.m.get_value(),
+            k: self.k.get_value(),
+        }
+        .calculate_shapes(
+            |i| {
+                r1.insert_ctx_entry("i", i);
+                r1.eval_expr()
+            },
+            |i| {
+                r2.insert_ctx_entry("i", i);
+                r2.eval_expr()
+            },
+        )
     }
 
     fn default_r1_expr() -> String {
-        "120.0".to_string()
+        stick::Stick::R1.to_string()
     }
 
     fn default_r2_expr() -> String {
-        "100.0".to_string()
+        stick::Stick::R2.to_string()
     }
 }
 
 impl Default for Stick {
     fn default() -> Self {
-        let n = 100;
-        let k = 5;
+        let stick::Stick { n, m, k } = stick::Stick::default();
 
         let ctx = Context::new(&[
             ("n".to_string(), n as f32),
@@ -103,7 +84,7 @@ impl Default for Stick {
 
         Self {
             n: U32::new(n, 10..=600),
-            m: U32::new(1, 1..=6),
+            m: U32::new(m, 1..=6),
             k: U32::new(k, 1..=7),
             r1,
             r2,
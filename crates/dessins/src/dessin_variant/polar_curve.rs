@@ -1,13 +1,15 @@
 use crate::{
     adjustable_dessin::AdjustableDessin,
     adjustable_variable::types::{Context, ExpressionF32, U32},
-    shapes::{Segment, Shape, Shapes, NP},
+    shapes::Shapes,
 };
 use adjustable_dessin_derive::DefaultAdjustableDessin;
+use dessins_core::dessin_variant::polar_curve;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-#[derive(Clone, Debug, PartialEq, Reflect, DefaultAdjustableDessin)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize, DefaultAdjustableDessin)]
 #[reflect(Default)]
 pub struct PolarCurve {
     pub n: U32, // # segments
@@ -17,50 +19,38 @@ pub struct PolarCurve {
 
 impl PolarCurve {
     pub fn calculate_shapes(&mut self) -> Shapes {
-        let mut shapes = Shapes::new();
-        let mut shape = Shape::new();
-        let mut segment = Segment::new();
-
         let n = self.n.get_value() as f32;
+        self.a.insert_ctx_entry("n", n);
+        self.r.insert_ctx_entry("n", n);
 
-        for i in 0..=n as usize {
-            let i = i as f32;
-
-            self.a.insert_ctx_entry("n", self.n.get_value() as f32);
-            self.a.insert_ctx_entry("i", i);
-            let a = self.a.eval_expr();
-
-            self.r.insert_ctx_entry("n", self.n.get_value() as f32);
-            self.r.insert_ctx_entry("i", i);
-            let r = self.r.eval_expr();
-
-            let x = r * a.cos();
-            let y = r * a.sin();
-
-            let x = x * NP as f32 / 2.0;
-            let y = y * NP as f32 / 2.0;
-
-            segment.push(pt2(x, y));
+        let (a, r) = (&mut self.a, &mut self.r);
+        polar_curve::PolarCurve {
+            n: self.n.get_value(),
         }
-
-        shape.push(segment);
-        shapes.push(shape);
-
-        shapes
+        .calculate_shapes(
+            |i| {
+                a.insert_ctx_entry("i", i);
+                a.eval_expr()
+            },
+            |i| {
+                r.insert_ctx_entry("i", i);
+                r.eval_expr()
+            },
+        )
     }
 
     fn default_a_expr() -> String {
-        "3 * pi / 4 * math::sin(30*pi*i/n) * math::sin(pi*i/n)".to_string()
+        polar_curve::PolarCurve::A.to_string()
     }
 
     fn default_r_expr() -> String {
-        "i/n".to_string()
+        polar_curve::PolarCurve::R.to_string()
     }
 }
 
 impl Default for PolarCurve {
     fn default() -> Self {
-        let n = 2000;
+        let polar_curve::PolarCurve { n } = polar_curve::PolarCurve::default();
         let ctx = Context::new(&[("n".to_string(), n as f32), ("pi".to_string(), PI)]);
         let a = ExpressionF32::new(
             PolarCurve::default_a_expr(),
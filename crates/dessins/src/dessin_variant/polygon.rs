@@ -1,12 +1,14 @@
 use crate::{
     adjustable_dessin::AdjustableDessin,
     adjustable_variable::types::{F32Variant, F32, U32},
-    shapes::{Segment, Shape, Shapes},
+    shapes::Shapes,
 };
 use adjustable_dessin_derive::DefaultAdjustableDessin;
+use dessins_core::dessin_variant::polygon;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Reflect, DefaultAdjustableDessin)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize, DefaultAdjustableDessin)]
 #[reflect(Default)]
 pub struct Polygon {
     pub k: U32,  // # vertices TODO: reconsider f32, at least discretize behaviour
@@ -16,40 +18,25 @@ pub struct Polygon {
 
 impl Polygon {
     pub fn calculate_shapes(&mut self) -> Shapes {
-        let mut shapes = Shapes::new();
-        let mut shape = Shape::new();
-        let mut segment = Segment::new();
-
-        for i in 0..self.k.get_value() {
-            let point = self.calculate_point(i);
-            segment.push(point);
-        }
-
-        segment.push(segment[0]);
-
-        shape.push(segment);
-        shapes.push(shape);
-        shapes
+        self.params().calculate_shapes()
     }
 
-    pub fn calculate_point(&self, i: u32) -> Point2 {
-        let i = i as f32;
-        let k = self.k.get_value() as f32;
-        let ad = self.ad.get_value();
-
-        let angle = (2.0 * i * PI) / k + ad;
-        let x = self.r.get_value() * angle.cos();
-        let y = self.r.get_value() * angle.sin();
-        pt2(x, y)
+    pub fn params(&self) -> polygon::Polygon {
+        polygon::Polygon {
+            k: self.k.get_value(),
+            r: self.r.get_value(),
+            ad: self.ad.get_value(),
+        }
     }
 }
 
 impl Default for Polygon {
     fn default() -> Self {
+        let polygon::Polygon { k, r, ad } = polygon::Polygon::default();
         Self {
-            k: U32::new(3, 3..=20),
-            r: F32::new(0.45, F32Variant::Length),
-            ad: F32::new(0.0, F32Variant::Angle),
+            k: U32::new(k, 3..=20),
+            r: F32::from_raw(r, F32Variant::Length),
+            ad: F32::from_raw(ad, F32Variant::Angle),
         }
     }
 }
@@ -1,14 +1,15 @@
 use crate::{
-    adjustable_dessin::AdjustableDessin, adjustable_variable::types::Context, shapes::Shapes,
+    adjustable_dessin::AdjustableDessin,
+    adjustable_variable::{types::Context, Changes},
+    shapes::Shapes,
 };
+use dessins_core::dessin_variant::raw_shape;
 use nannou::prelude::*;
-use raw_shape_program::*;
-use raw_shape_variant::*;
+use serde::{Deserialize, Serialize};
 
-mod raw_shape_program;
-mod raw_shape_variant;
+pub use raw_shape::{RawShapeProgram, RawShapeVariant};
 
-#[derive(Clone, Debug, PartialEq, Reflect)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
 #[reflect(Default)]
 pub struct RawShape {
     #[reflect(ignore)]
@@ -19,9 +20,14 @@ pub struct RawShape {
 
 impl RawShape {
     pub fn calculate_shapes(&mut self) -> Shapes {
-        let mut raw_shape_decoder = RawShapeDecoder::new(&self.shape_variant);
-        self.program_variant
-            .calculate_shapes(&mut raw_shape_decoder)
+        self.params().calculate_shapes()
+    }
+
+    pub fn params(&self) -> raw_shape::RawShape {
+        raw_shape::RawShape {
+            shape_variant: self.shape_variant.clone(),
+            program_variant: self.program_variant.clone(),
+        }
     }
 }
 
@@ -31,21 +37,72 @@ impl AdjustableDessin for RawShape {
         ui: &mut egui::Ui,
         _osc_ctx: &Context,
         _time: Time<Virtual>,
-    ) -> bool {
+    ) -> Changes {
         let mut changed = false;
-        changed |= self.shape_variant.update(ui);
+        changed |= update_shape_variant(&mut self.shape_variant, ui);
         ui.separator();
-        changed |= self.program_variant.update(ui);
+        changed |= update_program_variant(&mut self.program_variant, ui);
         ui.separator();
-        changed
+        Changes::edited(changed)
     }
 }
 
 impl Default for RawShape {
     fn default() -> Self {
+        let raw_shape::RawShape {
+            shape_variant,
+            program_variant,
+        } = raw_shape::RawShape::default();
         Self {
-            shape_variant: RawShapeVariant::Horse,
-            program_variant: RawShapeProgram::Program1,
+            shape_variant,
+            program_variant,
         }
     }
 }
+
+fn update_shape_variant(shape_variant: &mut RawShapeVariant, ui: &mut egui::Ui) -> bool {
+    let mut changed = false;
+
+    ui.label("shape");
+    changed |= ui
+        .radio_value(shape_variant, RawShapeVariant::Horse, "horse")
+        .changed();
+    changed |= ui
+        .radio_value(shape_variant, RawShapeVariant::Lion, "lion")
+        .changed();
+    changed |= ui
+        .radio_value(shape_variant, RawShapeVariant::BirdFish, "bird-fish")
+        .changed();
+    changed |= ui
+        .radio_value(shape_variant, RawShapeVariant::Smurf, "smurf")
+        .changed();
+
+    changed
+}
+
+fn update_program_variant(program_variant: &mut RawShapeProgram, ui: &mut egui::Ui) -> bool {
+    let labels = [
+        (RawShapeProgram::Program1, "o"),
+        (RawShapeProgram::Program2, "oO"),
+        (RawShapeProgram::Program3, "oOo"),
+        (RawShapeProgram::Program4, "oOoO"),
+        (RawShapeProgram::Program5, "oOoOo"),
+        (RawShapeProgram::Program6, "oOoOoO"),
+        (RawShapeProgram::Program7, "oOoOoOo"),
+        (RawShapeProgram::Program8, "oOoOoOoO"),
+        (RawShapeProgram::Program9, "oOoOoOoOo"),
+        (RawShapeProgram::Program10, "oOoOoOoOoO"),
+        (RawShapeProgram::Program11, "oOoOoOoOoOo"),
+        (RawShapeProgram::Program12, "oOoOoOoOoOoO"),
+        (RawShapeProgram::Program13, "oOoOoOoOoOoOo"),
+    ];
+
+    let mut changed = false;
+
+    ui.label("program");
+    for (program, label) in labels {
+        changed |= ui.radio_value(program_variant, program, label).changed();
+    }
+
+    changed
+}
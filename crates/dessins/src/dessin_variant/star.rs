@@ -1,12 +1,14 @@
 use crate::{
     adjustable_dessin::AdjustableDessin,
     adjustable_variable::types::{F32Variant, F32, U32},
-    shapes::{Segment, Shape, Shapes},
+    shapes::Shapes,
 };
 use adjustable_dessin_derive::DefaultAdjustableDessin;
+use dessins_core::dessin_variant::star;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Reflect, DefaultAdjustableDessin)]
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize, DefaultAdjustableDessin)]
 #[reflect(Default)]
 pub struct Star {
     pub k: U32,  // # vertices
@@ -17,42 +19,27 @@ pub struct Star {
 
 impl Star {
     pub fn calculate_shapes(&mut self) -> Shapes {
-        let mut shapes = Shapes::new();
-        let mut shape = Shape::new();
-        let mut segment = Segment::new();
-
-        for i in 0..self.k.get_value() {
-            let point = self.calculate_point(i);
-            segment.push(point);
-        }
-
-        segment.push(segment[0]);
-
-        shape.push(segment);
-        shapes.push(shape);
-        shapes
+        self.params().calculate_shapes()
     }
 
-    pub fn calculate_point(&self, i: u32) -> Point2 {
-        let i = i as f32;
-        let h = self.h.get_value() as f32;
-        let k = self.k.get_value() as f32;
-        let ad = self.ad.get_value();
-
-        let angle = (2.0 * i * h * PI) / k + ad;
-        let x = self.r.get_value() * angle.cos();
-        let y = self.r.get_value() * angle.sin();
-        pt2(x, y)
+    pub fn params(&self) -> star::Star {
+        star::Star {
+            k: self.k.get_value(),
+            h: self.h.get_value(),
+            r: self.r.get_value(),
+            ad: self.ad.get_value(),
+        }
     }
 }
 
 impl Default for Star {
     fn default() -> Self {
+        let star::Star { k, h, r, ad } = star::Star::default();
         Self {
-            k: U32::new(5, 5..=100),
-            h: U32::new(3, 3..=5),
-            r: F32::new(0.45, F32Variant::Length),
-            ad: F32::new(0.5, F32Variant::Angle),
+            k: U32::new(k, 5..=100),
+            h: U32::new(h, 3..=5),
+            r: F32::from_raw(r, F32Variant::Length),
+            ad: F32::from_raw(ad, F32Variant::Angle),
         }
     }
 }
@@ -1,22 +1,34 @@
 use crate::{
-    adjustable_dessin::AdjustableDessin, adjustable_variable::types::Context, shapes::Shapes,
+    adjustable_dessin::{
+        animate_from_reflect, animations_from_reflect, set_from_reflect, values_from_reflect,
+        variable_names_from_reflect, AdjustableDessin,
+    },
+    adjustable_variable::{types::Context, Changes},
+    animation::Animation,
+    osc::binding::Binding,
+    shapes::Shapes,
 };
 use egui::scroll_area::ScrollBarVisibility;
 use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 pub struct DessinWithVariables {
     pub variant: DessinVariant,
     pub variables: DessinVariables,
+    pub bindings: Vec<Binding>, // osc controls driving the variables
+    // variables and bindings of the other variants, kept for the session when switching away
+    cache: HashMap<DessinVariant, (DessinVariables, Vec<Binding>)>,
 }
 
 macro_rules! dessin_with_variables {
     ($($variant:ident => $variables:path),* $(,)?) => {
-        #[derive(Reflect)]
+        #[derive(Clone, Reflect, Serialize, Deserialize)]
         pub enum DessinVariables {
             $($variant($variables),)*
         }
 
-        #[derive(PartialEq, Eq, Clone, Copy)]
+        #[derive(PartialEq, Eq, Hash, Clone, Copy)]
         pub enum DessinVariant {
             $($variant,)*
         }
@@ -30,17 +42,54 @@ macro_rules! dessin_with_variables {
         }
 
         impl DessinVariables {
+            pub fn get_variant(&self) -> DessinVariant {
+                match self {
+                    $(DessinVariables::$variant(_) => DessinVariant::$variant,)*
+                }
+            }
+
             pub fn calculate_shapes(&mut self) -> Shapes {
                 match self {
                     $(DessinVariables::$variant(variables) => variables.calculate_shapes(),)*
                 }
             }
 
-            pub fn update(&mut self, ui: &mut egui::Ui, osc_ctx: &Context, time: Time<Virtual>) -> (bool, Option<Color>) {
+            pub fn update(&mut self, ui: &mut egui::Ui, osc_ctx: &Context, time: Time<Virtual>) -> (Changes, Option<Color>) {
                 match self {
                     $(DessinVariables::$variant(variables) => variables.update_dessin(ui, osc_ctx, time),)*
                 }
             }
+
+            pub fn set_variable(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+                match self {
+                    $(DessinVariables::$variant(variables) => set_from_reflect(variables, name, value),)*
+                }
+            }
+
+            pub fn variable_names(&self) -> Vec<&'static str> {
+                match self {
+                    $(DessinVariables::$variant(variables) => variable_names_from_reflect(variables),)*
+                }
+            }
+
+            // `name = value` pairs that `set_variable` takes back
+            pub fn values(&self) -> Vec<(String, String)> {
+                match self {
+                    $(DessinVariables::$variant(variables) => values_from_reflect(variables),)*
+                }
+            }
+
+            pub fn animate(&mut self, time: Time<Virtual>) {
+                match self {
+                    $(DessinVariables::$variant(variables) => animate_from_reflect(variables, time),)*
+                }
+            }
+
+            pub fn animations(&self) -> Vec<&Animation> {
+                match self {
+                    $(DessinVariables::$variant(variables) => animations_from_reflect(variables),)*
+                }
+            }
         }
     };
 }
@@ -87,11 +136,60 @@ impl DessinVariant {
         (Self::DeformedSimpleFractal, "deformed simple fractal"),
         (Self::PolarCurve, "polar curve"),
     ];
+
+    pub fn name(&self) -> &'static str {
+        Self::ALL
+            .iter()
+            .find(|(variant, _)| variant == self)
+            .map(|(_, name)| *name)
+            .expect("every variant has a name")
+    }
+
+    // accepts "-" or "_" in place of spaces, e.g. "polar-curve"
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.replace(['-', '_'], " ");
+        Self::ALL
+            .iter()
+            .find(|(_, variant_name)| *variant_name == name)
+            .map(|(variant, _)| *variant)
+    }
 }
 
 impl DessinWithVariables {
+    pub fn new(variant: DessinVariant) -> Self {
+        Self {
+            variant,
+            variables: variant.get_variables(),
+            bindings: vec![],
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn switch(&mut self, variant: DessinVariant) {
+        let (variables, bindings) = self
+            .cache
+            .remove(&variant)
+            .unwrap_or_else(|| (variant.get_variables(), vec![]));
+        let previous = std::mem::replace(&mut self.variables, variables);
+        let previous_bindings = std::mem::replace(&mut self.bindings, bindings);
+        self.cache
+            .insert(previous.get_variant(), (previous, previous_bindings));
+        self.variant = variant;
+    }
+
+    // replaces the variables of their variant and makes it the active one
+    pub fn load(&mut self, variables: DessinVariables) {
+        self.switch(variables.get_variant());
+        self.variables = variables;
+    }
+
+    pub fn reset(&mut self) {
+        self.variables = self.variant.get_variables();
+    }
+
     pub fn update(&mut self, ctx: &mut egui::Context) -> bool {
-        let mut changed = false;
+        let mut selected = self.variant;
+        let mut reset = false;
 
         egui::TopBottomPanel::top("active dessin").show(ctx, |ui| {
             egui::ScrollArea::horizontal()
@@ -99,18 +197,23 @@ impl DessinWithVariables {
                 .show(ui, |ui| {
                     ui.horizontal(|ui| {
                         for (variant, name) in DessinVariant::ALL {
-                            changed |= ui
-                                .selectable_value(&mut self.variant, *variant, *name)
-                                .changed();
+                            ui.selectable_value(&mut selected, *variant, *name);
                         }
+                        ui.separator();
+                        reset = ui.button("reset to defaults").clicked();
                     });
                 });
         });
 
-        if changed {
-            self.variables = self.variant.get_variables();
+        if selected != self.variant {
+            self.switch(selected);
+            return true;
+        }
+
+        if reset {
+            self.reset();
         }
 
-        changed
+        reset
     }
 }
@@ -0,0 +1,241 @@
+use super::{
+    raster::{rasterize, PngOptions},
+    write,
+};
+use crate::{animation::Animation, layer::LayerShapes};
+use anyhow::{bail, Context as _};
+use dessins_render::raster::{encode, rgba};
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+// longest loop looked for when no duration is given
+const MAX_LOOP_PERIOD: f32 = 120.0;
+// color quantization effort for gif frames, 1 is best and slowest, 30 is fastest
+const GIF_QUANTIZATION_SPEED: i32 = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FrameFormat {
+    PngSequence,
+    Gif,
+    Apng,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FrameOptions {
+    pub fps: u32,
+    pub duration: Option<f32>, // seconds, one loop of the animations if none
+    pub start: f32,            // seconds on the timeline
+}
+
+impl Default for FrameOptions {
+    fn default() -> Self {
+        Self {
+            fps: 30,
+            duration: None,
+            start: 0.0,
+        }
+    }
+}
+
+impl FrameOptions {
+    pub fn update(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("frames").num_columns(2).show(ui, |ui| {
+            ui.label("fps");
+            ui.add(egui::DragValue::new(&mut self.fps).range(1..=120));
+            ui.end_row();
+
+            ui.label("duration (s)");
+            ui.horizontal(|ui| {
+                let mut one_loop = self.duration.is_none();
+                ui.checkbox(&mut one_loop, "one loop");
+                if one_loop {
+                    self.duration = None;
+                } else {
+                    let duration = self.duration.get_or_insert(5.0);
+                    ui.add(egui::DragValue::new(duration).speed(0.1).range(0.1..=600.0));
+                }
+            });
+            ui.end_row();
+
+            ui.label("start (s)");
+            ui.add(
+                egui::DragValue::new(&mut self.start)
+                    .speed(0.1)
+                    .range(0.0..=f32::MAX),
+            );
+            ui.end_row();
+        });
+    }
+}
+
+impl FrameFormat {
+    pub const ALL: &'static [(Self, &'static str)] = &[
+        (Self::Gif, "gif"),
+        (Self::Apng, "apng"),
+        (Self::PngSequence, "png frames"),
+    ];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::PngSequence => "png",
+            Self::Gif => "gif",
+            Self::Apng => "apng",
+        }
+    }
+
+    // "png" is a numbered png sequence, use "apng" for a single animated png
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "png" => Some(Self::PngSequence),
+            "gif" => Some(Self::Gif),
+            "apng" => Some(Self::Apng),
+            _ => None,
+        }
+    }
+}
+
+// smallest duration after which every animation is back where it started
+pub fn loop_period(animations: &[&Animation]) -> anyhow::Result<f32> {
+    if animations.is_empty() {
+        bail!("nothing is animated, give a duration");
+    }
+
+    // periods are compared in milliseconds, waveform frequencies rarely give exact periods
+    let mut period_ms: u64 = 1;
+
+    for animation in animations {
+        let period = animation.period().with_context(|| {
+            format!(
+                "the {} animation never repeats, give a duration",
+                animation.name()
+            )
+        })?;
+
+        let ms = (period * 1000.0).round() as u64;
+        if ms == 0 {
+            continue;
+        }
+
+        period_ms = period_ms / gcd(period_ms, ms) * ms;
+        if period_ms as f32 / 1000.0 > MAX_LOOP_PERIOD {
+            bail!("the animations only loop after more than {MAX_LOOP_PERIOD}s, give a duration");
+        }
+    }
+
+    Ok(period_ms as f32 / 1000.0)
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// virtual time of a frame, always one frame after the previous one so that waveforms sample
+// at the export frame rate rather than at whatever rate the app ran
+fn frame_time(start: f32, fps: u32, index: u32) -> Time<Virtual> {
+    let frame = Duration::from_secs_f64(1.0 / fps as f64);
+    let elapsed = Duration::from_secs_f64(start as f64) + frame * index;
+
+    let mut time = Time::default();
+    time.advance_to(elapsed.saturating_sub(frame));
+    time.advance_to(elapsed);
+    time
+}
+
+// renders the animation frame by frame, returns the number of frames written
+pub fn save_frames(
+    layers: &[LayerShapes],
+    format: FrameFormat,
+    options: &FrameOptions,
+    raster: &PngOptions,
+    path: impl AsRef<Path>,
+) -> anyhow::Result<u32> {
+    let path = path.as_ref();
+
+    if options.fps == 0 {
+        bail!("the frame rate has to be above 0");
+    }
+
+    let animations = layers
+        .iter()
+        .flat_map(LayerShapes::animations)
+        .collect::<Vec<_>>();
+    let duration = match options.duration {
+        Some(duration) => duration,
+        None => loop_period(&animations)?,
+    };
+
+    let frame_count = ((duration * options.fps as f32).round() as u32).max(1);
+
+    let mut layers = layers.to_vec();
+    let frames = (0..frame_count).map(|index| {
+        let time = frame_time(options.start, options.fps, index);
+        layers.iter_mut().for_each(|layer| layer.animate(time));
+        rasterize(&layers, raster)
+    });
+
+    match format {
+        FrameFormat::PngSequence => {
+            for (index, frame) in frames.enumerate() {
+                write(numbered_path(path, index), encode(&frame?, raster.dpi)?)?;
+            }
+        }
+        FrameFormat::Gif => {
+            let mut bytes = Vec::new();
+            let too_large = || format!("gif frames are at most {}px wide and high", u16::MAX);
+            let width = u16::try_from(raster.width).with_context(too_large)?;
+            let height = u16::try_from(raster.height).with_context(too_large)?;
+            let mut encoder = gif::Encoder::new(&mut bytes, width, height, &[])?;
+            encoder.set_repeat(gif::Repeat::Infinite)?;
+
+            // gif delays are in hundredths of a second
+            let delay = (100.0 / options.fps as f32).round().max(1.0) as u16;
+
+            for frame in frames {
+                let mut pixels = rgba(&frame?);
+                let mut frame =
+                    gif::Frame::from_rgba_speed(width, height, &mut pixels, GIF_QUANTIZATION_SPEED);
+                frame.delay = delay;
+                encoder.write_frame(&frame)?;
+            }
+
+            drop(encoder);
+            write(path, bytes)?;
+        }
+        FrameFormat::Apng => {
+            let mut bytes = Vec::new();
+            let mut encoder = png::Encoder::new(&mut bytes, raster.width, raster.height);
+            encoder.set_color(png::ColorType::Rgba);
+            encoder.set_depth(png::BitDepth::Eight);
+            encoder.set_animated(frame_count, 0)?;
+            encoder.set_frame_delay(1, u16::try_from(options.fps).unwrap_or(u16::MAX))?;
+            let mut writer = encoder.write_header()?;
+
+            for frame in frames {
+                writer.write_image_data(&rgba(&frame?))?;
+            }
+            writer.finish()?;
+
+            write(path, bytes)?;
+        }
+    }
+
+    Ok(frame_count)
+}
+
+// "out.png" becomes "out-0000.png", "out-0001.png"...
+fn numbered_path(path: &Path, index: usize) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("frame");
+    path.with_file_name(format!("{stem}-{index:04}.png"))
+}
@@ -0,0 +1,203 @@
+use self::svg::{svg_document, SvgExportOptions};
+use crate::{
+    layer::{merge, LayerShapes, Layers},
+    stroke::Stroke,
+};
+use anyhow::Context as _;
+use dessins_render::{gcode::gcode, hpgl::hpgl, optimize::optimize, plotter::PlotterOptions};
+use frames::{save_frames, FrameFormat, FrameOptions};
+use nannou::prelude::*;
+use plotter::update_plotter_options;
+use raster::{encode_png, PngOptions};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub mod frames;
+pub mod plotter;
+pub mod raster;
+pub mod svg;
+#[cfg(target_arch = "wasm32")]
+mod web;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Svg,
+    Gcode,
+    Hpgl,
+    Png,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExportOptions {
+    pub plotter: PlotterOptions,
+    pub png: PngOptions,
+    pub svg: SvgExportOptions,
+}
+
+#[derive(Default)]
+pub struct Export {
+    optimize: bool,
+    filename: String, // without extension, a random one if empty
+    options: ExportOptions,
+    frames: FrameOptions,
+    status: Option<String>,
+}
+
+impl Export {
+    pub fn update(&mut self, ui: &mut egui::Ui, layers: &Layers, stroke: &Stroke) {
+        let mut format = None;
+
+        ui.horizontal(|ui| {
+            for (variant, name) in Format::ALL {
+                if ui.button(format!("export {}", name)).clicked() {
+                    format = Some(*variant);
+                }
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("filename");
+            ui.add(egui::TextEdit::singleline(&mut self.filename).hint_text("random"));
+        });
+
+        ui.checkbox(&mut self.optimize, "optimize paths");
+
+        ui.collapsing("svg", |ui| self.options.svg.update(ui));
+
+        ui.collapsing("plotter", |ui| {
+            update_plotter_options(&mut self.options.plotter, ui)
+        });
+
+        ui.collapsing("png", |ui| self.options.png.update(ui));
+
+        let mut frame_format = None;
+
+        ui.collapsing("animation", |ui| {
+            self.frames.update(ui);
+            ui.horizontal(|ui| {
+                for (variant, name) in FrameFormat::ALL {
+                    if ui.button(format!("export {}", name)).clicked() {
+                        frame_format = Some(*variant);
+                    }
+                }
+            });
+        });
+
+        if let Some(format) = frame_format {
+            let filename = self.filename(format.extension());
+            self.status = Some(
+                match save_frames(
+                    &layers.exported(stroke),
+                    format,
+                    &self.frames,
+                    &self.options.png,
+                    &filename,
+                ) {
+                    Ok(frame_count) => format!("exported {} frames to {}", frame_count, filename),
+                    Err(err) => format!("{:#}", err),
+                },
+            );
+        }
+
+        if let Some(format) = format {
+            let filename = self.filename(format.extension());
+
+            let mut layers = layers.exported(stroke);
+            let report = self.optimize.then(|| optimize_layers(&mut layers));
+
+            self.status = Some(match save(&layers, format, &self.options, &filename) {
+                Ok(()) => match report {
+                    Some(report) => format!("exported {}\n{}", filename, report),
+                    None => format!("exported {}", filename),
+                },
+                Err(err) => format!("{:#}", err),
+            });
+        }
+
+        if let Some(status) = &self.status {
+            ui.label(status);
+        }
+    }
+
+    fn filename(&self, extension: &str) -> String {
+        let name = self.filename.trim();
+        if name.is_empty() {
+            format!("{}.{}", uuid::Uuid::new_v4(), extension)
+        } else {
+            format!("{}.{}", name, extension)
+        }
+    }
+}
+
+impl Format {
+    pub const ALL: &'static [(Self, &'static str)] = &[
+        (Self::Svg, "svg"),
+        (Self::Gcode, "gcode"),
+        (Self::Hpgl, "hpgl"),
+        (Self::Png, "png"),
+    ];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Svg => "svg",
+            Self::Gcode => "gcode",
+            Self::Hpgl => "hpgl",
+            Self::Png => "png",
+        }
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "svg" => Some(Self::Svg),
+            "gcode" | "gc" | "nc" => Some(Self::Gcode),
+            "hpgl" | "plt" => Some(Self::Hpgl),
+            "png" => Some(Self::Png),
+            _ => None,
+        }
+    }
+}
+
+// the layers are drawn bottom first, plotters get them one after the other with the same pen
+pub fn save(
+    layers: &[LayerShapes],
+    format: Format,
+    options: &ExportOptions,
+    path: impl AsRef<Path>,
+) -> anyhow::Result<()> {
+    match format {
+        Format::Svg => write(path, svg_document(layers, &options.svg)?.to_string()),
+        Format::Gcode => write(path, gcode(&merge(layers), &options.plotter)),
+        Format::Hpgl => write(path, hpgl(&merge(layers), &options.plotter)),
+        Format::Png => write(path, encode_png(layers, &options.png)?),
+    }
+}
+
+// each layer on its own so that their strokes stay in their layer, returns the reports
+pub fn optimize_layers(layers: &mut [LayerShapes]) -> String {
+    layers
+        .iter_mut()
+        .map(|layer| {
+            let (points, report) = optimize(&layer.points);
+            layer.points = points;
+            format!("{}: {}", layer.style.name, report)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> anyhow::Result<()> {
+    let path = path.as_ref();
+    std::fs::write(path, contents).with_context(|| format!("could not write {}", path.display()))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn write(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> anyhow::Result<()> {
+    let path = path.as_ref();
+    let filename = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .with_context(|| format!("invalid filename {}", path.display()))?;
+    web::download(filename, contents.as_ref())
+}
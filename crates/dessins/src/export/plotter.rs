@@ -0,0 +1,56 @@
+use dessins_render::plotter::{PlotterOptions, Units};
+use nannou::prelude::*;
+
+// the options live with the plotter writers, which have no ui
+pub fn update_plotter_options(options: &mut PlotterOptions, ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        ui.label("paper");
+        for (name, width, height) in [
+            ("A4", 210.0, 297.0),
+            ("A3", 297.0, 420.0),
+            ("letter", 215.9, 279.4),
+        ] {
+            if ui.button(name).clicked() {
+                options.paper_width = width;
+                options.paper_height = height;
+            }
+        }
+    });
+
+    egui::Grid::new("plotter").num_columns(2).show(ui, |ui| {
+        ui.label("width (mm)");
+        ui.add(egui::DragValue::new(&mut options.paper_width).range(1.0..=2000.0));
+        ui.end_row();
+
+        ui.label("height (mm)");
+        ui.add(egui::DragValue::new(&mut options.paper_height).range(1.0..=2000.0));
+        ui.end_row();
+
+        ui.label("margin (mm)");
+        ui.add(egui::DragValue::new(&mut options.margin).range(0.0..=500.0));
+        ui.end_row();
+
+        ui.label("units");
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut options.units, Units::Millimeters, "mm");
+            ui.radio_value(&mut options.units, Units::Inches, "in");
+        });
+        ui.end_row();
+
+        ui.label("draw feed rate");
+        ui.add(egui::DragValue::new(&mut options.draw_feed_rate).range(1.0..=100000.0));
+        ui.end_row();
+
+        ui.label("travel feed rate");
+        ui.add(egui::DragValue::new(&mut options.travel_feed_rate).range(1.0..=100000.0));
+        ui.end_row();
+
+        ui.label("pen up");
+        ui.text_edit_singleline(&mut options.pen_up);
+        ui.end_row();
+
+        ui.label("pen down");
+        ui.text_edit_singleline(&mut options.pen_down);
+        ui.end_row();
+    });
+}
@@ -0,0 +1,150 @@
+use crate::{layer::LayerShapes, shapes::WEIGHT, stroke};
+use dessins_render::{
+    raster::{encode, pixmap, stroke_points, transform, DPI},
+    svg::VIEW_BOX,
+};
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tiny_skia::{LineCap, LineJoin, Paint, Pixmap, Stroke};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PngOptions {
+    pub width: u32,  // px
+    pub height: u32, // px
+    pub dpi: f32,    // only recorded in the file, for printing
+    pub background: Color,
+    pub weight: f32, // stroke width of a layer with the default weight, in on screen units
+}
+
+impl Default for PngOptions {
+    fn default() -> Self {
+        Self {
+            width: 2000,
+            height: 2000,
+            dpi: DPI,
+            background: Color::WHITE,
+            weight: WEIGHT,
+        }
+    }
+}
+
+impl PngOptions {
+    pub fn update(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("size");
+            for (name, size) in [("thumbnail", 256), ("screen", 2000)] {
+                if ui.button(name).clicked() {
+                    self.width = size;
+                    self.height = size;
+                }
+            }
+            // portrait paper sizes at the current dpi
+            for (name, width, height) in [("A4", 210.0, 297.0), ("A3", 297.0, 420.0)] {
+                if ui.button(name).clicked() {
+                    self.width = (width / 25.4 * self.dpi).round() as u32;
+                    self.height = (height / 25.4 * self.dpi).round() as u32;
+                }
+            }
+        });
+
+        egui::Grid::new("png").num_columns(2).show(ui, |ui| {
+            ui.label("width (px)");
+            ui.add(egui::DragValue::new(&mut self.width).range(1..=20000));
+            ui.end_row();
+
+            ui.label("height (px)");
+            ui.add(egui::DragValue::new(&mut self.height).range(1..=20000));
+            ui.end_row();
+
+            ui.label("dpi");
+            ui.add(egui::DragValue::new(&mut self.dpi).range(1.0..=2400.0));
+            ui.end_row();
+
+            ui.label("weight");
+            ui.add(
+                egui::DragValue::new(&mut self.weight)
+                    .speed(0.1)
+                    .range(0.1..=50.0),
+            );
+            ui.end_row();
+
+            ui.label("background");
+            let mut background = self.background.to_srgba().to_f32_array();
+            if ui
+                .color_edit_button_rgba_unmultiplied(&mut background)
+                .changed()
+            {
+                let [red, green, blue, alpha] = background;
+                self.background = Color::srgba(red, green, blue, alpha);
+            }
+            ui.end_row();
+        });
+    }
+}
+
+// the dessin's view box is fitted into the image and centered
+pub fn rasterize(layers: &[LayerShapes], options: &PngOptions) -> anyhow::Result<Pixmap> {
+    let (width, height) = (options.width, options.height);
+    let mut pixmap = pixmap(width, height, Some(skia_color(options.background)))?;
+    let transform = transform(VIEW_BOX, width, height);
+
+    // bottom layer first
+    for layer in layers {
+        let mut paint = Paint {
+            anti_alias: true,
+            ..Default::default()
+        };
+
+        let width = options.weight * layer.style.weight / WEIGHT * layer.stroke.scale();
+        let mut stroke = Stroke {
+            width,
+            line_cap: match layer.stroke.cap {
+                stroke::LineCap::Butt => LineCap::Butt,
+                stroke::LineCap::Round => LineCap::Round,
+                stroke::LineCap::Square => LineCap::Square,
+            },
+            line_join: match layer.stroke.join {
+                stroke::LineJoin::Miter => LineJoin::Miter,
+                stroke::LineJoin::Round => LineJoin::Round,
+                stroke::LineJoin::Bevel => LineJoin::Bevel,
+            },
+            ..Default::default()
+        };
+
+        let weights = layer.stroke.weights();
+        let colors = layer.colors();
+        for (segment, colors) in layer.points.iter().flatten().zip(colors.iter().flatten()) {
+            for (color, factor, points) in weights.pieces(segment, colors) {
+                paint.set_color(skia_color(color));
+                stroke.width = width * factor;
+                stroke_points(&mut pixmap, points, &paint, &stroke, transform);
+            }
+        }
+    }
+
+    Ok(pixmap)
+}
+
+pub fn save_png(
+    layers: &[LayerShapes],
+    options: &PngOptions,
+    path: impl AsRef<Path>,
+) -> anyhow::Result<()> {
+    super::write(path, encode_png(layers, options)?)
+}
+
+pub fn encode_png(layers: &[LayerShapes], options: &PngOptions) -> anyhow::Result<Vec<u8>> {
+    encode(&rasterize(layers, options)?, options.dpi)
+}
+
+fn skia_color(color: Color) -> tiny_skia::Color {
+    let [red, green, blue, alpha] = color.to_srgba().to_f32_array();
+    tiny_skia::Color::from_rgba(
+        red.clamp(0.0, 1.0),
+        green.clamp(0.0, 1.0),
+        blue.clamp(0.0, 1.0),
+        alpha.clamp(0.0, 1.0),
+    )
+    .expect("components are clamped")
+}
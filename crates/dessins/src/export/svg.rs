@@ -0,0 +1,266 @@
+use crate::{
+    dessin_with_variables::{DessinVariables, DessinVariant},
+    layer::{merge, LayerShapes},
+    preset::Preset,
+    shapes::WEIGHT,
+};
+use ::svg::{
+    node::{
+        element::{tag::Type, Element, Group, Title, SVG},
+        Text,
+    },
+    parser::Event,
+    Node,
+};
+use anyhow::{bail, Context as _};
+use dessins_render::svg::{
+    background, dessin_metadata, document, path, read_variables, unescape, view_box,
+};
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const PRESET_TAG: &str = "dessins:preset";
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SvgExportOptions {
+    pub size: f32, // of the longer side
+    pub units: SvgUnits,
+    pub fit: bool, // shrink the view box to the drawing instead of the on screen square
+    pub stroke_width: f32, // of a layer with the default weight
+    pub background: Option<Color>,
+    pub metadata: bool, // embed the variables to get back to the dessin later
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SvgUnits {
+    Pixels,
+    Millimeters,
+}
+
+impl Default for SvgExportOptions {
+    fn default() -> Self {
+        Self {
+            size: 2000.0,
+            units: SvgUnits::Pixels,
+            fit: false,
+            stroke_width: 2.0,
+            background: None,
+            metadata: true,
+        }
+    }
+}
+
+impl SvgExportOptions {
+    pub fn update(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("svg").num_columns(2).show(ui, |ui| {
+            ui.label("size");
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut self.size).range(1.0..=20000.0));
+                ui.radio_value(&mut self.units, SvgUnits::Pixels, "px");
+                ui.radio_value(&mut self.units, SvgUnits::Millimeters, "mm");
+            });
+            ui.end_row();
+
+            ui.label("stroke width");
+            ui.add(
+                egui::DragValue::new(&mut self.stroke_width)
+                    .speed(0.1)
+                    .range(0.1..=50.0),
+            );
+            ui.end_row();
+
+            ui.label("background");
+            ui.horizontal(|ui| {
+                let mut enabled = self.background.is_some();
+                ui.checkbox(&mut enabled, "");
+                if enabled {
+                    let background = self.background.get_or_insert(Color::WHITE);
+                    let mut rgba = background.to_srgba().to_f32_array();
+                    if ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed() {
+                        let [red, green, blue, alpha] = rgba;
+                        *background = Color::srgba(red, green, blue, alpha);
+                    }
+                } else {
+                    self.background = None;
+                }
+            });
+            ui.end_row();
+        });
+
+        ui.checkbox(&mut self.fit, "fit view box to drawing");
+        ui.checkbox(&mut self.metadata, "embed parameters");
+    }
+}
+
+impl SvgUnits {
+    fn suffix(&self) -> &'static str {
+        match self {
+            Self::Pixels => "px",
+            Self::Millimeters => "mm",
+        }
+    }
+}
+
+pub fn svg_document(layers: &[LayerShapes], options: &SvgExportOptions) -> anyhow::Result<SVG> {
+    let view_box = view_box(&merge(layers), options.fit, options.stroke_width);
+    let mut document = document(view_box, options.size, options.units.suffix());
+
+    if options.metadata {
+        let mut metadata = Element::new("metadata");
+        for layer in layers {
+            metadata.append(layer_metadata(&layer.variables)?);
+        }
+        document = document.add(metadata);
+    }
+
+    if let Some(color) = options.background {
+        let (color, opacity) = color_and_opacity(color);
+        document = document.add(background(view_box, &color, opacity));
+    }
+
+    // one group per layer, bottom first, holding one group per shape
+    for (index, layer) in layers.iter().enumerate() {
+        let (stroke, stroke_opacity) = color_and_opacity(layer.style.color.get_value());
+        let stroke_width =
+            options.stroke_width * layer.style.weight / WEIGHT * layer.stroke.scale();
+        let mut layer_group = Group::new()
+            .set("id", format!("layer-{}", index + 1))
+            .set("fill", "none")
+            .set("stroke", stroke)
+            .set("stroke-opacity", stroke_opacity)
+            .set("stroke-width", stroke_width)
+            .set("stroke-linecap", layer.stroke.cap.name())
+            .set("stroke-linejoin", layer.stroke.join.name())
+            .add(Title::new(layer.style.name.clone()));
+
+        // a mapped colour or a varying weight splits the segments, each piece with its own stroke
+        let weights = layer.stroke.weights();
+        let colors = layer.colors();
+        for (shape, shape_colors) in layer.points.iter().zip(&colors) {
+            let mut group = Group::new();
+
+            for (segment, segment_colors) in shape.iter().zip(shape_colors) {
+                for (color, factor, points) in weights.pieces(segment, segment_colors) {
+                    let mut path = path(points);
+                    if !layer.style.color_map.is_solid() {
+                        let (stroke, stroke_opacity) = color_and_opacity(color);
+                        path = path
+                            .set("stroke", stroke)
+                            .set("stroke-opacity", stroke_opacity);
+                    }
+                    if !weights.is_uniform() {
+                        path = path.set("stroke-width", stroke_width * factor);
+                    }
+                    group = group.add(path);
+                }
+            }
+
+            layer_group = layer_group.add(group);
+        }
+
+        document = document.add(layer_group);
+    }
+
+    Ok(document)
+}
+
+// a readable list of the variables for people and dessins_render, and the whole preset with its
+// animations for `read_preset`
+fn layer_metadata(variables: &DessinVariables) -> anyhow::Result<Element> {
+    let mut dessin = dessin_metadata(variables.get_variant().name(), &variables.values());
+
+    let mut preset = Element::new(PRESET_TAG);
+    preset.append(Text::new(Preset::new(variables.clone()).to_ron()?));
+    dessin.append(preset);
+
+    Ok(dessin)
+}
+
+// the preset embedded by `svg_document`, the bottom layer's one for several layers, or the
+// listed variables without animations when there is no preset
+pub fn read_preset(content: &str) -> anyhow::Result<Preset> {
+    let mut in_preset = false;
+
+    for event in ::svg::read(content)? {
+        match event {
+            Event::Error(err) => bail!("invalid svg: {}", err),
+            Event::Tag(PRESET_TAG, Type::Start, _) => in_preset = true,
+            Event::Text(ron) if in_preset => {
+                return Preset::from_ron(&unescape(ron)).context("invalid embedded preset");
+            }
+            _ => in_preset = false,
+        }
+    }
+
+    let (variant, values) = read_variables(content)?;
+    let variant =
+        DessinVariant::from_name(&variant).with_context(|| format!("unknown dessin {variant}"))?;
+    let mut variables = variant.get_variables();
+    for (name, value) in values {
+        variables.set_variable(&name, &value)?;
+    }
+
+    Ok(Preset::new(variables))
+}
+
+fn color_and_opacity(color: Color) -> (String, f32) {
+    let srgba = color.to_srgba();
+    (srgba.with_alpha(1.0).to_hex(), srgba.alpha)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // an svg from dessins_render or an older export, listing the variables without a preset
+    fn listed_variables(variables: &DessinVariables) -> String {
+        let mut metadata = Element::new("metadata");
+        metadata.append(dessin_metadata(
+            variables.get_variant().name(),
+            &variables.values(),
+        ));
+        SVG::new().add(metadata).to_string()
+    }
+
+    fn imports_expressions(variant: DessinVariant, expressions: &[(&str, &str)]) {
+        let mut variables = variant.get_variables();
+        for (name, expr) in expressions {
+            variables.set_variable(name, expr).unwrap();
+        }
+
+        let preset = read_preset(&listed_variables(&variables)).unwrap();
+
+        assert_eq!(preset.variables.values(), variables.values());
+    }
+
+    #[test]
+    fn imports_polar_curve_expressions() {
+        imports_expressions(
+            DessinVariant::PolarCurve,
+            &[("a", "2 * pi * i / n"), ("r", "i / n * 200")],
+        );
+    }
+
+    #[test]
+    fn imports_orbital_curve_expressions() {
+        imports_expressions(DessinVariant::OrbitalCurve, &[("r2", "i / n * 100")]);
+    }
+
+    #[test]
+    fn imports_rotating_curve_expressions() {
+        imports_expressions(DessinVariant::RotatingCurve, &[("s", "1 + math::sin(i)")]);
+    }
+
+    #[test]
+    fn imports_linear_modulo_expressions() {
+        imports_expressions(DessinVariant::LinearModulo, &[("y", "k2 * i")]);
+    }
+
+    #[test]
+    fn imports_linear_stick_expressions() {
+        imports_expressions(
+            DessinVariant::LinearStick,
+            &[("r1", "100 + i"), ("r2", "k * i / n")],
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use anyhow::anyhow;
+use wasm_bindgen::JsCast;
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+// there is no file system in the browser, hand the file over as a download instead
+pub fn download(filename: &str, contents: &[u8]) -> anyhow::Result<()> {
+    let window = web_sys::window().ok_or_else(|| anyhow!("no browser window"))?;
+    let document = window
+        .document()
+        .ok_or_else(|| anyhow!("no browser document"))?;
+
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(contents));
+    let properties = BlobPropertyBag::new();
+    properties.set_type("application/octet-stream");
+    let blob = Blob::new_with_u8_array_sequence_and_options(&parts, &properties)
+        .map_err(|err| anyhow!("could not create download: {:?}", err))?;
+    let url = Url::create_object_url_with_blob(&blob)
+        .map_err(|err| anyhow!("could not create download: {:?}", err))?;
+
+    let anchor = document
+        .create_element("a")
+        .map_err(|err| anyhow!("could not create download: {:?}", err))?
+        .unchecked_into::<HtmlAnchorElement>();
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    anchor.click();
+
+    Url::revoke_object_url(&url).map_err(|err| anyhow!("could not revoke download: {:?}", err))
+}
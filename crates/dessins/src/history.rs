@@ -0,0 +1,153 @@
+use crate::dessin_with_variables::{DessinVariables, DessinWithVariables};
+use egui::{Key, KeyboardShortcut, Modifiers};
+use nannou::prelude::*;
+use std::collections::VecDeque;
+
+const DEFAULT_BUDGET: usize = 16 * 1024 * 1024; // bytes of stored snapshots
+
+const UNDO: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+const REDO: KeyboardShortcut =
+    KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::Z);
+
+// snapshots are kept as ron, which is compact and gives their size for the budget
+pub struct History {
+    undo: VecDeque<String>,
+    redo: Vec<String>,
+    current: String,
+    // an edit happened that is not in `current` yet
+    dirty: bool,
+    budget: usize,
+}
+
+impl History {
+    pub fn new(dessin: &DessinWithVariables) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: vec![],
+            current: snapshot(&dessin.variables).unwrap_or_default(),
+            dirty: false,
+            budget: DEFAULT_BUDGET,
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn undo(&mut self, dessin: &mut DessinWithVariables) -> bool {
+        let Some(previous) = self.undo.pop_back() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut self.current, previous);
+        self.redo.push(current);
+        self.dirty = false;
+        restore(&self.current, dessin)
+    }
+
+    pub fn redo(&mut self, dessin: &mut DessinWithVariables) -> bool {
+        let Some(next) = self.redo.pop() else {
+            return false;
+        };
+        let current = std::mem::replace(&mut self.current, next);
+        self.undo.push_back(current);
+        self.dirty = false;
+        restore(&self.current, dessin)
+    }
+
+    // call once per frame after all the ui that can edit the dessin ran
+    // `edited` is whether the user changed the dessin through the ui this frame, animations and osc
+    // change it every frame without making steps
+    pub fn record(&mut self, ctx: &egui::Context, dessin: &DessinWithVariables, edited: bool) {
+        self.dirty |= edited;
+
+        // a slider drag or a text edit in progress becomes a single step once released
+        if !self.dirty || interacting(ctx) {
+            return;
+        }
+        self.dirty = false;
+
+        let Some(next) = snapshot(&dessin.variables) else {
+            return;
+        };
+        if next == self.current {
+            return;
+        }
+
+        let previous = std::mem::replace(&mut self.current, next);
+        self.undo.push_back(previous);
+        self.redo.clear();
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        let mut size = self.undo.iter().map(String::len).sum::<usize>();
+        while size > self.budget {
+            match self.undo.pop_front() {
+                Some(oldest) => size -= oldest.len(),
+                None => break,
+            }
+        }
+    }
+
+    pub fn shortcuts(&mut self, ctx: &egui::Context, dessin: &mut DessinWithVariables) -> bool {
+        // text fields have their own undo
+        if ctx.memory(|memory| memory.focused().is_some()) {
+            return false;
+        }
+
+        // cmd+z also matches cmd+shift+z, so redo goes first
+        if ctx.input_mut(|input| input.consume_shortcut(&REDO)) {
+            return self.redo(dessin);
+        }
+        if ctx.input_mut(|input| input.consume_shortcut(&UNDO)) {
+            return self.undo(dessin);
+        }
+        false
+    }
+
+    pub fn update(&mut self, ui: &mut egui::Ui, dessin: &mut DessinWithVariables) -> bool {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            let undo = ui
+                .add_enabled(self.can_undo(), egui::Button::new("undo"))
+                .on_hover_text(ui.ctx().format_shortcut(&UNDO));
+            if undo.clicked() {
+                changed |= self.undo(dessin);
+            }
+
+            let redo = ui
+                .add_enabled(self.can_redo(), egui::Button::new("redo"))
+                .on_hover_text(ui.ctx().format_shortcut(&REDO));
+            if redo.clicked() {
+                changed |= self.redo(dessin);
+            }
+        });
+
+        changed
+    }
+}
+
+fn snapshot(variables: &DessinVariables) -> Option<String> {
+    ron::to_string(variables).ok()
+}
+
+fn restore(snapshot: &str, dessin: &mut DessinWithVariables) -> bool {
+    match ron::from_str::<DessinVariables>(snapshot) {
+        Ok(variables) => {
+            dessin.load(variables);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+fn interacting(ctx: &egui::Context) -> bool {
+    ctx.is_using_pointer()
+        || ctx.input(|input| input.pointer.any_down())
+        || ctx.memory(|memory| memory.focused().is_some())
+}
@@ -0,0 +1,407 @@
+use crate::{
+    adjustable_dessin::{animate_from_reflect, animations_from_reflect},
+    adjustable_variable::{
+        types::{Context, Rgba},
+        AdjustableVariable, UpdateVariableParams,
+    },
+    animation::Animation,
+    color_map::{ColorMap, Colors},
+    dessin_with_variables::{DessinVariables, DessinVariant, DessinWithVariables},
+    history::History,
+    modifier::Modifiers,
+    shapes::{Shapes, WEIGHT},
+    stroke::Stroke,
+    ui::{add_float_pi, add_float_position, float},
+};
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LayerStyle {
+    pub name: String,
+    pub visible: bool,
+    pub color: Rgba,
+    pub color_map: ColorMap,
+    pub weight: f32, // on screen, exports scale their stroke width by weight / WEIGHT
+    pub transform: LayerTransform,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LayerTransform {
+    pub translate: Vec2,
+    pub rotate: f32, // radians
+    pub scale: f32,
+}
+
+pub struct Layer {
+    pub style: LayerStyle,
+    pub dessin: DessinWithVariables,
+    pub modifiers: Modifiers,
+    pub history: History,
+    shapes: Shapes, // as calculated by the dessin
+    points: Shapes, // modified and transformed, as drawn
+    colors: Colors, // of each point
+}
+
+// a layer as exported, with its shapes already modified and transformed
+#[derive(Clone)]
+pub struct LayerShapes {
+    pub style: LayerStyle,
+    pub modifiers: Modifiers,
+    pub variables: DessinVariables,
+    pub stroke: Stroke, // the settings' one, shared by every layer
+    pub points: Shapes,
+}
+
+pub struct Layers {
+    layers: Vec<Layer>, // drawn in order, the last one on top
+    active: usize,
+    added: usize, // numbers the default names
+}
+
+impl LayerStyle {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            visible: true,
+            color: Rgba::new(Color::srgb(random(), random(), random())),
+            color_map: ColorMap::default(),
+            weight: WEIGHT,
+            transform: LayerTransform::default(),
+        }
+    }
+
+    // returns whether the points or their colours have to be computed again
+    pub fn update(&mut self, ui: &mut egui::Ui, osc_ctx: &Context, time: Time<Virtual>) -> bool {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            ui.label("name");
+            ui.text_edit_singleline(&mut self.name);
+        });
+
+        changed |= self
+            .color
+            .update(UpdateVariableParams {
+                ui,
+                osc_ctx,
+                name: "color".to_string(),
+                time,
+            })
+            .changed;
+        changed |= self.color_map.update(ui);
+
+        ui.label("weight");
+        ui.add(float(&mut self.weight, osc_ctx, 0.1..=20.0));
+
+        changed |= self.transform.update(ui, osc_ctx);
+        changed
+    }
+}
+
+impl LayerTransform {
+    pub fn apply(&self, shapes: &Shapes) -> Shapes {
+        let mut shapes = shapes.clone();
+        if *self == Self::default() {
+            return shapes;
+        }
+
+        let rotation = Vec2::from_angle(self.rotate);
+        shapes
+            .iter_mut()
+            .flatten()
+            .flatten()
+            .for_each(|point| *point = rotation.rotate(*point * self.scale) + self.translate);
+        shapes
+    }
+
+    fn update(&mut self, ui: &mut egui::Ui, osc_ctx: &Context) -> bool {
+        let mut changed = false;
+
+        ui.label("translate.x");
+        changed |= add_float_position(ui, osc_ctx, &mut self.translate.x);
+        ui.label("translate.y");
+        changed |= add_float_position(ui, osc_ctx, &mut self.translate.y);
+        ui.label("rotate");
+        changed |= add_float_pi(ui, osc_ctx, &mut self.rotate);
+        ui.label("scale");
+        changed |= ui
+            .add(float(&mut self.scale, osc_ctx, 0.05..=4.0).logarithmic(true))
+            .changed();
+
+        if ui.button("reset transform").clicked() {
+            *self = Self::default();
+            changed = true;
+        }
+
+        changed
+    }
+}
+
+impl Default for LayerTransform {
+    fn default() -> Self {
+        Self {
+            translate: Vec2::ZERO,
+            rotate: 0.0,
+            scale: 1.0,
+        }
+    }
+}
+
+impl Layer {
+    pub fn new(name: String, variant: DessinVariant) -> Self {
+        let dessin = DessinWithVariables::new(variant);
+        Self {
+            style: LayerStyle::new(name),
+            history: History::new(&dessin),
+            dessin,
+            modifiers: Modifiers::default(),
+            shapes: Shapes::new_non_empty(),
+            points: Shapes::new_non_empty(),
+            colors: Colors::new(),
+        }
+    }
+
+    pub fn initialized(&self) -> bool {
+        self.shapes != Shapes::new_non_empty()
+    }
+
+    pub fn calculate_shapes(&mut self) {
+        self.shapes = self.dessin.variables.calculate_shapes();
+        self.update_points();
+    }
+
+    // after a change to the style, the dessin's shapes stay the same
+    pub fn update_points(&mut self) {
+        let modified = self.modifiers.apply(&self.shapes);
+        self.points = self.style.transform.apply(&modified);
+        self.update_colors();
+    }
+
+    pub fn update_colors(&mut self) {
+        let color = self.style.color.get_value();
+        self.colors = self.style.color_map.colors(color, &self.points);
+    }
+
+    // for the layers whose ui is not shown, returns whether anything moved
+    pub fn animate(&mut self, time: Time<Virtual>) -> bool {
+        let animated = !self.dessin.variables.animations().is_empty()
+            || !self.modifiers.animations().is_empty();
+        if animated {
+            self.dessin.variables.animate(time);
+            self.modifiers.animate(time);
+            self.calculate_shapes();
+        }
+        self.animate_style(time) || animated
+    }
+
+    // the style's ui is folded most of the time
+    pub fn animate_style(&mut self, time: Time<Virtual>) -> bool {
+        let animated = !self.style.color.animations().is_empty();
+        if animated {
+            self.style.color.animate(time);
+            self.update_colors();
+        }
+        animated
+    }
+
+    pub fn points(&self) -> &Shapes {
+        &self.points
+    }
+
+    pub fn colors(&self) -> &Colors {
+        &self.colors
+    }
+
+    pub fn exported(&self, stroke: &Stroke) -> LayerShapes {
+        LayerShapes {
+            style: self.style.clone(),
+            modifiers: self.modifiers.clone(),
+            variables: self.dessin.variables.clone(),
+            stroke: stroke.clone(),
+            points: self.points.clone(),
+        }
+    }
+}
+
+impl LayerShapes {
+    pub fn new(style: LayerStyle, modifiers: Modifiers, variables: DessinVariables) -> Self {
+        let mut layer = Self {
+            style,
+            modifiers,
+            variables,
+            stroke: Stroke::default(),
+            points: Shapes::new(),
+        };
+        layer.calculate_shapes();
+        layer
+    }
+
+    pub fn animate(&mut self, time: Time<Virtual>) {
+        self.variables.animate(time);
+        self.modifiers.animate(time);
+        self.style.color.animate(time);
+        animate_from_reflect(&mut self.stroke, time);
+        self.calculate_shapes();
+    }
+
+    pub fn animations(&self) -> Vec<&Animation> {
+        let mut animations = self.variables.animations();
+        animations.extend(self.modifiers.animations());
+        animations.extend(self.style.color.animations());
+        animations.extend(animations_from_reflect(&self.stroke));
+        animations
+    }
+
+    fn calculate_shapes(&mut self) {
+        let modified = self.modifiers.apply(&self.variables.calculate_shapes());
+        self.points = self.style.transform.apply(&modified);
+    }
+
+    pub fn colors(&self) -> Colors {
+        let color = self.style.color.get_value();
+        self.style.color_map.colors(color, &self.points)
+    }
+}
+
+// every layer's shapes in a single drawing, for the exports that have one pen
+pub fn merge(layers: &[LayerShapes]) -> Shapes {
+    let mut shapes = Shapes::new();
+    for layer in layers {
+        shapes.extend(layer.points.iter().cloned());
+    }
+    shapes
+}
+
+impl Layers {
+    pub fn new(variant: DessinVariant) -> Self {
+        Self {
+            layers: vec![Layer::new("layer 1".to_string(), variant)],
+            active: 0,
+            added: 1,
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Layer> {
+        self.layers.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Layer> {
+        self.layers.iter_mut()
+    }
+
+    pub fn active(&self) -> &Layer {
+        &self.layers[self.active]
+    }
+
+    pub fn active_mut(&mut self) -> &mut Layer {
+        &mut self.layers[self.active]
+    }
+
+    pub fn inactive_mut(&mut self) -> impl Iterator<Item = &mut Layer> {
+        let active = self.active;
+        self.layers
+            .iter_mut()
+            .enumerate()
+            .filter(move |(index, _)| *index != active)
+            .map(|(_, layer)| layer)
+    }
+
+    // the visible layers, bottom first
+    pub fn exported(&self, stroke: &Stroke) -> Vec<LayerShapes> {
+        self.layers
+            .iter()
+            .filter(|layer| layer.style.visible)
+            .map(|layer| layer.exported(stroke))
+            .collect()
+    }
+
+    pub fn update(&mut self, ui: &mut egui::Ui, osc_ctx: &Context, time: Time<Virtual>) -> bool {
+        let mut changed = false;
+        let mut moved = None;
+        let mut removed = None;
+        let removable = self.layers.len() > 1;
+
+        // top layer first, like in most editors
+        for (index, layer) in self.layers.iter_mut().enumerate().rev() {
+            ui.horizontal(|ui| {
+                changed |= ui.checkbox(&mut layer.style.visible, "").changed();
+                if ui
+                    .selectable_label(index == self.active, layer.style.name.as_str())
+                    .clicked()
+                {
+                    self.active = index;
+                }
+                if ui.small_button("up").clicked() {
+                    moved = Some((index, index + 1));
+                }
+                if ui.small_button("down").clicked() {
+                    moved = index.checked_sub(1).map(|below| (index, below));
+                }
+                if ui
+                    .add_enabled(removable, egui::Button::new("remove").small())
+                    .clicked()
+                {
+                    removed = Some(index);
+                }
+            });
+        }
+
+        if let Some((from, to)) = moved {
+            if to < self.layers.len() {
+                self.layers.swap(from, to);
+                if self.active == from {
+                    self.active = to;
+                } else if self.active == to {
+                    self.active = from;
+                }
+                changed = true;
+            }
+        }
+
+        if let Some(index) = removed {
+            self.layers.remove(index);
+            if self.active > index || self.active == self.layers.len() {
+                self.active -= 1;
+            }
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            if ui.button("add layer").clicked() {
+                self.added += 1;
+                let variant = self.active().dessin.variant;
+                let layer = Layer::new(format!("layer {}", self.added), variant);
+                self.active += 1;
+                self.layers.insert(self.active, layer);
+                changed = true;
+            }
+            if ui.button("duplicate layer").clicked() {
+                self.added += 1;
+                let active = self.active();
+                let mut layer = Layer::new(format!("layer {}", self.added), active.dessin.variant);
+                layer.dessin.load(active.dessin.variables.clone());
+                layer.dessin.bindings = active.dessin.bindings.clone();
+                layer.history = History::new(&layer.dessin);
+                layer.modifiers = active.modifiers.clone();
+                layer.style = LayerStyle {
+                    name: layer.style.name,
+                    ..active.style.clone()
+                };
+                self.active += 1;
+                self.layers.insert(self.active, layer);
+                changed = true;
+            }
+        });
+
+        ui.collapsing("style", |ui| {
+            let layer = self.active_mut();
+            if layer.style.update(ui, osc_ctx, time) {
+                layer.update_points();
+                changed = true;
+            }
+        });
+
+        changed
+    }
+}
@@ -1,10 +1,17 @@
 pub mod adjustable_dessin;
 pub mod adjustable_variable;
 pub mod animation;
+pub mod color_map;
 pub mod dessin_variant;
 pub mod dessin_with_variables;
 pub mod export;
+pub mod history;
+pub mod layer;
 pub mod model;
+pub mod modifier;
 pub mod osc;
+pub mod preset;
+pub mod settings;
 pub mod shapes;
+pub mod stroke;
 pub mod ui;
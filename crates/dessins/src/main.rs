@@ -32,13 +32,10 @@ fn setup(mut commands: Commands) {
 fn update_active_dessin(
     mut model: ResMut<Model>,
     time: Res<Time<Virtual>>,
+    real_time: Res<Time<Real>>,
     egui_ctx: EguiContexts,
 ) {
-    let (changed, color) = model.update_active_dessin(egui_ctx, *time);
-
-    if let Some(new_color) = color {
-        model.color = new_color;
-    }
+    let changed = model.update_active_dessin(egui_ctx, *time, *real_time);
 
     if changed || !model.initialized() {
         model.calculate_shapes();
@@ -47,7 +44,7 @@ fn update_active_dessin(
 
 fn draw_dessin(draw: Single<&Draw>, model: Res<Model>) {
     // TODO: alpha does not work https://github.com/supiket/dessins/issues/61
-    draw.background().srgba(0.1, 0.1, 0.1, 0.85);
+    draw.background().color(model.background());
 
     model.draw_points(draw);
 }
@@ -1,51 +1,103 @@
 use crate::{
-    adjustable_variable::types::Context,
-    dessin_with_variables::{DessinVariant, DessinWithVariables},
-    export::add_export_svg_button,
-    osc::Osc,
-    shapes::{Shapes, WEIGHT},
+    animation::Timeline,
+    dessin_with_variables::DessinVariant,
+    export::Export,
+    layer::Layers,
+    osc::{
+        binding::{update_bindings, with_learn_buttons},
+        Osc,
+    },
+    preset::Presets,
+    settings::{Settings, SettingsFile},
+    stroke::{LineCap, LineJoin},
 };
 use bevy_egui::EguiContexts;
 use nannou::prelude::*;
 
 #[derive(Resource)]
 pub struct Model {
-    active_dessin: DessinWithVariables,
+    layers: Layers,
+    settings: Settings,
+    settings_file: SettingsFile,
+    export: Export,
     osc: Osc,
-    points: Shapes,
-    // TODO: animate
-    pub color: Color,
+    presets: Presets,
+    timeline: Timeline,
 }
 
 impl Model {
     pub fn new(variant: DessinVariant) -> Self {
         Self {
-            active_dessin: DessinWithVariables {
-                variant,
-                variables: variant.get_variables(),
-            },
+            layers: Layers::new(variant),
+            settings: Settings::default(),
+            settings_file: SettingsFile::default(),
+            export: Export::default(),
             osc: Osc::default(),
-            points: Shapes::new_non_empty(),
-            color: Color::srgb(random(), random(), random()),
+            presets: Presets::default(),
+            timeline: Timeline::default(),
         }
     }
 
     pub fn initialized(&self) -> bool {
-        self.points != Shapes::new_non_empty()
+        self.layers.iter().all(|layer| layer.initialized())
     }
 
+    // the active layer is the only one edited, the others only need their first shapes
     pub fn calculate_shapes(&mut self) {
-        self.points = self.active_dessin.variables.calculate_shapes();
+        self.layers.active_mut().calculate_shapes();
+        self.layers
+            .iter_mut()
+            .filter(|layer| !layer.initialized())
+            .for_each(|layer| layer.calculate_shapes());
+
+        self.osc.send(self.layers.active(), self.timeline.time());
+    }
+
+    pub fn background(&self) -> Color {
+        self.settings.background.get_value()
     }
 
     pub fn draw_points(&self, draw: Single<&Draw>) {
-        self.points.iter().for_each(|shape| {
-            shape.iter().for_each(|segment| {
-                let points_colored = segment.iter().copied().map(|point| (point, self.color));
-                draw.polyline()
-                    .weight(WEIGHT)
-                    .points_colored(points_colored);
-            })
+        let stroke = &self.settings.stroke;
+        let weights = stroke.weights();
+
+        let visible = self.layers.iter().filter(|layer| layer.style.visible);
+        visible.for_each(|layer| {
+            let weight = layer.style.weight * stroke.scale();
+            let colors = layer.colors().iter().flatten();
+            layer
+                .points()
+                .iter()
+                .flatten()
+                .zip(colors)
+                .for_each(|(segment, colors)| {
+                    if weights.is_uniform() {
+                        let points_colored = segment.iter().copied().zip(colors.iter().copied());
+                        let polyline = draw.polyline().weight(weight);
+                        let polyline = match stroke.cap {
+                            LineCap::Butt => polyline.caps_butt(),
+                            LineCap::Round => polyline.caps_round(),
+                            LineCap::Square => polyline.caps_square(),
+                        };
+                        let polyline = match stroke.join {
+                            LineJoin::Miter => polyline.join_miter(),
+                            LineJoin::Round => polyline.join_round(),
+                            LineJoin::Bevel => polyline.join_bevel(),
+                        };
+                        polyline.points_colored(points_colored);
+                        return;
+                    }
+
+                    // a varying weight is drawn line by line, rounded to hide the seams
+                    for (color, factor, points) in weights.pieces(segment, colors) {
+                        draw.line()
+                            .start(points[0])
+                            .end(points[1])
+                            .weight(weight * factor)
+                            .caps_round()
+                            .color(color);
+                    }
+                })
         });
     }
 
@@ -53,27 +105,84 @@ impl Model {
         &mut self,
         mut egui_ctx: EguiContexts,
         time: Time<Virtual>,
-    ) -> (bool, Option<Color>) {
+        real_time: Time<Real>,
+    ) -> bool {
         let ctx = egui_ctx.ctx_mut();
 
-        let mut changed = false;
-        changed |= self.active_dessin.update(ctx);
+        self.timeline.tick(time.delta());
 
-        let mut color_changed = None;
-        let mut osc_ctx = Context::new(Default::default());
+        let mut changed = false;
+        // only the edits made through the ui are undo steps, not animations or osc
+        let mut edited = false;
+        {
+            let layer = self.layers.active_mut();
+            changed |= layer.history.shortcuts(ctx, &mut layer.dessin);
+            let switched = layer.dessin.update(ctx);
+            changed |= switched;
+            edited |= switched;
+        }
 
         egui::SidePanel::left("osc and variables").show(ctx, |ui| {
             egui::ScrollArea::vertical().show(ui, |ui| {
-                add_export_svg_button(ui, &self.points);
+                let layer = self.layers.active_mut();
+                changed |= layer.history.update(ui, &mut layer.dessin);
+                ui.separator();
+                self.export.update(ui, &self.layers, &self.settings.stroke);
                 ui.separator();
-                osc_ctx = self.osc.update(ui);
+                let loaded = self
+                    .presets
+                    .update(ui, &mut self.layers.active_mut().dessin);
+                changed |= loaded;
+                edited |= loaded;
                 ui.separator();
-                let res = self.active_dessin.variables.update(ui, &osc_ctx, time);
-                changed |= res.0;
-                color_changed = res.1;
+                let osc_ctx = self.osc.update(ui, real_time);
+                let dessin = &mut self.layers.active_mut().dessin;
+                changed |= self.osc.apply(&mut dessin.variables);
+                changed |= self.osc.bind(ui.ctx(), dessin);
+                ui.collapsing("osc bindings", |ui| {
+                    update_bindings(ui, &mut dessin.bindings);
+                });
+                ui.separator();
+                self.timeline.update(ui);
+                ui.separator();
+                let time = self.timeline.time();
+                ui.collapsing("settings", |ui| {
+                    self.settings.update(ui, &osc_ctx, time);
+                    ui.separator();
+                    self.settings_file.update(ui, &mut self.settings);
+                });
+                ui.separator();
+                ui.collapsing("layers", |ui| {
+                    changed |= self.layers.update(ui, &osc_ctx, time);
+                });
+                ui.separator();
+                let layer = self.layers.active_mut();
+                ui.collapsing("modifiers", |ui| {
+                    changed |= layer.modifiers.update(ui, &osc_ctx, time);
+                });
+                ui.separator();
+                let (variables, color) =
+                    with_learn_buttons(ui, |ui| layer.dessin.variables.update(ui, &osc_ctx, time));
+                changed |= variables.changed;
+                edited |= variables.edited;
+                if let Some(color) = color {
+                    layer.style.color.set_value(color);
+                    layer.update_colors();
+                }
             });
         });
 
-        (changed, color_changed)
+        let time = self.timeline.time();
+        let layer = self.layers.active_mut();
+        layer.history.record(ctx, &layer.dessin, edited);
+        layer.animate_style(time);
+        self.settings.animate(time);
+
+        // the other layers are not in the ui, their animations still run
+        for layer in self.layers.inactive_mut() {
+            layer.animate(time);
+        }
+
+        changed
     }
 }
@@ -0,0 +1,201 @@
+use crate::{
+    adjustable_dessin::AdjustableDessin,
+    adjustable_variable::{
+        types::{Context, ExpressionF32, F32Variant, F32},
+        AdjustableVariable, Changes, UpdateVariableParams,
+    },
+    shapes::{Shapes, NP},
+};
+use dessins_core::modifier::deformation::{self, Deformation, DeformationParams};
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Default)]
+pub struct Deform {
+    #[reflect(ignore)]
+    pub variant: DeformVariant,
+    pub exponent: F32,    // of the distance to the centre
+    pub coefficient: F32, // of the change of the angle
+    pub frequency: F32,   // of the waves of the angle
+    pub stretch: F32,     // of y
+    pub x: ExpressionF32, // x' of x, y, r and θ, 1 reaching the edge
+    pub y: ExpressionF32, // y' of x, y, r and θ
+    pub resolution: F32,  // longest line left straight before deforming
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum DeformVariant {
+    Program(Deformation),
+    Expression,
+}
+
+impl Deform {
+    pub fn apply(&self, shapes: &Shapes) -> Shapes {
+        let resolution = self.resolution.get_value();
+
+        match self.variant {
+            DeformVariant::Program(program) => {
+                let params = self.params();
+                deformation::deforme(shapes, resolution, |point| {
+                    program.deforme_point(point, &params)
+                })
+            }
+            DeformVariant::Expression => {
+                let half = NP as f32 / 2.0;
+                let (mut x, mut y) = (self.x.clone(), self.y.clone());
+                deformation::deforme(shapes, resolution, |point| {
+                    let point = point / half;
+                    let r = point.length();
+                    let theta = point.y.atan2(point.x);
+                    for expression in [&mut x, &mut y] {
+                        expression.insert_ctx_entry("x", point.x);
+                        expression.insert_ctx_entry("y", point.y);
+                        expression.insert_ctx_entry("r", r);
+                        expression.insert_ctx_entry("θ", theta);
+                        expression.insert_ctx_entry("theta", theta);
+                    }
+                    pt2(x.eval_expr(), y.eval_expr()) * half
+                })
+            }
+        }
+    }
+
+    pub fn params(&self) -> DeformationParams {
+        DeformationParams {
+            exponent: self.exponent.get_value(),
+            coefficient: self.coefficient.get_value(),
+            frequency: self.frequency.get_value(),
+            stretch: self.stretch.get_value(),
+        }
+    }
+
+    // each program starts from its original values
+    fn set_params(&mut self, params: DeformationParams) {
+        let coefficient = (4.0 * params.coefficient.abs()).max(1.0);
+
+        self.exponent = F32::new(params.exponent, F32Variant::None(0.25..=8.0));
+        self.coefficient = F32::new(
+            params.coefficient,
+            F32Variant::None(-coefficient..=coefficient),
+        );
+        self.frequency = F32::new(params.frequency, F32Variant::None(0.0..=24.0));
+        self.stretch = F32::new(params.stretch, F32Variant::None(0.25..=4.0));
+    }
+
+    fn default_x_expr() -> String {
+        "r * math::cos(θ + r)".to_string()
+    }
+
+    fn default_y_expr() -> String {
+        "r * math::sin(θ + r)".to_string()
+    }
+}
+
+impl AdjustableDessin for Deform {
+    fn update_variables(
+        &mut self,
+        ui: &mut egui::Ui,
+        osc_ctx: &Context,
+        time: Time<Virtual>,
+    ) -> Changes {
+        let mut changes = Changes::default();
+
+        let variant = self.variant;
+        ui.horizontal_wrapped(|ui| {
+            for (program, name) in Deformation::ALL {
+                let program = DeformVariant::Program(*program);
+                changes |=
+                    Changes::edited(ui.radio_value(&mut self.variant, program, *name).changed());
+            }
+            changes |= Changes::edited(
+                ui.radio_value(&mut self.variant, DeformVariant::Expression, "expression")
+                    .changed(),
+            );
+        });
+
+        if let DeformVariant::Program(program) = self.variant {
+            if self.variant != variant {
+                self.set_params(program.params());
+            }
+        }
+
+        ui.separator();
+
+        // only the variables the chosen deformation uses
+        let mut update = |name: &str, variable: &mut dyn AdjustableVariable| {
+            let changes = variable.update(UpdateVariableParams {
+                ui: &mut *ui,
+                osc_ctx,
+                name: name.to_string(),
+                time,
+            });
+            ui.separator();
+            changes
+        };
+
+        match self.variant {
+            DeformVariant::Program(program) => {
+                changes |= update("exponent", &mut self.exponent);
+                if program.has_coefficient() {
+                    changes |= update("coefficient", &mut self.coefficient);
+                }
+                if program.has_frequency() {
+                    changes |= update("frequency", &mut self.frequency);
+                }
+                changes |= update("stretch", &mut self.stretch);
+            }
+            DeformVariant::Expression => {
+                changes |= update("x'", &mut self.x);
+                changes |= update("y'", &mut self.y);
+            }
+        }
+        changes |= update("resolution", &mut self.resolution);
+
+        changes
+    }
+}
+
+impl Default for Deform {
+    fn default() -> Self {
+        // the point's coordinates are set for each point, zero until then
+        let point = ["x", "y", "r", "θ", "theta"];
+        let ctx = Context::new(
+            &point
+                .iter()
+                .map(|key| (key.to_string(), 0.0))
+                .chain([("pi".to_string(), PI)])
+                .collect::<Vec<_>>(),
+        );
+        let ctx_ext = point
+            .iter()
+            .map(|key| (key.to_string(), ()))
+            .collect::<HashMap<_, _>>();
+        let expression = |expr: String| {
+            ExpressionF32::new(
+                expr.clone(),
+                expr,
+                ctx.clone(),
+                ctx_ext.clone(),
+                0.0,
+                -1.0..=1.0,
+                0.01,
+            )
+        };
+
+        let program = Deformation::Program1;
+        let mut self_ = Self {
+            variant: DeformVariant::Program(program),
+            exponent: F32::new(1.0, F32Variant::None(0.25..=8.0)),
+            coefficient: F32::new(0.0, F32Variant::None(-1.0..=1.0)),
+            frequency: F32::new(0.0, F32Variant::None(0.0..=24.0)),
+            stretch: F32::new(1.0, F32Variant::None(0.25..=4.0)),
+            x: expression(Deform::default_x_expr()),
+            y: expression(Deform::default_y_expr()),
+            resolution: F32::new(0.01, F32Variant::Length),
+        };
+        self_.set_params(program.params());
+        self_
+    }
+}
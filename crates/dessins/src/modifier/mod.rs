@@ -0,0 +1,159 @@
+use crate::{
+    adjustable_dessin::{animate_from_reflect, animations_from_reflect, AdjustableDessin},
+    adjustable_variable::types::Context,
+    animation::Animation,
+    shapes::Shapes,
+};
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub use deformation::Deform;
+pub use symmetry::Symmetry;
+
+pub mod deformation;
+pub mod symmetry;
+
+// post-processing of a dessin's shapes, whatever its variant
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Modifier {
+    Symmetry(Symmetry),
+    Deformation(Deform),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ModifierVariant {
+    Symmetry,
+    Deformation,
+}
+
+// applied in order to the dessin's shapes
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Modifiers(Vec<Modifier>);
+
+impl ModifierVariant {
+    pub const ALL: &'static [(Self, &'static str)] = &[
+        (Self::Symmetry, "symmetry"),
+        (Self::Deformation, "deformation"),
+    ];
+
+    pub fn get_modifier(&self) -> Modifier {
+        match self {
+            Self::Symmetry => Modifier::Symmetry(Symmetry::default()),
+            Self::Deformation => Modifier::Deformation(Deform::default()),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        Self::ALL
+            .iter()
+            .find(|(variant, _)| variant == self)
+            .map(|(_, name)| *name)
+            .expect("every variant has a name")
+    }
+}
+
+impl Modifier {
+    pub fn get_variant(&self) -> ModifierVariant {
+        match self {
+            Self::Symmetry(_) => ModifierVariant::Symmetry,
+            Self::Deformation(_) => ModifierVariant::Deformation,
+        }
+    }
+
+    pub fn apply(&self, shapes: &Shapes) -> Shapes {
+        match self {
+            Self::Symmetry(symmetry) => symmetry.params().apply(shapes),
+            Self::Deformation(deform) => deform.apply(shapes),
+        }
+    }
+
+    pub fn update(&mut self, ui: &mut egui::Ui, osc_ctx: &Context, time: Time<Virtual>) -> bool {
+        match self {
+            Self::Symmetry(symmetry) => symmetry.update_variables(ui, osc_ctx, time).changed,
+            Self::Deformation(deform) => deform.update_variables(ui, osc_ctx, time).changed,
+        }
+    }
+
+    pub fn animate(&mut self, time: Time<Virtual>) {
+        match self {
+            Self::Symmetry(symmetry) => animate_from_reflect(symmetry, time),
+            Self::Deformation(deform) => animate_from_reflect(deform, time),
+        }
+    }
+
+    pub fn animations(&self) -> Vec<&Animation> {
+        match self {
+            Self::Symmetry(symmetry) => animations_from_reflect(symmetry),
+            Self::Deformation(deform) => animations_from_reflect(deform),
+        }
+    }
+}
+
+impl Modifiers {
+    pub fn apply(&self, shapes: &Shapes) -> Shapes {
+        self.0
+            .iter()
+            .fold(shapes.clone(), |shapes, modifier| modifier.apply(&shapes))
+    }
+
+    pub fn animate(&mut self, time: Time<Virtual>) {
+        self.0
+            .iter_mut()
+            .for_each(|modifier| modifier.animate(time));
+    }
+
+    pub fn animations(&self) -> Vec<&Animation> {
+        self.0
+            .iter()
+            .flat_map(|modifier| modifier.animations())
+            .collect()
+    }
+
+    pub fn update(&mut self, ui: &mut egui::Ui, osc_ctx: &Context, time: Time<Virtual>) -> bool {
+        let mut changed = false;
+        let mut moved = None;
+        let mut removed = None;
+
+        for (index, modifier) in self.0.iter_mut().enumerate() {
+            ui.push_id(index, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(modifier.get_variant().name());
+                    if ui.small_button("up").clicked() {
+                        moved = index.checked_sub(1).map(|above| (index, above));
+                    }
+                    if ui.small_button("down").clicked() {
+                        moved = Some((index, index + 1));
+                    }
+                    if ui.small_button("remove").clicked() {
+                        removed = Some(index);
+                    }
+                });
+                changed |= modifier.update(ui, osc_ctx, time);
+            });
+            ui.separator();
+        }
+
+        if let Some((from, to)) = moved {
+            if to < self.0.len() {
+                self.0.swap(from, to);
+                changed = true;
+            }
+        }
+
+        if let Some(index) = removed {
+            self.0.remove(index);
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            for (variant, name) in ModifierVariant::ALL {
+                if ui.button(format!("add {}", name)).clicked() {
+                    self.0.push(variant.get_modifier());
+                    changed = true;
+                }
+            }
+        });
+
+        changed
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{
+    adjustable_dessin::AdjustableDessin,
+    adjustable_variable::{
+        types::{Context, F32Variant, Pt2, F32, U32},
+        AdjustableVariable, Changes, UpdateVariableParams,
+    },
+};
+use dessins_core::modifier::symmetry::{self, SymmetryGroup, WallpaperGroup};
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Default)]
+pub struct Symmetry {
+    #[reflect(ignore)]
+    pub variant: SymmetryVariant,
+    #[reflect(ignore)]
+    pub wallpaper: WallpaperGroup,
+    pub n: U32,      // # rotations
+    pub angle: F32,  // of the mirror axis
+    pub cell: F32,   // distance between two lattice points
+    pub count: U32,  // # cells on each side of the centre
+    pub offset: Pt2, // of the motif from the centre of symmetry
+    pub scale: F32,  // of the motif
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SymmetryVariant {
+    Rotation,
+    Mirror,
+    Dihedral,
+    Wallpaper,
+}
+
+impl SymmetryVariant {
+    pub const ALL: &'static [(Self, &'static str)] = &[
+        (Self::Rotation, "rotation"),
+        (Self::Mirror, "mirror"),
+        (Self::Dihedral, "dihedral"),
+        (Self::Wallpaper, "wallpaper"),
+    ];
+}
+
+impl Symmetry {
+    pub fn params(&self) -> symmetry::Symmetry {
+        let group = match self.variant {
+            SymmetryVariant::Rotation => SymmetryGroup::Rotation {
+                n: self.n.get_value(),
+            },
+            SymmetryVariant::Mirror => SymmetryGroup::Mirror {
+                angle: self.angle.get_value(),
+            },
+            SymmetryVariant::Dihedral => SymmetryGroup::Dihedral {
+                n: self.n.get_value(),
+                angle: self.angle.get_value(),
+            },
+            SymmetryVariant::Wallpaper => SymmetryGroup::Wallpaper {
+                group: self.wallpaper,
+                cell: self.cell.get_value(),
+                count: self.count.get_value(),
+            },
+        };
+
+        symmetry::Symmetry {
+            group,
+            offset: self.offset.get_value(),
+            scale: self.scale.get_value(),
+        }
+    }
+}
+
+impl AdjustableDessin for Symmetry {
+    fn update_variables(
+        &mut self,
+        ui: &mut egui::Ui,
+        osc_ctx: &Context,
+        time: Time<Virtual>,
+    ) -> Changes {
+        let mut changes = Changes::default();
+
+        ui.horizontal_wrapped(|ui| {
+            for (variant, name) in SymmetryVariant::ALL {
+                changes |=
+                    Changes::edited(ui.radio_value(&mut self.variant, *variant, *name).changed());
+            }
+        });
+
+        if self.variant == SymmetryVariant::Wallpaper {
+            ui.horizontal_wrapped(|ui| {
+                for (group, name) in WallpaperGroup::ALL {
+                    changes |= Changes::edited(
+                        ui.radio_value(&mut self.wallpaper, *group, *name).changed(),
+                    );
+                }
+            });
+        }
+
+        ui.separator();
+
+        // only the variables the chosen symmetry uses
+        let mut update = |name: &str, variable: &mut dyn AdjustableVariable| {
+            let changes = variable.update(UpdateVariableParams {
+                ui: &mut *ui,
+                osc_ctx,
+                name: name.to_string(),
+                time,
+            });
+            ui.separator();
+            changes
+        };
+
+        match self.variant {
+            SymmetryVariant::Rotation => changes |= update("n", &mut self.n),
+            SymmetryVariant::Mirror => changes |= update("angle", &mut self.angle),
+            SymmetryVariant::Dihedral => {
+                changes |= update("n", &mut self.n);
+                changes |= update("angle", &mut self.angle);
+            }
+            SymmetryVariant::Wallpaper => {
+                changes |= update("cell", &mut self.cell);
+                changes |= update("count", &mut self.count);
+            }
+        }
+        changes |= update("offset", &mut self.offset);
+        changes |= update("scale", &mut self.scale);
+
+        changes
+    }
+}
+
+impl Default for Symmetry {
+    fn default() -> Self {
+        Self {
+            variant: SymmetryVariant::Rotation,
+            wallpaper: WallpaperGroup::P4m,
+            n: U32::new(6, 1..=24),
+            angle: F32::new(0.0, F32Variant::Angle),
+            cell: F32::new(0.25, F32Variant::Length),
+            count: U32::new(2, 1..=4),
+            offset: Pt2::new(pt2(0.0, 0.0)),
+            scale: F32::new(1.0, F32Variant::None(0.05..=2.0)),
+        }
+    }
+}
@@ -0,0 +1,149 @@
+// osc address patterns, matched part by part between the slashes: `?` is any character, `*` any
+// characters, `[a-z]` one of and `[!a-z]` none of the characters, `{foo,bar}` any of the strings
+
+pub fn is_pattern(address: &str) -> bool {
+    address.contains(['?', '*', '[', '{'])
+}
+
+pub fn matches(pattern: &str, address: &str) -> bool {
+    let patterns = pattern.split('/');
+    let parts = address.split('/');
+    patterns.clone().count() == parts.clone().count()
+        && patterns
+            .zip(parts)
+            .all(|(pattern, part)| matches_part(pattern, part))
+}
+
+pub fn matches_part(pattern: &str, part: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let part = part.chars().collect::<Vec<_>>();
+    match_chars(&pattern, &part)
+}
+
+fn match_chars(pattern: &[char], part: &[char]) -> bool {
+    let Some((&first, rest)) = pattern.split_first() else {
+        return part.is_empty();
+    };
+
+    match first {
+        '*' => (0..=part.len()).any(|skipped| match_chars(rest, &part[skipped..])),
+        '?' => !part.is_empty() && match_chars(rest, &part[1..]),
+        '[' => {
+            let Some(end) = rest.iter().position(|c| *c == ']') else {
+                return false;
+            };
+            match part.split_first() {
+                Some((c, part)) => in_set(&rest[..end], *c) && match_chars(&rest[end + 1..], part),
+                None => false,
+            }
+        }
+        '{' => {
+            let Some(end) = rest.iter().position(|c| *c == '}') else {
+                return false;
+            };
+            rest[..end].split(|c| *c == ',').any(|alternative| {
+                part.starts_with(alternative)
+                    && match_chars(&rest[end + 1..], &part[alternative.len()..])
+            })
+        }
+        c => part.first() == Some(&c) && match_chars(rest, &part[1..]),
+    }
+}
+
+fn in_set(set: &[char], c: char) -> bool {
+    let (negated, set) = match set.split_first() {
+        Some(('!', set)) => (true, set),
+        _ => (false, set),
+    };
+
+    let mut found = false;
+    let mut index = 0;
+    while index < set.len() {
+        if index + 2 < set.len() && set[index + 1] == '-' {
+            found |= (set[index]..=set[index + 2]).contains(&c);
+            index += 3;
+        } else {
+            found |= set[index] == c;
+            index += 1;
+        }
+    }
+
+    found != negated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn star_matches_any_characters() {
+        assert!(matches_part("*", ""));
+        assert!(matches_part("*", "positions"));
+        assert!(matches_part("pos*", "pos"));
+        assert!(matches_part("p*s", "positions"));
+        assert!(!matches_part("p*s", "position"));
+    }
+
+    #[test]
+    fn question_mark_matches_one_character() {
+        assert!(matches_part("p?", "p0"));
+        assert!(!matches_part("p?", "p"));
+        assert!(!matches_part("p?", "p10"));
+    }
+
+    #[test]
+    fn set_matches_one_of_its_characters() {
+        assert!(matches_part("[a-z]", "k"));
+        assert!(!matches_part("[a-z]", "K"));
+        assert!(!matches_part("[a-z]", "kk"));
+        assert!(matches_part("p[0-2x]", "p1"));
+        assert!(matches_part("p[0-2x]", "px"));
+        assert!(!matches_part("p[0-2x]", "p3"));
+        // a dash at the end is not a range
+        assert!(matches_part("[a-]", "-"));
+        assert!(!matches_part("[a-]", "b"));
+        assert!(!matches_part("[a-z", "k"));
+    }
+
+    #[test]
+    fn negated_set_matches_none_of_its_characters() {
+        assert!(matches_part("[!a-z]", "K"));
+        assert!(!matches_part("[!a-z]", "k"));
+        assert!(matches_part("p[!0-9]", "px"));
+        assert!(!matches_part("p[!0-9]", "p5"));
+        assert!(!matches_part("[!a-z]", ""));
+    }
+
+    #[test]
+    fn alternatives_match_any_of_the_strings() {
+        assert!(matches_part("{a,b}", "a"));
+        assert!(matches_part("{a,b}", "b"));
+        assert!(!matches_part("{a,b}", "c"));
+        assert!(!matches_part("{a,b}", "ab"));
+        assert!(matches_part("{k,n}[0-9]", "n2"));
+    }
+
+    #[test]
+    fn empty_alternative_matches_an_empty_string() {
+        assert!(matches_part("point{,s}", "point"));
+        assert!(matches_part("point{,s}", "points"));
+        assert!(!matches_part("point{,s}", "pointss"));
+    }
+
+    #[test]
+    fn matches_part_by_part() {
+        assert!(matches("/dessin/*/k", "/dessin/dragon/k"));
+        assert!(!matches("/dessin/*/k", "/dessin/dragon/n"));
+        // a star does not cross slashes
+        assert!(!matches("/dessin/*", "/dessin/dragon/k"));
+        assert!(!matches("/dessin/*/k", "/dessin/dragon"));
+    }
+
+    #[test]
+    fn detects_patterns() {
+        assert!(is_pattern("/var/*"));
+        assert!(is_pattern("/var/k[0-9]"));
+        assert!(is_pattern("/var/{k,n}"));
+        assert!(!is_pattern("/var/k"));
+    }
+}
@@ -0,0 +1,178 @@
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// an osc control driving a variable of the dessin
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Binding {
+    pub source: String, // the control's address, or its key in key/value bundles and /var
+    pub target: String, // the variable with a path into it, as `set_variable` takes it
+    pub input: (f32, f32),
+    pub output: (f32, f32), // in the unit of the variable's slider
+    pub smoothing: f32,     // 0 follows the control at once, closer to 1 slower
+    pub invert: bool,
+    #[serde(skip)]
+    received: Option<f32>, // mapped to the output
+    #[serde(skip)]
+    value: Option<f32>, // last set, on its way to `received`
+}
+
+impl Binding {
+    pub fn new(source: String, target: String) -> Self {
+        Self {
+            source,
+            target,
+            input: (0.0, 1.0),
+            output: (0.0, 1.0),
+            smoothing: 0.0,
+            invert: false,
+            received: None,
+            value: None,
+        }
+    }
+
+    pub fn receive(&mut self, value: f32) {
+        let (input_start, input_end) = self.input;
+        let (output_start, output_end) = self.output;
+
+        let mut t = if input_end != input_start {
+            ((value - input_start) / (input_end - input_start)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        if self.invert {
+            t = 1.0 - t;
+        }
+
+        self.received = Some(output_start + t * (output_end - output_start));
+    }
+
+    // the value to set this frame, none once it has reached the control's
+    pub fn step(&mut self) -> Option<f32> {
+        let received = self.received?;
+        let value = match self.value {
+            Some(value) if (received - value).abs() <= f32::EPSILON => return None,
+            Some(value) => {
+                let value = value + (received - value) * (1.0 - self.smoothing);
+                if (received - value).abs() < 1e-4 {
+                    received
+                } else {
+                    value
+                }
+            }
+            None => received,
+        };
+        self.value = Some(value);
+        Some(value)
+    }
+
+    fn update(&mut self, ui: &mut egui::Ui, index: usize) {
+        egui::Grid::new(("binding", index))
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("control");
+                ui.text_edit_singleline(&mut self.source);
+                ui.end_row();
+
+                ui.label("variable");
+                ui.text_edit_singleline(&mut self.target);
+                ui.end_row();
+
+                ui.label("input");
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.input.0).speed(0.01));
+                    ui.add(egui::DragValue::new(&mut self.input.1).speed(0.01));
+                });
+                ui.end_row();
+
+                ui.label("output");
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut self.output.0).speed(0.01));
+                    ui.add(egui::DragValue::new(&mut self.output.1).speed(0.01));
+                });
+                ui.end_row();
+
+                ui.label("smoothing");
+                ui.add(egui::Slider::new(&mut self.smoothing, 0.0..=0.99));
+                ui.end_row();
+            });
+        ui.checkbox(&mut self.invert, "invert");
+    }
+}
+
+pub fn update_bindings(ui: &mut egui::Ui, bindings: &mut Vec<Binding>) {
+    if let Some(target) = learning(ui.ctx()) {
+        ui.horizontal(|ui| {
+            ui.label(format!("move a control to bind it to {}", target));
+            if ui.button("cancel").clicked() {
+                stop_learning(ui.ctx());
+            }
+        });
+    }
+
+    if bindings.is_empty() {
+        ui.label("no bindings, click a variable's learn button");
+    }
+
+    let mut removed = None;
+    for (index, binding) in bindings.iter_mut().enumerate() {
+        ui.separator();
+        binding.update(ui, index);
+        if ui.button("remove").clicked() {
+            removed = Some(index);
+        }
+    }
+
+    if let Some(index) = removed {
+        bindings.remove(index);
+    }
+}
+
+// the variable waiting for a control is kept in egui's memory, the variables' ui being drawn
+// without any osc state but the context
+fn learning_id() -> egui::Id {
+    egui::Id::new("osc learning")
+}
+
+fn learnable_id() -> egui::Id {
+    egui::Id::new("osc learnable")
+}
+
+pub fn learning(ctx: &egui::Context) -> Option<String> {
+    ctx.data(|data| data.get_temp::<String>(learning_id()))
+}
+
+pub fn stop_learning(ctx: &egui::Context) {
+    ctx.data_mut(|data| data.remove::<String>(learning_id()));
+}
+
+// only the dessin's variables can be bound, the other variables' ui has no learn button
+pub fn with_learn_buttons<R>(
+    ui: &mut egui::Ui,
+    add_contents: impl FnOnce(&mut egui::Ui) -> R,
+) -> R {
+    ui.data_mut(|data| data.insert_temp(learnable_id(), true));
+    let result = add_contents(ui);
+    ui.data_mut(|data| data.remove::<bool>(learnable_id()));
+    result
+}
+
+pub fn learn_button(ui: &mut egui::Ui, name: &str) {
+    if !ui
+        .data(|data| data.get_temp::<bool>(learnable_id()))
+        .unwrap_or(false)
+    {
+        return;
+    }
+
+    let learning = learning(ui.ctx()).as_deref() == Some(name);
+    let response = ui
+        .selectable_label(learning, "learn")
+        .on_hover_text("bind the next osc control that moves to this variable");
+    if response.clicked() {
+        if learning {
+            stop_learning(ui.ctx());
+        } else {
+            ui.data_mut(|data| data.insert_temp(learning_id(), name.to_string()));
+        }
+    }
+}
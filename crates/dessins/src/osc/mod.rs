@@ -0,0 +1,410 @@
+use crate::{
+    adjustable_variable::types::Context,
+    dessin_with_variables::{DessinVariables, DessinWithVariables},
+    layer::Layer,
+};
+use address::{is_pattern, matches_part};
+use anyhow::{anyhow, bail};
+use binding::{learning, stop_learning, Binding};
+use nannou::prelude::*;
+use nannou_osc::{rosc::OscPacket, Bundle, Message, Packet, Type};
+use receiver::{OscReceiver, DEFAULT_PORT};
+use sender::OscSender;
+use state::OscState;
+use std::{
+    collections::{HashMap, VecDeque},
+    net::SocketAddr,
+};
+
+pub mod address;
+pub mod binding;
+pub mod receiver;
+pub mod sender;
+pub mod state;
+
+const DEFAULT_MONITOR_LENGTH: usize = 20;
+const DESSIN_PREFIX: &str = "/dessin/";
+const VARIABLE_PREFIX: &str = "/var/";
+
+pub struct Osc {
+    receivers: Vec<OscReceiver>,
+    sender: OscSender,
+    monitor: VecDeque<String>, // the last messages received, newest first
+    monitor_length: usize,
+    state: OscState, // the latest value of each key, for the expressions
+    dessin_messages: Vec<Message>, // addressed at the dessin's variables, set once
+    inputs: Vec<(String, f32)>, // the controls that moved since the last frame, for bindings
+    error: Option<String>,
+}
+
+impl Osc {
+    // `time` is the real one, osc values keep moving while the app's time is paused or slowed
+    pub fn update(&mut self, ui: &mut egui::Ui, time: Time<Real>) -> Context {
+        let packets = self
+            .receivers
+            .iter()
+            .flat_map(OscReceiver::packets)
+            .collect::<Vec<_>>();
+        for (packet, source) in &packets {
+            for message in messages(packet) {
+                self.monitor(*source, message);
+                if let Err(err) = self.receive(message, time) {
+                    self.error = Some(format!("{}: {:#}", message.addr, err));
+                }
+            }
+        }
+
+        let mut removed = None;
+        let removable = self.receivers.len() > 1;
+        for (index, receiver) in self.receivers.iter_mut().enumerate() {
+            if receiver.update(ui, removable) {
+                removed = Some(index);
+            }
+        }
+        if let Some(index) = removed {
+            self.receivers.remove(index);
+        }
+
+        if ui.button("add receiver").clicked() {
+            let port = self.receivers.iter().map(OscReceiver::port).max();
+            let port = port.map_or(DEFAULT_PORT, |port| port.saturating_add(1));
+            self.receivers.push(OscReceiver::new(port));
+        }
+
+        ui.label("/dessin/<dessin>/<variable> <value>, /var/<key> <value>")
+            .on_hover_text("addresses can be patterns, e.g. /dessin/*/r{1,2}");
+        if let Some(error) = &self.error {
+            ui.label(error);
+        }
+
+        ui.collapsing("monitor", |ui| {
+            ui.horizontal(|ui| {
+                ui.label("last");
+                ui.add(egui::DragValue::new(&mut self.monitor_length).range(1..=500));
+                ui.label("messages");
+                if ui.button("clear").clicked() {
+                    self.monitor.clear();
+                }
+            });
+            self.monitor.truncate(self.monitor_length);
+
+            if self.monitor.is_empty() {
+                ui.label("no messages received");
+            }
+            for message in &self.monitor {
+                ui.monospace(message);
+            }
+        });
+
+        ui.collapsing("values", |ui| self.state.update(ui, time));
+
+        ui.collapsing("output", |ui| self.sender.update(ui));
+
+        self.state.step(time);
+        self.state.context()
+    }
+
+    // after a change to the layer, `time` being the animations' one
+    pub fn send(&mut self, layer: &Layer, time: Time<Virtual>) {
+        self.sender.send(layer, time);
+    }
+
+    // sets the variables addressed at the dessin since the last call, returns whether any was
+    pub fn apply(&mut self, variables: &mut DessinVariables) -> bool {
+        let mut changed = false;
+
+        for message in std::mem::take(&mut self.dessin_messages) {
+            match set_dessin_variable(&message, variables) {
+                Ok(set) => changed |= set,
+                Err(err) => self.error = Some(format!("{}: {:#}", message.addr, err)),
+            }
+        }
+
+        changed
+    }
+
+    // binds the control that moved to the variable learning one, then sets the bound variables
+    pub fn bind(&mut self, ctx: &egui::Context, dessin: &mut DessinWithVariables) -> bool {
+        let inputs = std::mem::take(&mut self.inputs);
+
+        if let (Some(target), Some((source, _))) = (learning(ctx), inputs.last()) {
+            dessin.bindings.retain(|binding| binding.target != target);
+            dessin.bindings.push(Binding::new(source.clone(), target));
+            stop_learning(ctx);
+        }
+
+        let mut changed = false;
+        for binding in &mut dessin.bindings {
+            inputs
+                .iter()
+                .filter(|(source, _)| *source == binding.source)
+                .for_each(|(_, value)| binding.receive(*value));
+
+            if let Some(value) = binding.step() {
+                match dessin
+                    .variables
+                    .set_variable(&binding.target, &value.to_string())
+                {
+                    Ok(()) => changed = true,
+                    Err(err) => self.error = Some(format!("{}: {:#}", binding.target, err)),
+                }
+            }
+        }
+
+        changed
+    }
+
+    fn monitor(&mut self, source: SocketAddr, message: &Message) {
+        let args = message
+            .args
+            .iter()
+            .map(|arg| match arg {
+                Type::String(value) => format!("{:?}", value),
+                arg => number(arg).map_or_else(|| format!("{:?}", arg), |value| value.to_string()),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.monitor
+            .push_front(format!("{} {} {}", source, message.addr, args));
+        self.monitor.truncate(self.monitor_length);
+    }
+
+    fn receive(&mut self, message: &Message, time: Time<Real>) -> anyhow::Result<()> {
+        if message.addr.starts_with(DESSIN_PREFIX) {
+            self.dessin_messages.push(message.clone());
+            return Ok(());
+        }
+
+        if let Some(key) = message.addr.strip_prefix(VARIABLE_PREFIX) {
+            let value = message
+                .args
+                .first()
+                .and_then(number)
+                .ok_or_else(|| anyhow!("expected a number"))?;
+
+            if is_pattern(key) {
+                self.state.receive_matching(key, value, time);
+            } else {
+                self.state.receive(key, value, time);
+                self.inputs.push((key.to_string(), value));
+            }
+            return Ok(());
+        }
+
+        let key_values = key_values(message);
+        if key_values.is_empty() {
+            // a control of a device, known by its address
+            if let Some(value) = message.args.first().and_then(number) {
+                self.inputs.push((message.addr.clone(), value));
+            }
+        }
+        for (key, value) in key_values {
+            self.state.receive(&key, value, time);
+            self.inputs.push((key, value));
+        }
+        Ok(())
+    }
+}
+
+// every message of the packet, those of nested bundles included
+fn messages(packet: &Packet) -> Vec<&Message> {
+    let mut messages = vec![];
+    match packet {
+        Packet::Message(message) => messages.push(message),
+        Packet::Bundle(bundle) => bundle_messages(bundle, &mut messages),
+    }
+    messages
+}
+
+fn bundle_messages<'a>(bundle: &'a Bundle, messages: &mut Vec<&'a Message>) {
+    for packet in &bundle.content {
+        match packet {
+            OscPacket::Message(message) => messages.push(message),
+            OscPacket::Bundle(bundle) => bundle_messages(bundle, messages),
+        }
+    }
+}
+
+// alternating keys and values whatever the address, the keys prefixed by the value of "s"
+fn key_values(message: &Message) -> HashMap<String, f32> {
+    let mut map = HashMap::new();
+    let mut prefix = "";
+
+    for pair in message.args.chunks(2) {
+        if let [Type::String(key), value] = pair {
+            match value {
+                Type::String(s) if key == "s" => prefix = s,
+                value => {
+                    if let Some(value) = number(value) {
+                        map.insert(key.clone(), value);
+                    }
+                }
+            }
+        }
+    }
+
+    map.into_iter()
+        .map(|(key, value)| (format!("{}{}", prefix, key), value))
+        .collect()
+}
+
+// returns false for the messages addressed at another dessin
+fn set_dessin_variable(message: &Message, variables: &mut DessinVariables) -> anyhow::Result<bool> {
+    let (dessin, name, path) = dessin_variable(&message.addr)?;
+
+    // spaces are not allowed in addresses
+    let variant = variables.get_variant().name();
+    if !matches_part(dessin, &variant.replace(' ', "-"))
+        && !matches_part(dessin, &variant.replace(' ', "_"))
+    {
+        return Ok(false);
+    }
+
+    let value = message
+        .args
+        .first()
+        .ok_or_else(|| anyhow!("expected a value"))
+        .and_then(argument)?;
+
+    let names = variables
+        .variable_names()
+        .into_iter()
+        .filter(|variable| matches_part(name, variable))
+        .collect::<Vec<_>>();
+    if names.is_empty() {
+        bail!("no variable of the {} matches {}", variant, name);
+    }
+
+    for name in names {
+        variables.set_variable(&format!("{}{}", name, path), &value)?;
+    }
+
+    Ok(true)
+}
+
+// `/dessin/<dessin>/<variable>` followed by a path into the variable, e.g.
+// `/dessin/jolygon/positions/2/y` gives `jolygon`, `positions` and `[2].y`
+fn dessin_variable(address: &str) -> anyhow::Result<(&str, &str, String)> {
+    let address = address
+        .strip_prefix(DESSIN_PREFIX)
+        .ok_or_else(|| anyhow!("expected {}<dessin>/<variable>", DESSIN_PREFIX))?;
+    let mut parts = address.split('/');
+    let (Some(dessin), Some(name)) = (parts.next(), parts.next()) else {
+        bail!("expected {}<dessin>/<variable>", DESSIN_PREFIX);
+    };
+
+    let path = parts
+        .map(|part| match part.parse::<usize>() {
+            Ok(index) => format!("[{}]", index),
+            Err(_) => format!(".{}", part),
+        })
+        .collect::<String>();
+
+    Ok((dessin, name, path))
+}
+
+fn number(arg: &Type) -> Option<f32> {
+    match arg {
+        Type::Float(value) => Some(*value),
+        Type::Double(value) => Some(*value as f32),
+        Type::Int(value) => Some(*value as f32),
+        Type::Long(value) => Some(*value as f32),
+        Type::Bool(value) => Some(if *value { 1.0 } else { 0.0 }),
+        _ => None,
+    }
+}
+
+// as `set_variable` takes it, strings being expressions or colours
+fn argument(arg: &Type) -> anyhow::Result<String> {
+    match arg {
+        Type::String(value) => Ok(value.clone()),
+        arg => number(arg)
+            .map(|value| value.to_string())
+            .ok_or_else(|| anyhow!("unsupported argument {:?}", arg)),
+    }
+}
+
+impl Default for Osc {
+    fn default() -> Self {
+        Self {
+            receivers: vec![OscReceiver::new(DEFAULT_PORT)],
+            sender: OscSender::default(),
+            monitor: VecDeque::new(),
+            monitor_length: DEFAULT_MONITOR_LENGTH,
+            state: OscState::default(),
+            dessin_messages: vec![],
+            inputs: vec![],
+            error: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(args: Vec<Type>) -> Message {
+        Message {
+            addr: "/dirt/play".to_string(),
+            args,
+        }
+    }
+
+    fn key(key: &str) -> Type {
+        Type::String(key.to_string())
+    }
+
+    #[test]
+    fn dessin_variable_rewrites_the_path() {
+        let (dessin, name, path) = dessin_variable("/dessin/jolygon/positions/2/y").unwrap();
+        assert_eq!(dessin, "jolygon");
+        assert_eq!(format!("{}{}", name, path), "positions[2].y");
+    }
+
+    #[test]
+    fn dessin_variable_without_path() {
+        let (dessin, name, path) = dessin_variable("/dessin/dragon/n").unwrap();
+        assert_eq!((dessin, name, path.as_str()), ("dragon", "n", ""));
+    }
+
+    #[test]
+    fn dessin_variable_needs_a_variable() {
+        assert!(dessin_variable("/dessin/dragon").is_err());
+        assert!(dessin_variable("/var/k").is_err());
+    }
+
+    #[test]
+    fn key_values_alternate() {
+        let message = message(vec![
+            key("gain"),
+            Type::Float(0.5),
+            key("vowel"),
+            key("a"), // not a number
+            key("speed"),
+            Type::Int(2),
+        ]);
+
+        assert_eq!(
+            key_values(&message),
+            HashMap::from([("gain".to_string(), 0.5), ("speed".to_string(), 2.0)])
+        );
+    }
+
+    #[test]
+    fn key_values_are_prefixed_by_s() {
+        // tidal's sound names every key, also those before it
+        let message = message(vec![
+            key("cps"),
+            Type::Float(0.5),
+            key("s"),
+            key("bd"),
+            key("n"),
+            Type::Int(3),
+        ]);
+
+        assert_eq!(
+            key_values(&message),
+            HashMap::from([("bdcps".to_string(), 0.5), ("bdn".to_string(), 3.0)])
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use nannou::prelude::*;
+use nannou_osc::{Packet, Receiver};
+use std::net::SocketAddr;
+
+pub const DEFAULT_PORT: u16 = 34254;
+const DEFAULT_ADDRESS: &str = "0.0.0.0"; // every interface
+
+// a socket listening for osc packets, e.g. one for tidal cycles and one for a tablet
+pub struct OscReceiver {
+    address: String,
+    port: u16,
+    receiver: Option<Receiver>,
+    error: Option<String>, // of the last bind
+}
+
+impl OscReceiver {
+    pub fn new(port: u16) -> Self {
+        Self {
+            address: DEFAULT_ADDRESS.to_string(),
+            port,
+            receiver: None,
+            error: None,
+        }
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    pub fn connect(&mut self) {
+        let address = match format!("{}:{}", self.address.trim(), self.port).parse::<SocketAddr>() {
+            Ok(address) => address,
+            Err(err) => {
+                self.receiver = None;
+                self.error = Some(format!("invalid address {}: {}", self.address, err));
+                return;
+            }
+        };
+
+        match Receiver::bind_to(address) {
+            Ok(receiver) => {
+                self.receiver = Some(receiver);
+                self.error = None;
+            }
+            Err(err) => {
+                self.receiver = None;
+                self.error = Some(format!("could not listen on {}: {}", address, err));
+            }
+        }
+    }
+
+    // the packets received since the last call, with the address they came from
+    pub fn packets(&self) -> Vec<(Packet, SocketAddr)> {
+        match &self.receiver {
+            Some(receiver) => receiver.try_iter().collect(),
+            None => vec![],
+        }
+    }
+
+    // returns whether the receiver should be removed
+    pub fn update(&mut self, ui: &mut egui::Ui, removable: bool) -> bool {
+        let mut removed = false;
+        let connected = self.receiver.is_some();
+
+        ui.horizontal(|ui| {
+            ui.add_enabled_ui(!connected, |ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.address).desired_width(100.0));
+                ui.add(egui::DragValue::new(&mut self.port).range(1..=u16::MAX));
+            });
+
+            if connected {
+                if ui.button("disconnect").clicked() {
+                    self.receiver = None;
+                }
+            } else if ui.button("connect").clicked() {
+                self.connect();
+            }
+
+            removed = ui
+                .add_enabled(removable, egui::Button::new("remove").small())
+                .clicked();
+        });
+
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+
+        removed
+    }
+}
@@ -0,0 +1,153 @@
+use super::DESSIN_PREFIX;
+use crate::{export::frames::loop_period, layer::Layer, shapes::NP};
+use anyhow::{anyhow, Context as _};
+use nannou::prelude::*;
+use nannou_osc::{sender, Sender, Type};
+use std::net::{SocketAddr, ToSocketAddrs};
+
+const DEFAULT_HOST: &str = "127.0.0.1";
+const DEFAULT_PORT: u16 = 57120; // supercollider's
+
+// apart from the dessins' addresses, where a variable could be named like a statistic
+const STATS_PREFIX: &str = "/dessins/stats/";
+
+// sends the active dessin's variables and what it draws, for a sound engine to follow it
+pub struct OscSender {
+    host: String,
+    port: u16,
+    sender: Option<(Sender, SocketAddr)>,
+    error: Option<String>,
+}
+
+impl OscSender {
+    pub fn update(&mut self, ui: &mut egui::Ui) {
+        let connected = self.sender.is_some();
+
+        ui.horizontal(|ui| {
+            ui.add_enabled_ui(!connected, |ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.host).desired_width(100.0));
+                ui.add(egui::DragValue::new(&mut self.port).range(1..=u16::MAX));
+            });
+
+            if connected {
+                if ui.button("stop sending").clicked() {
+                    self.sender = None;
+                }
+            } else if ui.button("send").clicked() {
+                match open(&self.host, self.port) {
+                    Ok(sender) => {
+                        self.sender = Some(sender);
+                        self.error = None;
+                    }
+                    Err(err) => self.error = Some(format!("{:#}", err)),
+                }
+            }
+        });
+
+        ui.label("/dessin/<dessin>/<variable>, /dessins/stats/points, bounds, time and phase")
+            .on_hover_text("bounds in the unit of the position sliders, phase from 0 to 1");
+
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+    }
+
+    pub fn send(&mut self, layer: &Layer, time: Time<Virtual>) {
+        let Some((sender, target)) = &self.sender else {
+            return;
+        };
+
+        for (address, args) in messages(layer, time) {
+            if let Err(err) = sender.send((address.clone(), args), *target) {
+                self.error = Some(format!("could not send {}: {}", address, err));
+                return;
+            }
+        }
+    }
+}
+
+fn open(host: &str, port: u16) -> anyhow::Result<(Sender, SocketAddr)> {
+    let target = (host.trim(), port)
+        .to_socket_addrs()
+        .with_context(|| format!("invalid host {}", host))?
+        .next()
+        .with_context(|| format!("could not resolve {}", host))?;
+    let sender = sender().map_err(|err| anyhow!("could not open a socket: {}", err))?;
+    Ok((sender, target))
+}
+
+fn messages(layer: &Layer, time: Time<Virtual>) -> Vec<(String, Vec<Type>)> {
+    let variables = &layer.dessin.variables;
+    let variant = variables.get_variant().name().replace(' ', "-");
+
+    // the addresses `/dessin/...` messages are received at, e.g. positions[2].y at positions/2/y
+    let mut messages = variables
+        .values()
+        .into_iter()
+        .map(|(name, value)| {
+            let address = format!(
+                "{}{}/{}",
+                DESSIN_PREFIX,
+                variant,
+                name.replace(['[', '.'], "/").replace(']', "")
+            );
+            let arg = match value.parse::<f32>() {
+                Ok(value) => Type::Float(value),
+                Err(_) => Type::String(value),
+            };
+            (address, vec![arg])
+        })
+        .collect::<Vec<_>>();
+
+    let mut points = layer.points().iter().flatten().flatten();
+    let count = points.clone().count();
+    messages.push((stat("points"), vec![Type::Int(count as i32)]));
+
+    if let Some(first) = points.next() {
+        let (min, max) = points.fold((*first, *first), |(min, max), point| {
+            (min.min(*point), max.max(*point))
+        });
+        let (min, max) = (min / NP as f32, max / NP as f32);
+        messages.push((
+            stat("bounds"),
+            vec![
+                Type::Float(min.x),
+                Type::Float(min.y),
+                Type::Float(max.x),
+                Type::Float(max.y),
+            ],
+        ));
+    }
+
+    messages.push((stat("time"), vec![Type::Float(time.elapsed_secs())]));
+    if let Some(phase) = phase(layer, time) {
+        messages.push((stat("phase"), vec![Type::Float(phase)]));
+    }
+
+    messages
+}
+
+fn stat(name: &str) -> String {
+    format!("{}{}", STATS_PREFIX, name)
+}
+
+// where the animations are in their common loop, none if they never loop
+fn phase(layer: &Layer, time: Time<Virtual>) -> Option<f32> {
+    let mut animations = layer.dessin.variables.animations();
+    animations.extend(layer.modifiers.animations());
+
+    let period = loop_period(&animations).ok()?;
+
+    Some(time.elapsed_secs() % period / period)
+}
+
+impl Default for OscSender {
+    fn default() -> Self {
+        Self {
+            host: DEFAULT_HOST.to_string(),
+            port: DEFAULT_PORT,
+            sender: None,
+            error: None,
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use super::address::matches;
+use crate::adjustable_variable::types::Context;
+use nannou::prelude::*;
+use std::{collections::BTreeMap, time::Duration};
+
+// the latest osc value of each key, held across frames until another one is received
+#[derive(Default)]
+pub struct OscState {
+    keys: BTreeMap<String, KeyState>, // sorted for the ui
+}
+
+struct KeyState {
+    received: f32,
+    received_at: Duration, // of the real time
+    value: f32,            // as in the context, following `received` with some lag
+    lag: f32,              // seconds to get most of the way to a new value
+    timeout: Option<f32>,  // seconds without any message before going back to `default`
+    default: f32,
+}
+
+impl OscState {
+    pub fn receive(&mut self, key: &str, value: f32, time: Time<Real>) {
+        match self.keys.get_mut(key) {
+            Some(state) => {
+                state.received = value;
+                state.received_at = time.elapsed();
+            }
+            None => {
+                self.keys
+                    .insert(key.to_string(), KeyState::new(value, time.elapsed()));
+            }
+        }
+    }
+
+    // a pattern only reaches the keys already received
+    pub fn receive_matching(&mut self, pattern: &str, value: f32, time: Time<Real>) {
+        self.keys
+            .iter_mut()
+            .filter(|(key, _)| matches(pattern, key))
+            .for_each(|(_, state)| {
+                state.received = value;
+                state.received_at = time.elapsed();
+            });
+    }
+
+    // moves the values towards the received ones, or back to their default
+    pub fn step(&mut self, time: Time<Real>) {
+        let delta = time.delta_secs();
+        let now = time.elapsed();
+
+        for state in self.keys.values_mut() {
+            let timed_out = state.timeout.is_some_and(|timeout| {
+                now.saturating_sub(state.received_at).as_secs_f32() > timeout
+            });
+            let target = if timed_out {
+                state.default
+            } else {
+                state.received
+            };
+
+            state.value = if state.lag > 0.0 {
+                state.value + (target - state.value) * (1.0 - (-delta / state.lag).exp())
+            } else {
+                target
+            };
+        }
+    }
+
+    pub fn context(&self) -> Context {
+        let values = self
+            .keys
+            .iter()
+            .map(|(key, state)| (key.clone(), state.value))
+            .collect::<Vec<_>>();
+        Context::new(&values)
+    }
+
+    pub fn update(&mut self, ui: &mut egui::Ui, time: Time<Real>) {
+        if self.keys.is_empty() {
+            ui.label("no values received");
+            return;
+        }
+
+        let mut removed = None;
+        for (key, state) in self.keys.iter_mut() {
+            ui.horizontal(|ui| {
+                ui.monospace(key);
+                ui.label(format!("{:.3}", state.value));
+                let age = time.elapsed().saturating_sub(state.received_at);
+                ui.label(format!("{:.1}s ago", age.as_secs_f32()));
+                if ui.small_button("remove").clicked() {
+                    removed = Some(key.clone());
+                }
+            });
+            state.update(ui);
+        }
+
+        if let Some(key) = removed {
+            self.keys.remove(&key);
+        }
+    }
+}
+
+impl KeyState {
+    fn new(value: f32, received_at: Duration) -> Self {
+        Self {
+            received: value,
+            received_at,
+            value,
+            lag: 0.0,
+            timeout: None,
+            default: 0.0,
+        }
+    }
+
+    fn update(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("lag");
+            ui.add(
+                egui::DragValue::new(&mut self.lag)
+                    .speed(0.01)
+                    .range(0.0..=10.0)
+                    .suffix("s"),
+            );
+
+            let mut decay = self.timeout.is_some();
+            ui.checkbox(&mut decay, "decay after");
+            match (decay, &mut self.timeout) {
+                (true, Some(timeout)) => {
+                    ui.add(
+                        egui::DragValue::new(timeout)
+                            .speed(0.1)
+                            .range(0.1..=600.0)
+                            .suffix("s"),
+                    );
+                    ui.label("to");
+                    ui.add(egui::DragValue::new(&mut self.default).speed(0.01));
+                }
+                (true, None) => self.timeout = Some(1.0),
+                (false, _) => self.timeout = None,
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: Duration = Duration::from_millis(16);
+
+    fn value(state: &OscState, key: &str) -> f32 {
+        state.context()[key]
+    }
+
+    #[test]
+    fn holds_values_across_frames() {
+        let mut time = Time::<Real>::default();
+        let mut state = OscState::default();
+
+        state.receive("k", 2.0, time);
+        for _ in 0..100 {
+            time.advance_by(FRAME);
+            state.step(time);
+        }
+
+        assert_eq!(value(&state, "k"), 2.0);
+    }
+
+    #[test]
+    fn lag_moves_part_of_the_way_to_the_received_value() {
+        let mut time = Time::<Real>::default();
+        let mut state = OscState::default();
+        state.receive("k", 0.0, time);
+        state.keys.get_mut("k").unwrap().lag = 1.0;
+
+        state.receive("k", 1.0, time);
+        time.advance_by(Duration::from_secs(1));
+        state.step(time);
+        assert!((value(&state, "k") - (1.0 - (-1.0f32).exp())).abs() < 1e-5);
+
+        time.advance_by(Duration::from_secs(1));
+        state.step(time);
+        assert!((value(&state, "k") - (1.0 - (-2.0f32).exp())).abs() < 1e-5);
+    }
+
+    #[test]
+    fn decays_to_the_default_without_messages() {
+        let mut time = Time::<Real>::default();
+        let mut state = OscState::default();
+        state.receive("k", 5.0, time);
+        let key = state.keys.get_mut("k").unwrap();
+        key.timeout = Some(1.0);
+        key.default = 0.5;
+
+        time.advance_by(Duration::from_millis(500));
+        state.step(time);
+        assert_eq!(value(&state, "k"), 5.0);
+
+        time.advance_by(Duration::from_secs(1));
+        state.step(time);
+        assert_eq!(value(&state, "k"), 0.5);
+
+        // a new message starts the timeout over
+        state.receive("k", 3.0, time);
+        time.advance_by(Duration::from_millis(500));
+        state.step(time);
+        assert_eq!(value(&state, "k"), 3.0);
+    }
+
+    #[test]
+    fn patterns_only_reach_received_keys() {
+        let time = Time::<Real>::default();
+        let mut state = OscState::default();
+        state.receive("a", 1.0, time);
+        state.receive("b", 2.0, time);
+
+        state.receive_matching("[ac]", 3.0, time);
+        state.step(time);
+
+        assert_eq!(value(&state, "a"), 3.0);
+        assert_eq!(value(&state, "b"), 2.0);
+        assert!(!state.context().contains_key("c"));
+    }
+}
@@ -0,0 +1,168 @@
+use crate::{
+    dessin_with_variables::{DessinVariables, DessinWithVariables},
+    export::svg::read_preset,
+    osc::binding::Binding,
+};
+use anyhow::{bail, Context as _};
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+const VERSION: u32 = 1;
+const DEFAULT_PATH: &str = "preset.ron";
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Preset {
+    pub version: u32,
+    pub variables: DessinVariables,
+    #[serde(default)]
+    pub bindings: Vec<Binding>,
+}
+
+pub struct Presets {
+    path: String,
+    status: Option<String>,
+}
+
+impl Preset {
+    pub fn new(variables: DessinVariables) -> Self {
+        Self {
+            version: VERSION,
+            variables,
+            bindings: vec![],
+        }
+    }
+
+    pub fn to_ron(&self) -> anyhow::Result<String> {
+        let config = ron::ser::PrettyConfig::default();
+        Ok(ron::ser::to_string_pretty(self, config)?)
+    }
+
+    pub fn from_ron(ron: &str) -> anyhow::Result<Self> {
+        let preset: Self = ron::from_str(ron)?;
+
+        if preset.version > VERSION {
+            bail!(
+                "preset version {} is newer than the supported version {}",
+                preset.version,
+                VERSION
+            );
+        }
+
+        Ok(preset)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_ron()?)
+            .with_context(|| format!("could not write preset to {}", path.display()))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let ron = fs::read_to_string(path)
+            .with_context(|| format!("could not read preset from {}", path.display()))?;
+        Self::from_ron(&ron).with_context(|| format!("invalid preset {}", path.display()))
+    }
+
+    // reads back the parameters embedded in an exported svg
+    pub fn import_svg(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let svg = fs::read_to_string(path)
+            .with_context(|| format!("could not read svg from {}", path.display()))?;
+        read_preset(&svg).with_context(|| format!("could not import {}", path.display()))
+    }
+}
+
+impl Presets {
+    pub fn update(&mut self, ui: &mut egui::Ui, dessin: &mut DessinWithVariables) -> bool {
+        let mut changed = false;
+
+        ui.label("preset");
+        ui.text_edit_singleline(&mut self.path);
+
+        ui.horizontal(|ui| {
+            if ui.button("save preset").clicked() {
+                let mut preset = Preset::new(dessin.variables.clone());
+                preset.bindings = dessin.bindings.clone();
+                self.status = Some(match preset.save(&self.path) {
+                    Ok(()) => format!("saved {}", self.path),
+                    Err(err) => format!("{:#}", err),
+                });
+            }
+
+            if ui.button("load preset").clicked() {
+                self.status = Some(match Preset::load(&self.path) {
+                    Ok(preset) => {
+                        dessin.load(preset.variables);
+                        dessin.bindings = preset.bindings;
+                        changed = true;
+                        format!("loaded {}", self.path)
+                    }
+                    Err(err) => format!("{:#}", err),
+                });
+            }
+
+            if ui.button("import from svg").clicked() {
+                self.status = Some(match Preset::import_svg(&self.path) {
+                    Ok(preset) => {
+                        dessin.load(preset.variables);
+                        changed = true;
+                        format!("imported {}", self.path)
+                    }
+                    Err(err) => format!("{:#}", err),
+                });
+            }
+        });
+
+        if let Some(status) = &self.status {
+            ui.label(status);
+        }
+
+        changed
+    }
+}
+
+impl Default for Presets {
+    fn default() -> Self {
+        Self {
+            path: DEFAULT_PATH.to_string(),
+            status: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dessin_variant::Polygon;
+    use std::time::Duration;
+
+    // with a frame's delta before it, waveforms derive their sample rate from it
+    fn time(seconds: f32) -> Time<Virtual> {
+        let elapsed = Duration::from_secs_f32(seconds);
+        let mut time = Time::default();
+        time.advance_to(elapsed.saturating_sub(Duration::from_secs_f32(1.0 / 60.0)));
+        time.advance_to(elapsed);
+        time
+    }
+
+    #[test]
+    fn round_trips_variables_with_animations() {
+        let mut polygon = Polygon::default();
+        polygon.k.toggle_animation();
+        let variables = DessinVariables::Polygon(polygon);
+
+        let ron = Preset::new(variables.clone()).to_ron().unwrap();
+        let mut loaded = Preset::from_ron(&ron).unwrap().variables;
+
+        assert_eq!(loaded.values(), variables.values());
+        assert_eq!(loaded.animations().len(), 1);
+
+        // the timeline of a new session starts over, the animation has to move from there on
+        loaded.animate(time(0.5));
+        let start = loaded.values();
+        loaded.animate(time(2.5));
+        assert_ne!(loaded.values(), start);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::{
+    adjustable_dessin::{animate_from_reflect, AdjustableDessin},
+    adjustable_variable::{
+        types::{Context, Rgba},
+        AdjustableVariable, UpdateVariableParams,
+    },
+    stroke::Stroke,
+};
+use anyhow::{bail, Context as _};
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+const VERSION: u32 = 1;
+const DEFAULT_PATH: &str = "settings.ron";
+
+// how every dessin is drawn, on screen and in the exports
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Settings {
+    pub version: u32,
+    pub background: Rgba,
+    pub stroke: Stroke,
+}
+
+pub struct SettingsFile {
+    path: String,
+    status: Option<String>,
+}
+
+impl Settings {
+    pub fn to_ron(&self) -> anyhow::Result<String> {
+        let config = ron::ser::PrettyConfig::default();
+        Ok(ron::ser::to_string_pretty(self, config)?)
+    }
+
+    pub fn from_ron(ron: &str) -> anyhow::Result<Self> {
+        let settings: Self = ron::from_str(ron)?;
+
+        if settings.version > VERSION {
+            bail!(
+                "settings version {} is newer than the supported version {}",
+                settings.version,
+                VERSION
+            );
+        }
+
+        Ok(settings)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_ron()?)
+            .with_context(|| format!("could not write settings to {}", path.display()))
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let ron = fs::read_to_string(path)
+            .with_context(|| format!("could not read settings from {}", path.display()))?;
+        Self::from_ron(&ron).with_context(|| format!("invalid settings {}", path.display()))
+    }
+
+    // the settings' ui is folded most of the time
+    pub fn animate(&mut self, time: Time<Virtual>) {
+        self.background.animate(time);
+        animate_from_reflect(&mut self.stroke, time);
+    }
+
+    pub fn update(&mut self, ui: &mut egui::Ui, osc_ctx: &Context, time: Time<Virtual>) {
+        self.background.update(UpdateVariableParams {
+            ui,
+            osc_ctx,
+            name: "background".to_string(),
+            time,
+        });
+        ui.separator();
+        self.stroke.update_variables(ui, osc_ctx, time);
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            version: VERSION,
+            background: Rgba::new(Color::srgba(0.1, 0.1, 0.1, 0.85)),
+            stroke: Stroke::default(),
+        }
+    }
+}
+
+impl SettingsFile {
+    pub fn update(&mut self, ui: &mut egui::Ui, settings: &mut Settings) {
+        ui.label("settings file");
+        ui.text_edit_singleline(&mut self.path);
+
+        ui.horizontal(|ui| {
+            if ui.button("save settings").clicked() {
+                self.status = Some(match settings.save(&self.path) {
+                    Ok(()) => format!("saved {}", self.path),
+                    Err(err) => format!("{:#}", err),
+                });
+            }
+
+            if ui.button("load settings").clicked() {
+                self.status = Some(match Settings::load(&self.path) {
+                    Ok(loaded) => {
+                        *settings = loaded;
+                        format!("loaded {}", self.path)
+                    }
+                    Err(err) => format!("{:#}", err),
+                });
+            }
+        });
+
+        if let Some(status) = &self.status {
+            ui.label(status);
+        }
+    }
+}
+
+impl Default for SettingsFile {
+    fn default() -> Self {
+        Self {
+            path: DEFAULT_PATH.to_string(),
+            status: None,
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use crate::{
+    adjustable_dessin::AdjustableDessin,
+    adjustable_variable::{
+        types::{Context, F32Variant, F32},
+        AdjustableVariable, Changes, UpdateVariableParams,
+    },
+    color_map::runs,
+    shapes::WEIGHT,
+};
+use evalexpr::{ContextWithMutableVariables, DefaultNumericTypes, HashMapContext, Node, Value};
+use nannou::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Default)]
+pub struct Stroke {
+    #[reflect(ignore)]
+    pub cap: LineCap,
+    #[reflect(ignore)]
+    pub join: LineJoin,
+    #[reflect(ignore)]
+    pub taper: Taper,
+    pub weight: F32, // on screen, exports scale their stroke width by weight / WEIGHT
+    pub amount: F32, // of the taper, 1 thinning the line down to nothing
+    #[reflect(ignore)]
+    pub factor: String, // of the weight at point i of n, t going from 0 to 1
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineCap {
+    Butt,
+    Round,
+    Square,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineJoin {
+    Miter,
+    Round,
+    Bevel,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Taper {
+    None,
+    Start, // thin at the first point
+    End,   // thin at the last point
+    Both,
+    Expression,
+}
+
+// the weight along the segments, prepared once for all of them
+pub struct Weights {
+    taper: Taper,
+    amount: f32,
+    expression: Option<Node<DefaultNumericTypes>>,
+}
+
+impl LineCap {
+    // also the names of svg's stroke-linecap
+    pub const ALL: &'static [(Self, &'static str)] = &[
+        (Self::Butt, "butt"),
+        (Self::Round, "round"),
+        (Self::Square, "square"),
+    ];
+
+    pub fn name(&self) -> &'static str {
+        name(Self::ALL, self)
+    }
+}
+
+impl LineJoin {
+    // also the names of svg's stroke-linejoin
+    pub const ALL: &'static [(Self, &'static str)] = &[
+        (Self::Miter, "miter"),
+        (Self::Round, "round"),
+        (Self::Bevel, "bevel"),
+    ];
+
+    pub fn name(&self) -> &'static str {
+        name(Self::ALL, self)
+    }
+}
+
+impl Taper {
+    pub const ALL: &'static [(Self, &'static str)] = &[
+        (Self::None, "none"),
+        (Self::Start, "start"),
+        (Self::End, "end"),
+        (Self::Both, "both"),
+        (Self::Expression, "expression"),
+    ];
+}
+
+fn name<T: PartialEq>(all: &[(T, &'static str)], value: &T) -> &'static str {
+    all.iter()
+        .find(|(variant, _)| variant == value)
+        .map(|(_, name)| *name)
+        .expect("every variant has a name")
+}
+
+impl Stroke {
+    // of a layer with the default weight, relative to WEIGHT
+    pub fn scale(&self) -> f32 {
+        self.weight.get_value() / WEIGHT
+    }
+
+    pub fn weights(&self) -> Weights {
+        let expression = match self.taper {
+            Taper::Expression => {
+                evalexpr::build_operator_tree::<DefaultNumericTypes>(&self.factor).ok()
+            }
+            _ => None,
+        };
+
+        Weights {
+            taper: self.taper,
+            amount: self.amount.get_value(),
+            expression,
+        }
+    }
+}
+
+impl Weights {
+    pub fn is_uniform(&self) -> bool {
+        match self.taper {
+            Taper::None => true,
+            Taper::Start | Taper::End | Taper::Both => self.amount == 0.0,
+            Taper::Expression => self.expression.is_none(),
+        }
+    }
+
+    // a factor of the weight for each of the `n` points of a segment
+    pub fn along(&self, n: usize) -> Vec<f32> {
+        let mut ctx = HashMapContext::<DefaultNumericTypes>::new();
+        set(&mut ctx, "n", n as f32);
+        set(&mut ctx, "pi", PI);
+
+        (0..n)
+            .map(|i| {
+                let t = i as f32 / n.saturating_sub(1).max(1) as f32;
+                let factor = match self.taper {
+                    Taper::None => 1.0,
+                    Taper::Start => 1.0 - self.amount * (1.0 - t),
+                    Taper::End => 1.0 - self.amount * t,
+                    Taper::Both => 1.0 - self.amount * (2.0 * t - 1.0).abs(),
+                    Taper::Expression => {
+                        let Some(expression) = &self.expression else {
+                            return 1.0;
+                        };
+                        set(&mut ctx, "i", i as f32);
+                        set(&mut ctx, "t", t);
+                        expression
+                            .eval_number_with_context(&ctx)
+                            .map_or(1.0, |factor| factor as f32)
+                    }
+                };
+                factor.max(0.0)
+            })
+            .collect()
+    }
+
+    // the pieces of a segment drawn with a single colour and weight factor, a varying weight
+    // gives one piece per line
+    pub fn pieces<'a>(
+        &self,
+        segment: &'a [Vec2],
+        colors: &[Color],
+    ) -> Vec<(Color, f32, &'a [Vec2])> {
+        if self.is_uniform() {
+            return runs(segment, colors)
+                .into_iter()
+                .map(|(color, points)| (color, 1.0, points))
+                .collect();
+        }
+
+        let factors = self.along(segment.len());
+        (0..segment.len().saturating_sub(1))
+            .map(|i| {
+                let factor = (factors[i] + factors[i + 1]) / 2.0;
+                (colors[i], factor, &segment[i..=i + 1])
+            })
+            .collect()
+    }
+}
+
+impl AdjustableDessin for Stroke {
+    fn update_variables(
+        &mut self,
+        ui: &mut egui::Ui,
+        osc_ctx: &Context,
+        time: Time<Virtual>,
+    ) -> Changes {
+        let mut changes = Changes::default();
+
+        ui.horizontal(|ui| {
+            ui.label("caps");
+            for (cap, name) in LineCap::ALL {
+                changes |= Changes::edited(ui.radio_value(&mut self.cap, *cap, *name).changed());
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("joins");
+            for (join, name) in LineJoin::ALL {
+                changes |= Changes::edited(ui.radio_value(&mut self.join, *join, *name).changed());
+            }
+        });
+        ui.horizontal_wrapped(|ui| {
+            ui.label("taper");
+            for (taper, name) in Taper::ALL {
+                changes |=
+                    Changes::edited(ui.radio_value(&mut self.taper, *taper, *name).changed());
+            }
+        });
+
+        ui.separator();
+
+        // only the variables the chosen taper uses
+        let mut update = |name: &str, variable: &mut dyn AdjustableVariable| {
+            let changes = variable.update(UpdateVariableParams {
+                ui: &mut *ui,
+                osc_ctx,
+                name: name.to_string(),
+                time,
+            });
+            ui.separator();
+            changes
+        };
+
+        changes |= update("weight", &mut self.weight);
+        match self.taper {
+            Taper::None => {}
+            Taper::Start | Taper::End | Taper::Both => {
+                changes |= update("amount", &mut self.amount);
+            }
+            Taper::Expression => {
+                ui.label("factor");
+                let response = ui.text_edit_singleline(&mut self.factor);
+                let entered =
+                    response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                changes |= Changes::edited(entered);
+                response.on_hover_text("of i, n and t (from 0 to 1 along the segment)");
+                if evalexpr::build_operator_tree::<DefaultNumericTypes>(&self.factor).is_err() {
+                    ui.label("invalid expression");
+                }
+                ui.separator();
+            }
+        }
+
+        changes
+    }
+}
+
+impl Default for Stroke {
+    fn default() -> Self {
+        Self {
+            cap: LineCap::Round,
+            join: LineJoin::Round,
+            taper: Taper::None,
+            weight: F32::new(WEIGHT, F32Variant::None(0.1..=20.0)),
+            amount: F32::new(0.75, F32Variant::None(0.0..=1.0)),
+            factor: "1 - 0.75 * t".to_string(),
+        }
+    }
+}
+
+fn set(ctx: &mut HashMapContext<DefaultNumericTypes>, key: &str, value: f32) {
+    ctx.set_value(key.to_string(), Value::Float(value as f64))
+        .expect("setting to value of same type each time");
+}
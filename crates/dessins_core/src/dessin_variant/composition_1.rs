@@ -0,0 +1,54 @@
+use super::{Polygon, Star};
+use crate::shapes::{Segment, Shape, Shapes, NP};
+use std::f32::consts::PI;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Composition1 {
+    pub polygon: Polygon, // where the stars are centered
+    pub star: Star,
+}
+
+impl Composition1 {
+    pub fn calculate_shapes(&self) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+
+        for i in 0..self.polygon.k {
+            let polygon_point = self.polygon.calculate_point(i);
+
+            let mut segment = Segment::new();
+
+            for j in 0..self.star.k {
+                let star_point = self.star.calculate_point(j);
+                let point = star_point + polygon_point;
+                segment.push(point);
+            }
+
+            segment.push(segment[0]);
+
+            shape.push(segment);
+        }
+
+        shapes.push(shape);
+        shapes
+    }
+}
+
+impl Default for Composition1 {
+    fn default() -> Self {
+        Self {
+            polygon: Polygon {
+                k: 5,
+                r: 0.27 * NP as f32,
+                ad: 0.5 * PI,
+            },
+            star: Star {
+                k: 25,
+                h: 12,
+                r: 0.22 * NP as f32,
+                ad: 0.5 * PI,
+            },
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::{Composition1, Polygon, Star};
+use crate::shapes::{Segment, Shape, Shapes};
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Composition2 {
+    pub polygon: Polygon, // where the stars are centered
+    pub star: Star,
+    pub n: u32,  // # stars
+    pub rr: f32, // reduction coefficient from one star to the next & the distance between the center of the spiral and the center of successive stars
+}
+
+impl Composition2 {
+    pub fn calculate_shapes(&self) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+
+        let mut polygon = self.polygon.clone();
+        let mut star = self.star.clone();
+
+        for i in 0..self.n {
+            let r2 = polygon.r * self.rr.powi(i as i32);
+            let r3 = star.r * self.rr.powi(i as i32);
+
+            polygon.r = r2;
+            let polygon_point = polygon.calculate_point(i);
+
+            let mut segment = Segment::new();
+
+            for j in 0..star.k {
+                star.r = r3;
+                let star_point = star.calculate_point(j);
+                let point = star_point + polygon_point;
+                segment.push(point);
+            }
+
+            segment.push(segment[0]);
+            shape.push(segment);
+        }
+
+        shapes.push(shape);
+
+        shapes
+    }
+}
+
+impl Default for Composition2 {
+    fn default() -> Self {
+        let Composition1 { polygon, star } = Composition1::default();
+        Self {
+            polygon,
+            star,
+            n: 32,
+            rr: 0.9,
+        }
+    }
+}
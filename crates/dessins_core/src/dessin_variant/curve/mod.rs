@@ -0,0 +1,7 @@
+pub use orbital::Orbital;
+pub use rotating::Rotating;
+pub use spiral::Spiral;
+
+pub mod orbital;
+pub mod rotating;
+pub mod spiral;
@@ -0,0 +1,64 @@
+use crate::shapes::{pt2, Segment, Shape, Shapes, NP};
+use std::f32::consts::PI;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Orbital {
+    pub n: u32,  // # segments
+    pub t1: u32, // # times the planet turns around the sun
+    pub t2: u32, // # times the satellite turns around the planet
+    pub r1: f32, // radius of the planet's curve
+    pub k1: u32, // elliptic parameter of the planet's curve
+    pub k2: u32, // elliptic parameter of the planet's curve
+}
+
+impl Orbital {
+    // the default radius of the satellite's curve, as an expression of i and n
+    pub const R2: &'static str = "96.0 * (1 - i / n)";
+
+    // `r2` gives the radius of the satellite's curve at each point i of 0..=n
+    pub fn calculate_shapes(&self, mut r2: impl FnMut(f32) -> f32) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+        let mut segment = Segment::new();
+
+        let n = self.n as f32;
+        let t1 = self.t1 as f32;
+        let t2 = self.t2 as f32;
+        let r1 = self.r1;
+        let k1 = self.k1 as f32;
+        let k2 = self.k2 as f32;
+
+        for i in 0..=n as usize {
+            let i = i as f32;
+
+            let r2 = r2(i);
+
+            let a1 = 2.0 * PI * i / n * t1;
+            let a2 = 2.0 * PI * i / n * t2;
+
+            let x = r1 * (k1 * a1).cos() + r2 * a2.cos();
+            let y = r1 * (k2 * a1).sin() + r2 * a2.sin();
+
+            segment.push(pt2(x, y));
+        }
+
+        shape.push(segment);
+        shapes.push(shape);
+
+        shapes
+    }
+}
+
+impl Default for Orbital {
+    fn default() -> Self {
+        Self {
+            n: 2000,
+            t1: 2,
+            t2: 100,
+            r1: 0.25 * NP as f32,
+            k1: 1,
+            k2: 1,
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::shapes::{pt2, Segment, Shape, Shapes, NP};
+use std::f32::consts::PI;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rotating {
+    pub n: u32,  // # segments
+    pub t1: f32, // # times the planet turns around the sun
+    pub t2: f32, // # times the satellite turns around the planet
+    pub r1: f32, // radius of the planet's curve
+    pub k1: u32, // elliptic parameter of the planet's curve
+    pub k2: u32, // elliptic parameter of the planet's curve
+    pub r2: f32, // radius of the satellite's curve
+    pub h1: u32, // elliptic parameter of the satellite's curve
+    pub h2: u32, // elliptic parameter of the satellite's curve
+}
+
+impl Rotating {
+    // the default scale of the satellite's curve, as an expression of i, n and pi
+    pub const S: &'static str = "math::cos(4.0 * pi * i / n) * 0.4 + 0.6";
+
+    // `s` scales the satellite's curve at each point i of 0..=n
+    pub fn calculate_shapes(&self, mut s: impl FnMut(f32) -> f32) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+        let mut segment = Segment::new();
+
+        let n = self.n as f32;
+        let t1 = self.t1;
+        let t2 = self.t2;
+        let r1 = self.r1;
+        let k1 = self.k1 as f32;
+        let k2 = self.k2 as f32;
+        let r2 = self.r2;
+        let h1 = self.h1 as f32;
+        let h2 = self.h2 as f32;
+
+        for i in 0..=n as usize {
+            let i = i as f32;
+
+            let s = s(i);
+
+            let an = 2.0 * PI * i / n;
+            let c1 = (h1 * an * t1).cos();
+            let s1 = (h2 * an * t1).sin();
+            let c2 = s * (k1 * an * t2).cos();
+            let s2 = s * (k2 * an * t2).sin();
+
+            let x = r1 * c1 + r2 * (c1 * c2 - s1 * s2);
+            let y = r1 * s1 + r2 * (s1 * c2 + c1 * s2);
+
+            segment.push(pt2(x, y));
+        }
+
+        shape.push(segment);
+        shapes.push(shape);
+
+        shapes
+    }
+}
+
+impl Default for Rotating {
+    fn default() -> Self {
+        Self {
+            n: 2000,
+            t1: 1.0,
+            t2: 100.0,
+            r1: 1.0 / 6.0 * NP as f32,
+            k1: 1,
+            k2: 1,
+            r2: 1.0 / 4.0 * NP as f32,
+            h1: 1,
+            h2: 1,
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::shapes::{pt2, Segment, Shape, Shapes, NP};
+use std::f32::consts::PI;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Spiral {
+    pub n: u32, // # segments
+    pub t: u32, // # times the planet turns around the sun
+    pub r: f32, // flattening parameter of the ellipse
+    pub l: f32, // decrease factor beween the first ellipse traveled and the last
+    pub an_factor: f32,
+}
+
+impl Spiral {
+    pub fn calculate_shapes(&self) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+        let mut segment = Segment::new();
+
+        let np = NP as f32;
+        let n = self.n as f32;
+        let t = self.t as f32;
+        let r = self.r;
+        let l = self.l;
+        let an_factor = self.an_factor;
+
+        for i in 0..=n as usize {
+            let i = i as f32;
+
+            let rr = l.powf(i / n);
+            let an = 2.0 * PI * i / n * an_factor;
+
+            let x = rr * (t * an).cos();
+            let y = rr * r * (t * an).sin();
+
+            let co = an.cos();
+            let si = an.sin();
+
+            let xx = x * co - y * si;
+            let yy = x * si + y * co;
+
+            let x = xx * np / 2.0;
+            let y = yy * np / 2.0;
+
+            segment.push(pt2(x, y));
+        }
+
+        shape.push(segment);
+        shapes.push(shape);
+
+        shapes
+    }
+}
+
+impl Default for Spiral {
+    fn default() -> Self {
+        Self {
+            n: 2000,
+            t: 40,
+            r: 0.8,
+            l: 0.1,
+            an_factor: 1.0,
+        }
+    }
+}
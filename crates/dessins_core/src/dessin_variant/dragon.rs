@@ -0,0 +1,96 @@
+use crate::shapes::{pt2, Point2, Segment, Shape, Shapes, NP};
+use std::f32::consts::PI;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Dragon {
+    pub n: u32,          // depth of recursion
+    pub l0: f32,         // initial length
+    pub a0: f32,         // initial angle
+    pub p0: Point2,      // initial position
+    pub rules: Vec<u32>, // turning rules, 0 or 1 for each of the n + 1 levels
+}
+
+impl Dragon {
+    // the default initial length and angle, as expressions of the depth n
+    pub const L0: &'static str = "480 / (math::sqrt(2.0) ^ n)";
+    pub const A0: &'static str = "-pi / 4 * (n - 2)";
+
+    pub fn calculate_shapes(&self) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+        let mut segment = Segment::new();
+
+        let n = self.n as usize;
+        let l0 = self.l0;
+
+        segment.push(self.p0);
+
+        let mut p0 = self.p0;
+        let mut p1 = p0;
+        let mut p2 = p0;
+
+        let mut current_angle = self.a0;
+
+        let nn = 2_i32.pow(n as u32) - 1;
+
+        fn step_segment(p0: &mut Point2, p1: &mut Point2, p2: &mut Point2, step: Point2) {
+            *p0 = *p1;
+            *p1 = *p2;
+            *p2 += step;
+        }
+
+        for i in 0..=nn {
+            if i == 0 {
+                step_segment(
+                    &mut p0,
+                    &mut p1,
+                    &mut p2,
+                    pt2(l0 * current_angle.cos(), l0 * current_angle.sin()),
+                );
+            } else {
+                let mut ii = i;
+                let mut j = 0;
+
+                while ii % 2 == 0 {
+                    ii /= 2;
+                    j += 1;
+                }
+
+                let aa = (self.rules[n - j] as i32 * 2 - 1) as f32
+                    * ((((ii - 1) / 2) % 2) * 2 - 1) as f32
+                    * PI
+                    / 2.0;
+                current_angle += aa;
+
+                step_segment(
+                    &mut p0,
+                    &mut p1,
+                    &mut p2,
+                    pt2(l0 * current_angle.cos(), l0 * current_angle.sin()),
+                );
+            }
+
+            segment.push((p0 + pt2(3.0, 3.0) * p1) / pt2(4.0, 4.0));
+            segment.push((p2 + pt2(3.0, 3.0) * p1) / pt2(4.0, 4.0));
+        }
+
+        shape.push(segment);
+        shapes.push(shape);
+
+        shapes
+    }
+}
+
+impl Default for Dragon {
+    fn default() -> Self {
+        let n = 6;
+        Self {
+            n,
+            l0: NP as f32 / 2.0_f32.sqrt().powf(n as f32),
+            a0: (n - 2) as f32 * -PI / 4.0,
+            p0: pt2(-(NP as f32) / 6.0, -(NP as f32) / 2.5),
+            rules: vec![0; n as usize + 1],
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::shapes::{pt2, Point2, Segment, Shape, Shapes, NP};
+use std::f32::consts::PI;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FractalStar {
+    pub n: u32,
+    pub k: u32,
+    pub ra: f32,
+    pub ll: f32,
+    pub aa: f32,
+    pub p0: Point2,
+    pub a0: f32,
+}
+
+impl FractalStar {
+    pub fn calculate_shapes(&self) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+        let mut segment = Segment::new();
+
+        let mut p0 = self.p0;
+        let mut a0 = self.a0;
+
+        let n = self.n;
+        let k = self.k;
+
+        let nn = n * (n - 1).pow(k - 1) - 1;
+
+        for i in 0..=nn {
+            let mut i1 = i;
+            let mut h = 0;
+
+            while i1 % (n - 1) == 0 && h < (k - 1) {
+                i1 /= n - 1;
+                h += 1;
+            }
+
+            let l0 = self.ll * self.ra.powf((k - 1 - h) as f32);
+            a0 += self.aa;
+
+            let point = p0 + pt2(l0 * a0.cos(), l0 * a0.sin());
+
+            segment.push(point);
+            p0 = point;
+        }
+
+        shape.push(segment);
+        shapes.push(shape);
+
+        shapes
+    }
+}
+
+impl Default for FractalStar {
+    fn default() -> Self {
+        let ll = NP as f32;
+        let aa = 4.0 / 5.0 * PI;
+        Self {
+            n: 5,
+            k: 5,
+            ra: 0.35,
+            ll,
+            aa,
+            p0: pt2(-ll / 2.0, 0.0),
+            a0: -aa,
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use crate::shapes::{pt2, Segment, Shape, Shapes, NP};
+use std::f32::consts::PI;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Jolygon {
+    pub k: u32,  // # segments
+    pub an: f32, // angle of two consecutive segments
+    pub ra: f32, // ratio of the lengths of two consecutive segments
+    pub aa: f32, // angle of the first segment with horizontal
+    pub rr: f32, // length of the first segment
+}
+
+impl Jolygon {
+    pub fn calculate_shapes(&self) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+        let mut segment = Segment::new();
+
+        let mut current_length = self.rr;
+        let mut current_pos = pt2(0.0, 0.0);
+        segment.push(current_pos);
+
+        let mut min_x: f32 = 0.0;
+        let mut max_x: f32 = 0.0;
+        let mut min_y: f32 = 0.0;
+        let mut max_y: f32 = 0.0;
+
+        for i in 0..self.k {
+            let angle = self.aa + i as f32 * self.an;
+
+            let dx = current_length * angle.cos();
+            let dy = current_length * angle.sin();
+            let d = pt2(dx, dy);
+            let point = current_pos + d;
+
+            // update bounds
+            min_x = min_x.min(point.x);
+            max_x = max_x.max(point.x);
+            min_y = min_y.min(point.y);
+            max_y = max_y.max(point.y);
+
+            segment.push(point);
+            current_pos = point;
+            current_length *= self.ra;
+        }
+
+        // calculate center offset
+        let center_offset_x = (min_x + max_x) / 2.0;
+        let center_offset_y = (min_y + max_y) / 2.0;
+
+        // make segments centered
+        for point in &mut segment {
+            *point -= pt2(center_offset_x, center_offset_y);
+        }
+
+        shape.push(segment);
+        shapes.push(shape);
+
+        shapes
+    }
+}
+
+impl Default for Jolygon {
+    fn default() -> Self {
+        Self {
+            k: 200,
+            an: 15.0 / 31.0 * PI,
+            ra: 0.98,
+            aa: 0.0,
+            rr: 0.8 * NP as f32,
+        }
+    }
+}
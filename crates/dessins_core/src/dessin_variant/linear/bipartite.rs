@@ -0,0 +1,75 @@
+use crate::shapes::{pt2, Point2, Segment, Shape, Shapes, NP};
+
+pub type OuterSegment = Segment;
+pub type InnerSegment = Segment;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bipartite {
+    pub n: u32,
+    pub a: Point2,
+    pub b: Point2,
+    pub c: Point2,
+    pub d: Point2,
+}
+
+impl Bipartite {
+    pub fn calculate_shapes(&self) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+
+        let (outer_points, inner_points) = self.calculate_points();
+
+        for outer in &outer_points {
+            for inner in &inner_points {
+                let segment = vec![*outer, *inner];
+                shape.push(segment);
+            }
+        }
+
+        shapes.push(shape);
+
+        shapes
+    }
+
+    pub fn calculate_points(&self) -> (OuterSegment, InnerSegment) {
+        let mut outer_segment = vec![];
+        let mut inner_segment = vec![];
+
+        let n = self.n as f32;
+        let a = self.a;
+        let b = self.b;
+        let c = self.c;
+        let d = self.d;
+
+        for i in 0..=self.n {
+            let i = i as f32;
+            let x1 = (i * a.x + (n - i) * b.x) / n;
+            let y1 = (i * a.y + (n - i) * b.y) / n;
+            outer_segment.push(pt2(x1, y1));
+
+            for j in 0..=self.n {
+                let j = j as f32;
+
+                let x2 = (j * c.x + (n - j) * d.x) / n;
+                let y2 = (j * c.y + (n - j) * d.y) / n;
+                inner_segment.push(pt2(x2, y2));
+            }
+        }
+
+        (outer_segment, inner_segment)
+    }
+}
+
+impl Default for Bipartite {
+    fn default() -> Self {
+        let half = NP as f32 / 2.0;
+        Self {
+            n: 10,
+            a: pt2(-half, -half),
+            b: pt2(-half, half),
+            c: pt2(half, -half),
+            d: pt2(half, half),
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub use bipartite::Bipartite;
+pub use modulo::Modulo;
+pub use stick::Stick;
+
+pub mod bipartite;
+pub mod modulo;
+pub mod stick;
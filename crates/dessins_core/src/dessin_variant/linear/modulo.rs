@@ -0,0 +1,68 @@
+use crate::shapes::{pt2, Segment, Shape, Shapes, NP};
+use std::f32::consts::PI;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Modulo {
+    pub n: u32,
+    pub m: u32,
+    pub k1: f32,
+    pub h: u32,
+    pub i1_factor: u32,
+}
+
+impl Modulo {
+    // the default height of the points, as an expression of i, n, k2 and pi
+    pub const Y: &'static str = "360 * math::cos(k2 * i * pi / n)";
+    pub const K2: f32 = 5.0; // the default k2 the height is written with
+
+    // `y` gives the height of each point i of 0..=n
+    pub fn calculate_shapes(&self, y: impl FnMut(f32) -> f32) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+
+        let points = self.calculate_points(y);
+
+        for i in 0..=self.m {
+            let start_index = ((self.i1_factor * i) % self.n) as usize;
+            let end_index = ((self.h * i) % self.n) as usize;
+
+            let segment = vec![points[start_index], points[end_index]];
+            shape.push(segment);
+        }
+
+        shapes.push(shape);
+
+        shapes
+    }
+
+    pub fn calculate_points(&self, mut y: impl FnMut(f32) -> f32) -> Segment {
+        let mut points = vec![];
+
+        let n = self.n as f32;
+        let k1 = self.k1;
+
+        for i in 0..=n as usize {
+            let i = i as f32;
+
+            let x = NP as f32 * 0.5 * (k1 * i * PI / n).sin();
+            let y = y(i);
+
+            points.push(pt2(x, y));
+        }
+
+        points
+    }
+}
+
+impl Default for Modulo {
+    fn default() -> Self {
+        Self {
+            n: 400,
+            m: 400,
+            k1: 4.0,
+            h: 2,
+            i1_factor: 1,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::shapes::{pt2, Shape, Shapes};
+use std::f32::consts::PI;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stick {
+    pub n: u32,
+    pub m: u32,
+    pub k: u32,
+}
+
+impl Stick {
+    // the default radii, as expressions of i, n, k and pi
+    pub const R1: &'static str = "120.0";
+    pub const R2: &'static str = "100.0";
+
+    // `r1` and `r2` give the two radii of each round i of 0..=m
+    pub fn calculate_shapes(
+        &self,
+        mut r1: impl FnMut(f32) -> f32,
+        mut r2: impl FnMut(f32) -> f32,
+    ) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+
+        let n = self.n;
+        let k = self.k;
+
+        for i in 0..=self.m {
+            let r1 = r1(i as f32);
+            let r2 = r2(i as f32);
+
+            for j in 0..n {
+                let j = j as f32;
+                let n = n as f32;
+                let k = k as f32;
+
+                let an = 2.0 * j * PI / n;
+
+                let x = r1 * an.cos() + r2 * (k * an).cos();
+                let y = r1 * an.sin() + r2 * (k * an).sin();
+                let d = pt2(x, y);
+
+                let x = r1 * an.cos() + r2 * (k * an + PI).cos();
+                let y = r1 * an.sin() + r2 * (k * an + PI).sin();
+                let a = pt2(x, y);
+
+                let segment = vec![d, a];
+                shape.push(segment);
+            }
+        }
+
+        shapes.push(shape);
+
+        shapes
+    }
+}
+
+impl Default for Stick {
+    fn default() -> Self {
+        Self { n: 100, m: 1, k: 5 }
+    }
+}
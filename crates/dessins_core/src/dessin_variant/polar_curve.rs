@@ -0,0 +1,52 @@
+use crate::shapes::{pt2, Segment, Shape, Shapes, NP};
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolarCurve {
+    pub n: u32, // # segments
+}
+
+impl PolarCurve {
+    // the default angle and radius, as expressions of i, n and pi
+    pub const A: &'static str = "3 * pi / 4 * math::sin(30*pi*i/n) * math::sin(pi*i/n)";
+    pub const R: &'static str = "i/n";
+
+    // `a` and `r` give the angle and the radius (1 reaching the edge) at each point i of 0..=n
+    pub fn calculate_shapes(
+        &self,
+        mut a: impl FnMut(f32) -> f32,
+        mut r: impl FnMut(f32) -> f32,
+    ) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+        let mut segment = Segment::new();
+
+        let n = self.n as f32;
+
+        for i in 0..=n as usize {
+            let i = i as f32;
+
+            let a = a(i);
+            let r = r(i);
+
+            let x = r * a.cos();
+            let y = r * a.sin();
+
+            let x = x * NP as f32 / 2.0;
+            let y = y * NP as f32 / 2.0;
+
+            segment.push(pt2(x, y));
+        }
+
+        shape.push(segment);
+        shapes.push(shape);
+
+        shapes
+    }
+}
+
+impl Default for PolarCurve {
+    fn default() -> Self {
+        Self { n: 2000 }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::shapes::{pt2, Point2, Segment, Shape, Shapes, NP};
+use std::f32::consts::PI;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Polygon {
+    pub k: u32,  // # vertices
+    pub r: f32,  // radius of the circle on which the vertices are
+    pub ad: f32, // angle (in radians) of the vector CS with horizontal, where S is the first vertex,
+}
+
+impl Polygon {
+    pub fn calculate_shapes(&self) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+        let mut segment = Segment::new();
+
+        for i in 0..self.k {
+            let point = self.calculate_point(i);
+            segment.push(point);
+        }
+
+        segment.push(segment[0]);
+
+        shape.push(segment);
+        shapes.push(shape);
+        shapes
+    }
+
+    pub fn calculate_point(&self, i: u32) -> Point2 {
+        let i = i as f32;
+        let k = self.k as f32;
+
+        let angle = (2.0 * i * PI) / k + self.ad;
+        let x = self.r * angle.cos();
+        let y = self.r * angle.sin();
+        pt2(x, y)
+    }
+}
+
+impl Default for Polygon {
+    fn default() -> Self {
+        Self {
+            k: 3,
+            r: 0.45 * NP as f32,
+            ad: 0.0,
+        }
+    }
+}
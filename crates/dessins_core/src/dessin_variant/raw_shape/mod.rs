@@ -0,0 +1,31 @@
+pub use raw_shape_program::RawShapeProgram;
+pub use raw_shape_variant::{DecodeAction, RawShapeDecoder, RawShapeVariant};
+
+use crate::shapes::Shapes;
+
+mod raw_shape_program;
+mod raw_shape_variant;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RawShape {
+    pub shape_variant: RawShapeVariant,
+    pub program_variant: RawShapeProgram,
+}
+
+impl RawShape {
+    pub fn calculate_shapes(&self) -> Shapes {
+        let mut raw_shape_decoder = RawShapeDecoder::new(&self.shape_variant);
+        self.program_variant
+            .calculate_shapes(&mut raw_shape_decoder)
+    }
+}
+
+impl Default for RawShape {
+    fn default() -> Self {
+        Self {
+            shape_variant: RawShapeVariant::Horse,
+            program_variant: RawShapeProgram::Program1,
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use super::{DecodeAction, RawShapeDecoder};
-use crate::shapes::{sign, Segment, Shape, Shapes, NP};
-use nannou::prelude::*;
+use crate::shapes::{pt2, sign, Segment, Shape, Shapes, NP};
+use std::f32::consts::PI;
 
-#[derive(Clone, Debug, PartialEq, Reflect)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RawShapeProgram {
     Program1,
     Program2,
@@ -20,7 +21,7 @@ pub enum RawShapeProgram {
 }
 
 impl RawShapeProgram {
-    pub fn calculate_shapes(&mut self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
+    pub fn calculate_shapes(&self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
         match self {
             Self::Program1 => self.program_1(raw_shape_decoder),
             Self::Program2 => self.program_2(raw_shape_decoder),
@@ -38,36 +39,7 @@ impl RawShapeProgram {
         }
     }
 
-    pub fn update(&mut self, ui: &mut egui::Ui) -> bool {
-        let mut changed = false;
-
-        ui.label("program");
-        changed |= ui.radio_value(self, Self::Program1, "o").changed();
-        changed |= ui.radio_value(self, Self::Program2, "oO").changed();
-        changed |= ui.radio_value(self, Self::Program3, "oOo").changed();
-        changed |= ui.radio_value(self, Self::Program4, "oOoO").changed();
-        changed |= ui.radio_value(self, Self::Program5, "oOoOo").changed();
-        changed |= ui.radio_value(self, Self::Program6, "oOoOoO").changed();
-        changed |= ui.radio_value(self, Self::Program7, "oOoOoOo").changed();
-        changed |= ui.radio_value(self, Self::Program8, "oOoOoOoO").changed();
-        changed |= ui.radio_value(self, Self::Program9, "oOoOoOoOo").changed();
-        changed |= ui
-            .radio_value(self, Self::Program10, "oOoOoOoOoO")
-            .changed();
-        changed |= ui
-            .radio_value(self, Self::Program11, "oOoOoOoOoOo")
-            .changed();
-        changed |= ui
-            .radio_value(self, Self::Program12, "oOoOoOoOoOoO")
-            .changed();
-        changed |= ui
-            .radio_value(self, Self::Program13, "oOoOoOoOoOoOo")
-            .changed();
-
-        changed
-    }
-
-    fn program_1(&mut self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
+    fn program_1(&self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
         let mut shapes = Shapes::new();
         let mut shape = Shape::new();
         let mut segment = Segment::new();
@@ -90,7 +62,7 @@ impl RawShapeProgram {
         shapes
     }
 
-    fn program_2(&mut self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
+    fn program_2(&self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
         let mut shapes = Shapes::new();
 
         for i in 0..=5 {
@@ -125,7 +97,7 @@ impl RawShapeProgram {
         shapes
     }
 
-    fn program_3(&mut self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
+    fn program_3(&self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
         let mut shapes = Shapes::new();
 
         for i in 0..=5 {
@@ -135,7 +107,7 @@ impl RawShapeProgram {
 
                 raw_shape_decoder.index = 0;
 
-                let r = pow(0.5, i as usize);
+                let r = 0.5_f32.powi(i);
 
                 while let DecodeAction::Continue(read_point, newsegment) =
                     raw_shape_decoder.decode_next()
@@ -159,7 +131,7 @@ impl RawShapeProgram {
         shapes
     }
 
-    fn program_4(&mut self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
+    fn program_4(&self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
         let mut shapes = Shapes::new();
 
         for i in 0..=15 {
@@ -171,7 +143,7 @@ impl RawShapeProgram {
             let an = 2.0 * i as f32 * PI / 6.0 + PI / 12.0;
             let co = an.cos();
             let si = an.sin();
-            let r = pow(0.87, i as usize);
+            let r = 0.87_f32.powi(i);
 
             while let DecodeAction::Continue(read_point, newsegment) =
                 raw_shape_decoder.decode_next()
@@ -196,17 +168,17 @@ impl RawShapeProgram {
         shapes
     }
 
-    fn program_5(&mut self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
+    fn program_5(&self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
         let mut shapes = Shapes::new();
 
         for i in 0..=5 {
-            for j in 0..pow(2, i as usize) {
+            for j in 0..2_i32.pow(i as u32) {
                 let mut shape = Shape::new();
                 let mut segment = Segment::new();
 
                 raw_shape_decoder.index = 0;
 
-                let r = pow(0.5, i as usize);
+                let r = 0.5_f32.powi(i);
 
                 while let DecodeAction::Continue(read_point, newsegment) =
                     raw_shape_decoder.decode_next()
@@ -232,7 +204,7 @@ impl RawShapeProgram {
         shapes
     }
 
-    fn program_6(&mut self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
+    fn program_6(&self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
         let mut shapes = Shapes::new();
 
         for i in 0..=2 {
@@ -264,11 +236,11 @@ impl RawShapeProgram {
         shapes
     }
 
-    fn program_7(&mut self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
+    fn program_7(&self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
         let mut shapes = Shapes::new();
 
         for i in -4..=4 {
-            for j in -abs(i)..=abs(i) {
+            for j in -i32::abs(i)..=i32::abs(i) {
                 let mut shape = Shape::new();
                 let mut segment = Segment::new();
 
@@ -298,7 +270,7 @@ impl RawShapeProgram {
         shapes
     }
 
-    fn program_8(&mut self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
+    fn program_8(&self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
         let mut shapes = Shapes::new();
 
         for i in -4..=4 {
@@ -318,8 +290,8 @@ impl RawShapeProgram {
 
                     let xx = (read_point.x + j as f32 * 20.0 - 20.0) / 100.0;
                     let yy = (read_point.y + i as f32 * 20.0 - 20.0) / 100.0;
-                    let x = xx * abs(xx) * 0.7 * NP as f32;
-                    let y = yy * abs(yy) * 0.7 * NP as f32;
+                    let x = xx * xx.abs() * 0.7 * NP as f32;
+                    let y = yy * yy.abs() * 0.7 * NP as f32;
                     let point = pt2(x, y);
 
                     segment.push(point);
@@ -332,7 +304,7 @@ impl RawShapeProgram {
         shapes
     }
 
-    fn program_9(&mut self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
+    fn program_9(&self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
         let mut shapes = Shapes::new();
 
         for i in -4..=4 {
@@ -378,7 +350,7 @@ impl RawShapeProgram {
         shapes
     }
 
-    fn program_10(&mut self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
+    fn program_10(&self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
         let mut shapes = Shapes::new();
 
         for i in -4..=4 {
@@ -398,8 +370,8 @@ impl RawShapeProgram {
 
                     let xx = (read_point.x + j as f32 * 20.0 - 20.0) / 100.0;
                     let yy = (read_point.y + i as f32 * 20.0 - 20.0) / 100.0;
-                    let x = abs(xx).powf(0.7) * sign(xx) * NP as f32 / 2.0;
-                    let y = abs(yy).powf(0.7) * sign(yy) * NP as f32 / 2.0;
+                    let x = xx.abs().powf(0.7) * sign(xx) * NP as f32 / 2.0;
+                    let y = yy.abs().powf(0.7) * sign(yy) * NP as f32 / 2.0;
 
                     let point = pt2(x, y);
 
@@ -413,7 +385,7 @@ impl RawShapeProgram {
         shapes
     }
 
-    fn program_11(&mut self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
+    fn program_11(&self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
         let mut shapes = Shapes::new();
 
         for i in 0..=4 {
@@ -455,7 +427,7 @@ impl RawShapeProgram {
         shapes
     }
 
-    fn program_12(&mut self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
+    fn program_12(&self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
         let mut shapes = Shapes::new();
 
         for i in 1..=4 {
@@ -490,7 +462,7 @@ impl RawShapeProgram {
         shapes
     }
 
-    fn program_13(&mut self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
+    fn program_13(&self, raw_shape_decoder: &mut RawShapeDecoder) -> Shapes {
         let mut shapes = Shapes::new();
 
         for i in 0..=6 {
@@ -499,7 +471,7 @@ impl RawShapeProgram {
 
             raw_shape_decoder.index = 0;
 
-            let k = (0.5).pow(i) as f32;
+            let k = 0.5_f32.powi(i);
 
             while let DecodeAction::Continue(read_point, newsegment) =
                 raw_shape_decoder.decode_next()
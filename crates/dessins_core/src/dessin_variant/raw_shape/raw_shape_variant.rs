@@ -1,4 +1,4 @@
-use nannou::prelude::*;
+use crate::shapes::{pt2, Point2};
 
 const HORSE: &[f32] = &[
     1000.0, 10.0, 10.0, 8.0, 12.0, 9.0, 16.0, 12.0, 17.0, 13.0, 18.0, 14.0, 20.0, 1000.0, 13.0,
@@ -83,7 +83,8 @@ pub enum DecodeAction {
     Break,
 }
 
-#[derive(Clone, Debug, PartialEq, Reflect)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RawShapeVariant {
     Horse,
     Lion,
@@ -126,25 +127,3 @@ impl RawShapeDecoder {
         DecodeAction::Continue(point, new_segment)
     }
 }
-
-impl RawShapeVariant {
-    pub fn update(&mut self, ui: &mut egui::Ui) -> bool {
-        let mut changed = false;
-
-        ui.label("shape");
-        changed |= ui
-            .radio_value(self, RawShapeVariant::Horse, "horse")
-            .changed();
-        changed |= ui
-            .radio_value(self, RawShapeVariant::Lion, "lion")
-            .changed();
-        changed |= ui
-            .radio_value(self, RawShapeVariant::BirdFish, "bird-fish")
-            .changed();
-        changed |= ui
-            .radio_value(self, RawShapeVariant::Smurf, "smurf")
-            .changed();
-
-        changed
-    }
-}
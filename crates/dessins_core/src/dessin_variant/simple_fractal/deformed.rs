@@ -0,0 +1,112 @@
+use crate::{
+    modifier::Deformation,
+    shapes::{pt2, sign, Point2, Segment, Shape, Shapes, NP},
+};
+use std::f32::consts::PI;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Deformed {
+    pub deformation: Deformation,
+    pub m: u32,                 // # of segments in starting curve
+    pub n: u32,                 // # of sub-segments per segment
+    pub k: u32,                 // depth
+    pub positions: Vec<Point2>, // m + 1 ends of the starting curve's segments
+    pub lengths: Vec<f32>,      // n length ratios of the sub-segments
+    pub angles: Vec<f32>,       // n turning angles of the sub-segments
+}
+
+impl Deformed {
+    // the starting curve and sub-segments of the deformation's original program: a triangle,
+    // or a square for the programs from 9 on
+    pub fn new(deformation: Deformation, k: u32) -> Self {
+        let n = 4;
+        let (m, positions, length, angle) = if deformation.starts_from_square() {
+            let m = 4;
+            let multiplier = NP as f32 / 2.0 * 5.0 / 6.0;
+            let positions = (0..=m)
+                .map(|ij| {
+                    let angle = 2.0 * ij as f32 * PI / m as f32 + PI / 4.0;
+                    pt2(multiplier * angle.cos(), multiplier * angle.sin())
+                })
+                .collect();
+            (m, positions, 1.0 / (2.0 + 2.0 * (0.48 * PI).cos()), 0.48)
+        } else {
+            let m = 3;
+            let multiplier = NP as f32 / 2.0;
+            let positions = (0..=m)
+                .map(|ij| {
+                    let angle = 2.0 * ij as f32 * PI / m as f32;
+                    pt2(multiplier * angle.sin(), multiplier * angle.cos())
+                })
+                .collect();
+            (m, positions, 1.0 / 3.0, 1.0 / 3.0)
+        };
+
+        Self {
+            deformation,
+            m,
+            n,
+            k,
+            positions,
+            lengths: vec![length; n as usize],
+            angles: vec![0.0, angle * PI, -angle * PI, 0.0],
+        }
+    }
+
+    pub fn calculate_shapes(&self) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+        let params = self.deformation.params();
+
+        for ii in 0..self.m as usize {
+            let mut segment = Segment::new();
+
+            let source = self.positions[ii];
+            let destination = self.positions[ii + 1];
+            let diff = destination - source;
+
+            let mut point = source;
+            segment.push(self.deformation.deforme_point(point, &params));
+
+            let angle = if diff.x == 0.0 {
+                PI / 2.0 * sign(diff.y)
+            } else {
+                (diff.y / diff.x).atan()
+            } + if diff.x < 0.0 { PI } else { 0.0 };
+
+            let length = diff.length();
+
+            for i in 0..(self.n as usize).pow(self.k) {
+                let mut current_length = length;
+                let mut current_angle = angle;
+                let mut t1 = i;
+                if self.k as usize != 0 {
+                    for j in (0..self.k).rev() {
+                        let r = (self.n as usize).pow(j);
+                        let t2 = t1 / r;
+                        current_angle += self.angles[t2];
+                        current_length *= self.lengths[t2];
+                        t1 -= t2 * r;
+                    }
+                }
+                point += pt2(
+                    current_length * current_angle.cos(),
+                    current_length * current_angle.sin(),
+                );
+                segment.push(self.deformation.deforme_point(point, &params));
+            }
+            shape.push(segment);
+        }
+
+        shapes.push(shape);
+
+        shapes
+    }
+}
+
+impl Default for Deformed {
+    fn default() -> Self {
+        Self::new(Deformation::Program1, 4)
+    }
+}
@@ -0,0 +1,7 @@
+pub use deformed::Deformed;
+pub use regular::Regular;
+pub use rounded::Rounded;
+
+pub mod deformed;
+pub mod regular;
+pub mod rounded;
@@ -0,0 +1,118 @@
+use crate::{
+    dessin_variant::Polygon,
+    shapes::{pt2, sign, Point2, Segment, Shape, Shapes, NP},
+};
+use std::f32::consts::PI;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Regular {
+    pub m: u32,                 // # of segments in starting curve
+    pub n: u32,                 // # of sub-segments per segment
+    pub k: u32,                 // depth
+    pub positions: Vec<Point2>, // m + 1 ends of the starting curve's segments
+    pub lengths: Vec<f32>,      // n length ratios of the sub-segments
+    pub angles: Vec<f32>,       // n turning angles of the sub-segments
+}
+
+impl Regular {
+    pub fn calculate_shapes(&self) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+
+        for ii in 0..self.m as usize {
+            let mut segment = Segment::new();
+
+            let source = self.positions[ii];
+            let destination = self.positions[ii + 1];
+            let diff = destination - source;
+
+            let mut point = source;
+            segment.push(point);
+
+            let angle = if diff.x == 0.0 {
+                PI / 2.0 * sign(diff.y)
+            } else {
+                (diff.y / diff.x).atan()
+            } + if diff.x < 0.0 { PI } else { 0.0 };
+
+            let length = diff.length();
+
+            for i in 0..(self.n as usize).pow(self.k) {
+                let mut current_length = length;
+                let mut current_angle = angle;
+                let mut t1 = i;
+                if self.k != 0 {
+                    for j in (0..self.k).rev() {
+                        let r = (self.n as usize).pow(j);
+                        let t2 = t1 / r;
+                        current_angle += self.angles[t2];
+                        current_length *= self.lengths[t2];
+                        t1 -= t2 * r;
+                    }
+                }
+                point += pt2(
+                    current_length * current_angle.cos(),
+                    current_length * current_angle.sin(),
+                );
+                segment.push(point);
+            }
+            shape.push(segment);
+        }
+
+        shapes.push(shape);
+
+        shapes
+    }
+
+    pub fn calculate_positions(m: u32) -> Vec<Point2> {
+        let polygon = Polygon {
+            k: m,
+            r: 0.58 * NP as f32,
+            ad: -7.0 / 6.0 * PI,
+        };
+
+        let mut points = vec![];
+        for i in 0..m {
+            let point = polygon.calculate_point(i);
+            points.push(point);
+        }
+        points.push(points[0]);
+
+        points.into_iter().rev().collect()
+    }
+
+    pub fn calculate_lengths(m: f32, n: usize) -> Vec<f32> {
+        vec![1.0 / m; n]
+    }
+
+    // in half turns
+    pub fn calculate_angles(m: f32, n: usize) -> Vec<f32> {
+        let mut angles = vec![0.0];
+
+        for i in 1..(n - 1) {
+            angles.push((1.0 / m) * if i % 2 == 1 { 1.0 } else { -1.0 });
+        }
+
+        angles.push(0.0);
+
+        angles
+    }
+}
+
+impl Default for Regular {
+    fn default() -> Self {
+        let (m, n) = (3, 4);
+        Self {
+            m,
+            n,
+            k: 4,
+            positions: Self::calculate_positions(m),
+            lengths: Self::calculate_lengths(m as f32, n as usize),
+            angles: Self::calculate_angles(m as f32, n as usize)
+                .into_iter()
+                .map(|angle| angle * PI)
+                .collect(),
+        }
+    }
+}
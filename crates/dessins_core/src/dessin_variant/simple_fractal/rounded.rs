@@ -0,0 +1,141 @@
+use crate::shapes::{pt2, Point2, Segment, Shape, Shapes, NP};
+use std::f32::consts::PI;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rounded {
+    pub m: u32,                 // # of segments in starting curve
+    pub n: u32,                 // # of sub-segments per segment
+    pub k: u32,                 // depth
+    pub s: u32,                 // curve fineness
+    pub positions: Vec<Point2>, // m + 1 ends of the starting curve's segments
+    pub lengths: Vec<f32>,      // n length ratios of the sub-segments
+    pub angles: Vec<f32>,       // n turning angles of the sub-segments
+}
+
+impl Rounded {
+    pub fn calculate_shapes(&self) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+
+        for ii in 0..self.m as usize {
+            let mut segment = Segment::new();
+
+            let source = self.positions[ii];
+            let destination = self.positions[ii + 1];
+            let diff = destination - source;
+
+            #[allow(unused_assignments)]
+            let mut point0 = source;
+            let mut point1 = source;
+            let mut point2 = source;
+
+            let angle = if diff.x == 0.0 {
+                PI / 2.0 * if diff.y < 0.0 { -1.0 } else { 1.0 }
+            } else {
+                (diff.y / diff.x).atan()
+            } + if diff.x < 0.0 { PI } else { 0.0 };
+
+            let length = diff.length();
+
+            for i in 0..(self.n).pow(self.k) {
+                let mut current_length = length;
+                let mut current_angle = angle;
+                let mut t1 = i;
+                if self.k != 0 {
+                    for j in (0..self.k).rev() {
+                        let r = (self.n).pow(j);
+                        let t2 = t1 / r;
+                        current_angle += self.angles[t2 as usize];
+                        current_length *= self.lengths[t2 as usize];
+                        t1 -= t2 * r;
+                    }
+                }
+                point0 = point1;
+                point1 = point2;
+                point2 += pt2(
+                    current_length * current_angle.cos(),
+                    current_length * current_angle.sin(),
+                );
+                segment.extend(Self::curve_points(self.s as usize, point0, point1, point2));
+            }
+            shape.push(segment);
+        }
+
+        shapes.push(shape);
+
+        shapes
+    }
+
+    fn curve_points(s: usize, point0: Point2, point1: Point2, point2: Point2) -> Vec<Point2> {
+        let mut points = vec![];
+
+        let v = point1 - point0;
+        let w = point2 - point1;
+
+        for k in 0..=s {
+            let angle = PI / 2.0 * k as f32 / s as f32;
+            let co = angle.cos();
+            let si = angle.sin();
+
+            let q = (point0 + point2 + pt2(-co, -co) * w + pt2(si, si) * v) / pt2(2.0, 2.0);
+            points.push(q);
+        }
+
+        points
+    }
+
+    pub fn calculate_positions(m: u32) -> Vec<Point2> {
+        let np = NP as f32;
+        let mut positions = vec![pt2(-0.5 * np, 0.5 * np), pt2(0.5 * np, -0.5 * np)];
+        if m == 2 {
+            positions.push(positions[0]);
+        }
+        positions
+    }
+
+    pub fn calculate_lengths(m: f32, n: usize) -> Vec<f32> {
+        vec![0.4 / m; n]
+    }
+
+    // in half turns
+    pub fn calculate_angles(n: usize) -> Vec<f32> {
+        let mut angles = vec![];
+
+        for i in 0..(n - 1) {
+            angles.push(if i % 2 == 0 {
+                0.0
+            } else if i % 3 == 0 {
+                -1.0
+            } else {
+                0.5
+            });
+        }
+
+        angles.push(0.0);
+
+        angles
+    }
+}
+
+impl Default for Rounded {
+    fn default() -> Self {
+        let m = 1;
+        Self {
+            m,
+            n: 13,
+            k: 2,
+            s: 4,
+            positions: Self::calculate_positions(m),
+            lengths: vec![
+                0.4, 0.4, 0.2, 0.2, 0.2, 0.2, 0.4, 0.4, 0.2, 0.2, 0.2, 0.2, 0.2,
+            ],
+            angles: [
+                0.0, 0.5, 0.0, -0.5, 0.0, -0.5, -1.0, -0.5, 0.0, 0.5, 0.0, 0.5, 0.0,
+            ]
+            .into_iter()
+            .map(|angle| angle * PI)
+            .collect(),
+        }
+    }
+}
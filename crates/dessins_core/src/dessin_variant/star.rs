@@ -0,0 +1,52 @@
+use crate::shapes::{pt2, Point2, Segment, Shape, Shapes, NP};
+use std::f32::consts::PI;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Star {
+    pub k: u32,  // # vertices
+    pub h: u32,  // # vertices to skip (clockwise) before connecting two dots
+    pub r: f32,  // radius of the circle C on which the vertices are
+    pub ad: f32, // angle (in radians) of the vector CS with horizontal, where S is the first vertex
+}
+
+impl Star {
+    pub fn calculate_shapes(&self) -> Shapes {
+        let mut shapes = Shapes::new();
+        let mut shape = Shape::new();
+        let mut segment = Segment::new();
+
+        for i in 0..self.k {
+            let point = self.calculate_point(i);
+            segment.push(point);
+        }
+
+        segment.push(segment[0]);
+
+        shape.push(segment);
+        shapes.push(shape);
+        shapes
+    }
+
+    pub fn calculate_point(&self, i: u32) -> Point2 {
+        let i = i as f32;
+        let h = self.h as f32;
+        let k = self.k as f32;
+
+        let angle = (2.0 * i * h * PI) / k + self.ad;
+        let x = self.r * angle.cos();
+        let y = self.r * angle.sin();
+        pt2(x, y)
+    }
+}
+
+impl Default for Star {
+    fn default() -> Self {
+        Self {
+            k: 5,
+            h: 3,
+            r: 0.45 * NP as f32,
+            ad: 0.5 * PI,
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod dessin_variant;
+pub mod modifier;
+pub mod shapes;
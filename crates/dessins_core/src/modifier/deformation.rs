@@ -0,0 +1,345 @@
+use crate::shapes::{pt2, sign, Point2, Segment, Shapes, NP};
+use std::f32::consts::PI;
+
+const MAX_STEPS: f32 = NP as f32; // # pieces a single line is resampled into at most
+
+// the polar warps of the deformed fractal, applicable to any shapes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Deformation {
+    Program1,
+    Program2,
+    Program3,
+    Program4,
+    Program5,
+    Program6,
+    Program7,
+    Program8,
+    Program9,
+    Program10,
+    Program11,
+    Program12,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeformationParams {
+    pub exponent: f32,    // of the distance to the centre
+    pub coefficient: f32, // of the change of the angle
+    pub frequency: f32,   // of the waves of the angle, in half turns
+    pub stretch: f32,     // of y
+}
+
+impl Deformation {
+    pub const ALL: &'static [(Self, &'static str)] = &[
+        (Self::Program1, "o"),
+        (Self::Program2, "oO"),
+        (Self::Program3, "oOo"),
+        (Self::Program4, "oOoO"),
+        (Self::Program5, "oOoOo"),
+        (Self::Program6, "oOoOoO"),
+        (Self::Program7, "oOoOoOo"),
+        (Self::Program8, "oOoOoOo"),
+        (Self::Program9, "oOoOoOoO"),
+        (Self::Program10, "oOoOoOoOo"),
+        (Self::Program11, "oOoOoOoOoO"),
+        (Self::Program12, "oOoOoOoOoOo"),
+    ];
+
+    // the values the original programs were written with
+    pub fn params(&self) -> DeformationParams {
+        let (exponent, coefficient, frequency, stretch) = match self {
+            Self::Program1 => (2.0, 0.0, 0.0, 1.0),
+            Self::Program2 => (4.0, PI, 0.0, 1.0),
+            Self::Program3 => (5.0, PI / 4.0, 2.0, 1.0),
+            Self::Program4 => (6.0, 1.0, 0.0, 1.0),
+            Self::Program5 => (6.0, 4.0, 0.0, 1.0),
+            Self::Program6 => (5.0, 10.0, 0.0, 1.0),
+            Self::Program7 => (5.0, PI / 18.0, 6.0, 1.0),
+            Self::Program8 => (5.0, 20.0, 0.0, 1.0),
+            Self::Program9 => (1.0, 1.0, 0.0, 2.0),
+            Self::Program10 => (1.0, PI / 2.0, 0.0, 2.0),
+            Self::Program11 => (2.0, 0.0, 0.0, 2.0),
+            Self::Program12 => (3.0, PI / 18.0, 6.0, 2.0),
+        };
+
+        DeformationParams {
+            exponent,
+            coefficient,
+            frequency,
+            stretch,
+        }
+    }
+
+    // the programs from 9 on start their fractal from a square instead of a triangle
+    pub fn starts_from_square(&self) -> bool {
+        matches!(
+            self,
+            Self::Program9 | Self::Program10 | Self::Program11 | Self::Program12
+        )
+    }
+
+    pub fn has_coefficient(&self) -> bool {
+        !matches!(self, Self::Program1 | Self::Program11)
+    }
+
+    pub fn has_frequency(&self) -> bool {
+        matches!(self, Self::Program3 | Self::Program7 | Self::Program12)
+    }
+
+    // the last programs also swap x and y
+    fn is_swapped(&self) -> bool {
+        matches!(
+            self,
+            Self::Program9 | Self::Program10 | Self::Program11 | Self::Program12
+        )
+    }
+
+    pub fn deforme_point(&self, point: Point2, params: &DeformationParams) -> Point2 {
+        let DeformationParams {
+            exponent,
+            coefficient,
+            frequency,
+            stretch,
+        } = *params;
+
+        let diff = pt2(point.x / NP as f32 * 2.0, point.y / NP as f32 * 2.0);
+        let mut dh = diff.length();
+        let mut angle = self.angle(diff);
+
+        match self {
+            Self::Program1 | Self::Program11 | Self::Program12 => {}
+            Self::Program2 => angle += coefficient * dh,
+            Self::Program3 | Self::Program7 => {
+                angle += coefficient * (frequency * PI * dh).sin();
+            }
+            Self::Program4 | Self::Program5 => {
+                angle = coefficient * angle.powf(3.0) / PI.powf(2.0);
+            }
+            Self::Program6 | Self::Program8 => angle *= coefficient,
+            Self::Program9 => angle *= coefficient * angle / PI * sign(angle),
+            Self::Program10 => angle += coefficient * (1.0 - dh),
+        }
+
+        dh = dh.powf(exponent);
+
+        if *self == Self::Program12 {
+            angle += coefficient * (frequency * PI / dh).sin();
+        }
+
+        let x = dh * angle.cos() * NP as f32 / 2.0;
+        let y = stretch * dh * angle.sin() * NP as f32 / 2.0;
+
+        if self.is_swapped() {
+            pt2(y, x)
+        } else {
+            pt2(x, y)
+        }
+    }
+
+    fn angle(&self, diff: Point2) -> f32 {
+        if diff.x == 0.0 {
+            PI / 2.0 * sign(diff.y)
+        } else if matches!(self, Self::Program1 | Self::Program2 | Self::Program3) {
+            (diff.y / diff.x).atan() - PI * (sign(diff.x) - 1.0) / 2.0
+        } else {
+            (diff.y / diff.x).atan() - PI * (sign(diff.x) - 1.0) / 2.0 * sign(diff.y)
+        }
+    }
+}
+
+// splits the lines longer than `max_length` so that a deformation bends them too
+pub fn resample(shapes: &Shapes, max_length: f32) -> Shapes {
+    let mut resampled = shapes.clone();
+    if max_length <= 0.0 {
+        return resampled;
+    }
+
+    for segment in resampled.iter_mut().flatten() {
+        let Some(&first) = segment.first() else {
+            continue;
+        };
+
+        let mut points = Segment::with_capacity(segment.len());
+        points.push(first);
+        for line in segment.windows(2) {
+            let (start, end) = (line[0], line[1]);
+            let steps = ((end - start).length() / max_length).ceil().min(MAX_STEPS) as usize;
+            points.extend((1..steps).map(|step| start.lerp(end, step as f32 / steps as f32)));
+            points.push(end);
+        }
+        *segment = points;
+    }
+
+    resampled
+}
+
+// moves every point of the resampled shapes
+pub fn deforme(
+    shapes: &Shapes,
+    max_length: f32,
+    mut deforme_point: impl FnMut(Point2) -> Point2,
+) -> Shapes {
+    let mut shapes = resample(shapes, max_length);
+    shapes
+        .iter_mut()
+        .flatten()
+        .flatten()
+        .for_each(|point| *point = deforme_point(*point));
+    shapes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // around the centre, on the axes and in every quadrant, away from the centre where the
+    // last program divides by the distance
+    const POINTS: &[(f32, f32)] = &[
+        (100.0, 50.0),
+        (-120.0, 80.0),
+        (-60.0, -150.0),
+        (200.0, -10.0),
+        (0.0, 90.0),
+        (0.0, -90.0),
+        (-30.0, 0.0),
+        (15.0, 0.5),
+    ];
+
+    // the distance and angle the original programs started from, those from 4 on mirror the
+    // angle of the points below the x axis
+    fn polar(point: Point2, mirrored: bool) -> (f32, f32) {
+        let diff = pt2(point.x / NP as f32 * 2.0, point.y / NP as f32 * 2.0);
+        let mirror = if mirrored { sign(diff.y) } else { 1.0 };
+        let angle = if diff.x == 0.0 {
+            PI / 2.0 * sign(diff.y)
+        } else {
+            (diff.y / diff.x).atan() - PI * (sign(diff.x) - 1.0) / 2.0 * mirror
+        };
+        (diff.length(), angle)
+    }
+
+    fn cartesian(dh: f32, angle: f32) -> Point2 {
+        pt2(
+            dh * angle.cos() * NP as f32 / 2.0,
+            dh * angle.sin() * NP as f32 / 2.0,
+        )
+    }
+
+    // the programs from 9 on stretch y twice and swap x and y
+    fn swapped(dh: f32, angle: f32) -> Point2 {
+        let point = cartesian(dh, angle);
+        pt2(2.0 * point.y, point.x)
+    }
+
+    fn assert_matches(deformation: Deformation, baseline: impl Fn(Point2) -> Point2) {
+        let params = deformation.params();
+        for (x, y) in POINTS {
+            let point = pt2(*x, *y);
+            let expected = baseline(point);
+            let actual = deformation.deforme_point(point, &params);
+            let tolerance = 1e-3 * expected.length().max(1.0);
+            assert!(
+                (actual - expected).length() < tolerance,
+                "{deformation:?} moves {point} to {actual} instead of {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn program_1_matches_the_baseline() {
+        assert_matches(Deformation::Program1, |point| {
+            let (dh, angle) = polar(point, false);
+            cartesian(dh * dh, angle)
+        });
+    }
+
+    #[test]
+    fn program_2_matches_the_baseline() {
+        assert_matches(Deformation::Program2, |point| {
+            let (dh, angle) = polar(point, false);
+            cartesian(dh.powf(4.0), angle + PI * dh)
+        });
+    }
+
+    #[test]
+    fn program_3_matches_the_baseline() {
+        assert_matches(Deformation::Program3, |point| {
+            let (dh, angle) = polar(point, false);
+            cartesian(dh.powf(5.0), angle + PI / 4.0 * (2.0 * PI * dh).sin())
+        });
+    }
+
+    #[test]
+    fn program_4_matches_the_baseline() {
+        assert_matches(Deformation::Program4, |point| {
+            let (dh, angle) = polar(point, true);
+            cartesian(dh.powf(6.0), angle.powf(3.0) / PI.powf(2.0))
+        });
+    }
+
+    #[test]
+    fn program_5_matches_the_baseline() {
+        assert_matches(Deformation::Program5, |point| {
+            let (dh, angle) = polar(point, true);
+            cartesian(dh.powf(6.0), 4.0 * angle.powf(3.0) / PI.powf(2.0))
+        });
+    }
+
+    #[test]
+    fn program_6_matches_the_baseline() {
+        assert_matches(Deformation::Program6, |point| {
+            let (dh, angle) = polar(point, true);
+            cartesian(dh.powf(5.0), angle * 10.0)
+        });
+    }
+
+    #[test]
+    fn program_7_matches_the_baseline() {
+        assert_matches(Deformation::Program7, |point| {
+            let (dh, angle) = polar(point, true);
+            cartesian(dh.powf(5.0), angle + PI / 18.0 * (6.0 * PI * dh).sin())
+        });
+    }
+
+    #[test]
+    fn program_8_matches_the_baseline() {
+        assert_matches(Deformation::Program8, |point| {
+            let (dh, angle) = polar(point, true);
+            cartesian(dh.powf(5.0), angle * 20.0)
+        });
+    }
+
+    #[test]
+    fn program_9_matches_the_baseline() {
+        assert_matches(Deformation::Program9, |point| {
+            let (dh, angle) = polar(point, true);
+            swapped(dh, angle * angle / PI * sign(angle))
+        });
+    }
+
+    #[test]
+    fn program_10_matches_the_baseline() {
+        assert_matches(Deformation::Program10, |point| {
+            let (dh, angle) = polar(point, true);
+            swapped(dh, angle + PI / 2.0 * (1.0 - dh))
+        });
+    }
+
+    #[test]
+    fn program_11_matches_the_baseline() {
+        assert_matches(Deformation::Program11, |point| {
+            let (dh, angle) = polar(point, true);
+            swapped(dh.powf(2.0), angle)
+        });
+    }
+
+    #[test]
+    fn program_12_matches_the_baseline() {
+        assert_matches(Deformation::Program12, |point| {
+            let (dh, angle) = polar(point, true);
+            let dh = dh.powf(3.0);
+            swapped(dh, angle + PI / 18.0 * (6.0 * PI / dh).sin())
+        });
+    }
+}
@@ -0,0 +1,5 @@
+pub use deformation::{Deformation, DeformationParams};
+pub use symmetry::{Symmetry, SymmetryGroup, WallpaperGroup};
+
+pub mod deformation;
+pub mod symmetry;
@@ -0,0 +1,307 @@
+use crate::shapes::{Point2, Shapes};
+use glam::{Affine2, Mat2};
+use std::f32::consts::PI;
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Symmetry {
+    pub group: SymmetryGroup,
+    pub offset: Point2, // of the motif from the centre of symmetry
+    pub scale: f32,     // of the motif
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SymmetryGroup {
+    Rotation {
+        n: u32,
+    }, // n-fold rotations around the centre
+    Mirror {
+        angle: f32,
+    }, // one mirror axis through the centre
+    Dihedral {
+        n: u32,
+        angle: f32,
+    }, // n-fold rotations of the motif and of its mirror image
+    Wallpaper {
+        group: WallpaperGroup,
+        cell: f32,  // distance between two lattice points
+        count: u32, // # cells on each side of the centre
+    },
+}
+
+// the symmorphic wallpaper groups, their symmetries are all centred on the lattice points
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WallpaperGroup {
+    P1,
+    P2,
+    Pm,
+    Pmm,
+    P4,
+    P4m,
+    P3,
+    P6,
+    P6m,
+}
+
+impl Symmetry {
+    // every copy of the motif, including the motif itself
+    pub fn transforms(&self) -> Vec<Affine2> {
+        let motif =
+            Affine2::from_scale_angle_translation(Point2::splat(self.scale), 0.0, self.offset);
+
+        let copies = match &self.group {
+            SymmetryGroup::Rotation { n } => rotations(*n),
+            SymmetryGroup::Mirror { angle } => vec![Mat2::IDENTITY, reflection(*angle)],
+            SymmetryGroup::Dihedral { n, angle } => dihedral(*n, *angle),
+            SymmetryGroup::Wallpaper { group, cell, count } => {
+                return group
+                    .lattice(*cell, *count)
+                    .into_iter()
+                    .flat_map(|translation| {
+                        group.point_group().into_iter().map(move |matrix| {
+                            Affine2::from_mat2_translation(matrix, translation) * motif
+                        })
+                    })
+                    .collect();
+            }
+        };
+
+        copies
+            .into_iter()
+            .map(|matrix| Affine2::from_mat2(matrix) * motif)
+            .collect()
+    }
+
+    pub fn apply(&self, shapes: &Shapes) -> Shapes {
+        let mut result = Shapes::new();
+
+        for transform in self.transforms() {
+            result.extend(shapes.iter().map(|shape| {
+                shape
+                    .iter()
+                    .map(|segment| {
+                        segment
+                            .iter()
+                            .map(|point| transform.transform_point2(*point))
+                            .collect()
+                    })
+                    .collect()
+            }));
+        }
+
+        result
+    }
+}
+
+impl WallpaperGroup {
+    pub const ALL: &'static [(Self, &'static str)] = &[
+        (Self::P1, "p1"),
+        (Self::P2, "p2"),
+        (Self::Pm, "pm"),
+        (Self::Pmm, "pmm"),
+        (Self::P4, "p4"),
+        (Self::P4m, "p4m"),
+        (Self::P3, "p3"),
+        (Self::P6, "p6"),
+        (Self::P6m, "p6m"),
+    ];
+
+    fn hexagonal(&self) -> bool {
+        matches!(self, Self::P3 | Self::P6 | Self::P6m)
+    }
+
+    // symmetries around a lattice point
+    fn point_group(&self) -> Vec<Mat2> {
+        match self {
+            Self::P1 => rotations(1),
+            Self::P2 => rotations(2),
+            Self::Pm => vec![Mat2::IDENTITY, reflection(PI / 2.0)],
+            Self::Pmm => dihedral(2, 0.0),
+            Self::P4 => rotations(4),
+            Self::P4m => dihedral(4, 0.0),
+            Self::P3 => rotations(3),
+            Self::P6 => rotations(6),
+            Self::P6m => dihedral(6, 0.0),
+        }
+    }
+
+    // lattice points within `count` cells of the centre, a square or a hexagon of cells
+    fn lattice(&self, cell: f32, count: u32) -> Vec<Point2> {
+        let count = count as i32;
+        let (a, b) = if self.hexagonal() {
+            (
+                Point2::new(cell, 0.0),
+                Point2::new(cell / 2.0, cell * 3.0_f32.sqrt() / 2.0),
+            )
+        } else {
+            (Point2::new(cell, 0.0), Point2::new(0.0, cell))
+        };
+
+        let mut points = vec![];
+        for i in -count..=count {
+            for j in -count..=count {
+                if self.hexagonal() && (i + j).abs() > count {
+                    continue;
+                }
+                points.push(i as f32 * a + j as f32 * b);
+            }
+        }
+        points
+    }
+}
+
+fn rotations(n: u32) -> Vec<Mat2> {
+    let n = n.max(1);
+    (0..n)
+        .map(|k| Mat2::from_angle(2.0 * PI * k as f32 / n as f32))
+        .collect()
+}
+
+// the mirror axis goes through the centre with `angle` to the horizontal
+fn reflection(angle: f32) -> Mat2 {
+    let (sin, cos) = (2.0 * angle).sin_cos();
+    Mat2::from_cols_array(&[cos, sin, sin, -cos])
+}
+
+fn dihedral(n: u32, angle: f32) -> Vec<Mat2> {
+    let mirror = reflection(angle);
+    rotations(n)
+        .into_iter()
+        .flat_map(|rotation| [rotation, rotation * mirror])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shapes::pt2;
+
+    fn symmetry(group: SymmetryGroup) -> Symmetry {
+        Symmetry {
+            group,
+            offset: Point2::ZERO,
+            scale: 1.0,
+        }
+    }
+
+    fn close(a: Point2, b: Point2) -> bool {
+        (a - b).length() < 1e-4
+    }
+
+    #[test]
+    fn copies_the_motif_once_per_symmetry() {
+        let counts = [
+            (SymmetryGroup::Rotation { n: 5 }, 5),
+            (SymmetryGroup::Rotation { n: 0 }, 1),
+            (SymmetryGroup::Mirror { angle: 0.3 }, 2),
+            (SymmetryGroup::Dihedral { n: 4, angle: 0.0 }, 8),
+            (
+                SymmetryGroup::Wallpaper {
+                    group: WallpaperGroup::P1,
+                    cell: 100.0,
+                    count: 1,
+                },
+                9,
+            ),
+            (
+                SymmetryGroup::Wallpaper {
+                    group: WallpaperGroup::P4m,
+                    cell: 100.0,
+                    count: 1,
+                },
+                9 * 8,
+            ),
+            // a hexagon of 7 cells
+            (
+                SymmetryGroup::Wallpaper {
+                    group: WallpaperGroup::P6,
+                    cell: 100.0,
+                    count: 1,
+                },
+                7 * 6,
+            ),
+        ];
+
+        let mut shapes = Shapes::new();
+        shapes.push(vec![vec![pt2(10.0, 20.0), pt2(30.0, 5.0)]]);
+        shapes.push(vec![vec![pt2(-10.0, 0.0)]]);
+
+        for (group, count) in counts {
+            let symmetry = symmetry(group);
+            assert_eq!(symmetry.transforms().len(), count, "{:?}", symmetry.group);
+            assert_eq!(
+                symmetry.apply(&shapes).len(),
+                2 * count,
+                "{:?}",
+                symmetry.group
+            );
+        }
+    }
+
+    #[test]
+    fn points_on_the_mirror_axis_stay_in_place() {
+        let angle = PI / 6.0;
+        let symmetry = symmetry(SymmetryGroup::Mirror { angle });
+        let on_axis = 50.0 * Point2::from_angle(angle);
+
+        for transform in symmetry.transforms() {
+            assert!(close(transform.transform_point2(on_axis), on_axis));
+        }
+    }
+
+    #[test]
+    fn mirror_images_are_symmetric_about_the_axis() {
+        let angle = PI / 6.0;
+        let axis = Point2::from_angle(angle);
+        let symmetry = symmetry(SymmetryGroup::Mirror { angle });
+        let point = pt2(40.0, -15.0);
+
+        let image = symmetry.transforms()[1].transform_point2(point);
+        let middle = (point + image) / 2.0;
+
+        // the middle lies on the axis and the axis is perpendicular to the line between them
+        assert!(middle.perp_dot(axis).abs() < 1e-4);
+        assert!((image - point).dot(axis).abs() < 1e-4);
+        assert!((image.length() - point.length()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn dihedral_copies_of_a_point_on_an_axis_coincide_in_pairs() {
+        let n = 3;
+        let symmetry = symmetry(SymmetryGroup::Dihedral { n, angle: 0.0 });
+        let on_axis = pt2(50.0, 0.0);
+
+        let mut images = symmetry
+            .transforms()
+            .iter()
+            .map(|transform| transform.transform_point2(on_axis))
+            .collect::<Vec<_>>();
+        images.dedup_by(|a, b| close(*a, *b));
+
+        assert_eq!(images.len(), n as usize);
+        for (k, image) in images.iter().enumerate() {
+            let expected = 50.0 * Point2::from_angle(2.0 * PI * k as f32 / n as f32);
+            assert!(close(*image, expected));
+        }
+    }
+
+    #[test]
+    fn the_motif_is_offset_and_scaled_before_the_copies() {
+        let symmetry = Symmetry {
+            group: SymmetryGroup::Rotation { n: 2 },
+            offset: pt2(100.0, 0.0),
+            scale: 0.5,
+        };
+
+        let images = symmetry
+            .transforms()
+            .iter()
+            .map(|transform| transform.transform_point2(pt2(20.0, 10.0)))
+            .collect::<Vec<_>>();
+
+        assert!(close(images[0], pt2(110.0, 5.0)));
+        assert!(close(images[1], pt2(-110.0, -5.0)));
+    }
+}
@@ -0,0 +1,751 @@
+use anyhow::{anyhow, bail, Context as _};
+use dessins_core::{
+    dessin_variant::{
+        composition_1::Composition1,
+        composition_2::Composition2,
+        curve::{Orbital, Rotating, Spiral},
+        dragon::Dragon,
+        fractal_star::FractalStar,
+        jolygon::Jolygon,
+        linear::{Bipartite, Modulo, Stick},
+        polar_curve::PolarCurve,
+        polygon::Polygon,
+        raw_shape::{RawShape, RawShapeProgram, RawShapeVariant},
+        simple_fractal::{Deformed, Regular, Rounded},
+        star::Star,
+    },
+    modifier::Deformation,
+    shapes::{Point2, Shapes, NP},
+};
+use evalexpr::{ContextWithMutableVariables, HashMapContext, Value};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{
+    f32::consts::{PI, TAU},
+    ops::RangeInclusive,
+};
+
+// the app's dessins without their ui: the params of dessins_core with their defaults, and the
+// expressions the app evaluates into them
+#[derive(Clone, Debug, PartialEq)]
+pub enum Dessin {
+    Polygon(Polygon),
+    Star(Star),
+    Composition1(Composition1),
+    Composition2(Composition2),
+    Jolygon(Jolygon),
+    RawShape(RawShape),
+    Dragon {
+        params: Dragon,
+        l0: String,
+        a0: String,
+    },
+    FractalStar(FractalStar),
+    OrbitalCurve {
+        params: Orbital,
+        r2: String,
+    },
+    RotatingCurve {
+        params: Rotating,
+        s: String,
+    },
+    SpiralCurve(Spiral),
+    LinearBipartite(Bipartite),
+    LinearModulo {
+        params: Modulo,
+        k2: f32,
+        y: String,
+    },
+    LinearStick {
+        params: Stick,
+        r1: String,
+        r2: String,
+    },
+    RegularSimpleFractal(Regular),
+    RoundedSimpleFractal(Rounded),
+    DeformedSimpleFractal(Deformed),
+    PolarCurve {
+        params: PolarCurve,
+        a: String,
+        r: String,
+    },
+}
+
+// a variable of the params, named and set in the units of the app's sliders
+#[derive(Debug)]
+pub enum Variable<'a> {
+    Count(&'a mut u32, RangeInclusive<u32>),
+    Number(&'a mut f32, Unit),
+    Point(&'a mut Point2),
+    Counts(&'a mut Vec<u32>, RangeInclusive<u32>),
+    Numbers(&'a mut Vec<f32>, Unit),
+    Points(&'a mut Vec<Point2>),
+    Expression(&'a mut String),
+    Choice(Choice<'a>),
+}
+
+// what the app chooses with radio buttons, and the deformed fractal's counts that follow its
+// deformation: set with their ron values (e.g. `Program3`) and left out of the values, the app
+// has no variable to set them with
+#[derive(Debug)]
+pub enum Choice<'a> {
+    Count(&'a mut u32),
+    Deformation(&'a mut Deformation),
+    ShapeVariant(&'a mut RawShapeVariant),
+    Program(&'a mut RawShapeProgram),
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub enum Unit {
+    None(RangeInclusive<f32>),
+    Angle,
+    Length,
+    Position,
+}
+
+macro_rules! dessin_variants {
+    ($($variant:ident => $name:literal),* $(,)?) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum DessinVariant {
+            $($variant,)*
+        }
+
+        impl DessinVariant {
+            pub const ALL: &'static [(Self, &'static str)] = &[$((Self::$variant, $name),)*];
+
+            // as the app's presets spell the variant
+            pub fn from_ident(ident: &str) -> Option<Self> {
+                match ident {
+                    $(stringify!($variant) => Some(Self::$variant),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+dessin_variants! {
+    Polygon => "polygon",
+    Star => "star",
+    Composition1 => "composition 1",
+    Composition2 => "composition 2",
+    Jolygon => "jolygon",
+    RawShape => "raw shape",
+    Dragon => "dragon",
+    FractalStar => "fractal star",
+    OrbitalCurve => "orbital curve",
+    RotatingCurve => "rotating curve",
+    SpiralCurve => "spiral curve",
+    LinearBipartite => "linear bipartite",
+    LinearModulo => "linear modulo",
+    LinearStick => "linear stick",
+    RegularSimpleFractal => "regular simple fractal",
+    RoundedSimpleFractal => "rounded simple fractal",
+    DeformedSimpleFractal => "deformed simple fractal",
+    PolarCurve => "polar curve",
+}
+
+impl DessinVariant {
+    pub fn name(&self) -> &'static str {
+        Self::ALL
+            .iter()
+            .find(|(variant, _)| variant == self)
+            .map(|(_, name)| *name)
+            .expect("every variant has a name")
+    }
+
+    // accepts "-" or "_" in place of spaces, e.g. "polar-curve"
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.replace(['-', '_'], " ");
+        Self::ALL
+            .iter()
+            .find(|(_, variant_name)| *variant_name == name)
+            .map(|(variant, _)| *variant)
+    }
+}
+
+impl Dessin {
+    // the app's defaults
+    pub fn new(variant: DessinVariant) -> Self {
+        match variant {
+            DessinVariant::Polygon => Self::Polygon(Polygon::default()),
+            DessinVariant::Star => Self::Star(Star::default()),
+            DessinVariant::Composition1 => Self::Composition1(Composition1::default()),
+            DessinVariant::Composition2 => Self::Composition2(Composition2::default()),
+            DessinVariant::Jolygon => Self::Jolygon(Jolygon::default()),
+            DessinVariant::RawShape => Self::RawShape(RawShape::default()),
+            DessinVariant::Dragon => Self::Dragon {
+                params: Dragon::default(),
+                l0: Dragon::L0.to_string(),
+                a0: Dragon::A0.to_string(),
+            },
+            DessinVariant::FractalStar => Self::FractalStar(FractalStar::default()),
+            DessinVariant::OrbitalCurve => Self::OrbitalCurve {
+                params: Orbital::default(),
+                r2: Orbital::R2.to_string(),
+            },
+            DessinVariant::RotatingCurve => Self::RotatingCurve {
+                params: Rotating::default(),
+                s: Rotating::S.to_string(),
+            },
+            DessinVariant::SpiralCurve => Self::SpiralCurve(Spiral::default()),
+            DessinVariant::LinearBipartite => Self::LinearBipartite(Bipartite::default()),
+            DessinVariant::LinearModulo => Self::LinearModulo {
+                params: Modulo::default(),
+                k2: Modulo::K2,
+                y: Modulo::Y.to_string(),
+            },
+            DessinVariant::LinearStick => Self::LinearStick {
+                params: Stick::default(),
+                r1: Stick::R1.to_string(),
+                r2: Stick::R2.to_string(),
+            },
+            DessinVariant::RegularSimpleFractal => Self::RegularSimpleFractal(Regular::default()),
+            DessinVariant::RoundedSimpleFractal => Self::RoundedSimpleFractal(Rounded::default()),
+            DessinVariant::DeformedSimpleFractal => {
+                Self::DeformedSimpleFractal(Deformed::default())
+            }
+            DessinVariant::PolarCurve => Self::PolarCurve {
+                params: PolarCurve::default(),
+                a: PolarCurve::A.to_string(),
+                r: PolarCurve::R.to_string(),
+            },
+        }
+    }
+
+    pub fn variant(&self) -> DessinVariant {
+        match self {
+            Self::Polygon(_) => DessinVariant::Polygon,
+            Self::Star(_) => DessinVariant::Star,
+            Self::Composition1(_) => DessinVariant::Composition1,
+            Self::Composition2(_) => DessinVariant::Composition2,
+            Self::Jolygon(_) => DessinVariant::Jolygon,
+            Self::RawShape(_) => DessinVariant::RawShape,
+            Self::Dragon { .. } => DessinVariant::Dragon,
+            Self::FractalStar(_) => DessinVariant::FractalStar,
+            Self::OrbitalCurve { .. } => DessinVariant::OrbitalCurve,
+            Self::RotatingCurve { .. } => DessinVariant::RotatingCurve,
+            Self::SpiralCurve(_) => DessinVariant::SpiralCurve,
+            Self::LinearBipartite(_) => DessinVariant::LinearBipartite,
+            Self::LinearModulo { .. } => DessinVariant::LinearModulo,
+            Self::LinearStick { .. } => DessinVariant::LinearStick,
+            Self::RegularSimpleFractal(_) => DessinVariant::RegularSimpleFractal,
+            Self::RoundedSimpleFractal(_) => DessinVariant::RoundedSimpleFractal,
+            Self::DeformedSimpleFractal(_) => DessinVariant::DeformedSimpleFractal,
+            Self::PolarCurve { .. } => DessinVariant::PolarCurve,
+        }
+    }
+
+    // the variables under the names of the app's fields, in their order
+    pub(crate) fn variables(&mut self) -> Vec<(&'static str, Variable<'_>)> {
+        use Variable as V;
+
+        match self {
+            Self::Polygon(params) => polygon_variables(params, ["k", "r", "ad"]),
+            Self::Star(params) => star_variables(params, ["k", "h", "r", "ad"]),
+            Self::Composition1(params) => {
+                let mut variables = polygon_variables(&mut params.polygon, POLYGON);
+                variables.extend(star_variables(&mut params.star, STAR));
+                variables
+            }
+            Self::Composition2(params) => {
+                let mut variables = polygon_variables(&mut params.polygon, POLYGON);
+                variables.extend(star_variables(&mut params.star, STAR));
+                variables.push(("n", V::Count(&mut params.n, 1..=100)));
+                variables.push(("rr", V::Number(&mut params.rr, Unit::None(0.7..=1.3))));
+                variables
+            }
+            Self::Jolygon(params) => vec![
+                ("k", V::Count(&mut params.k, 1..=2500)),
+                ("an", V::Number(&mut params.an, Unit::Angle)),
+                ("ra", V::Number(&mut params.ra, Unit::None(0.9..=1.0))),
+                ("aa", V::Number(&mut params.aa, Unit::Angle)),
+                ("rr", V::Number(&mut params.rr, Unit::Length)),
+            ],
+            Self::RawShape(params) => vec![
+                (
+                    "shape_variant",
+                    V::Choice(Choice::ShapeVariant(&mut params.shape_variant)),
+                ),
+                (
+                    "program_variant",
+                    V::Choice(Choice::Program(&mut params.program_variant)),
+                ),
+            ],
+            Self::Dragon { params, l0, a0 } => vec![
+                ("n", V::Count(&mut params.n, 2..=14)),
+                ("l0", V::Expression(l0)),
+                ("a0", V::Expression(a0)),
+                ("p0", V::Point(&mut params.p0)),
+                ("rules", V::Counts(&mut params.rules, 0..=1)),
+            ],
+            Self::FractalStar(params) => vec![
+                ("n", V::Count(&mut params.n, 3..=20)),
+                ("k", V::Count(&mut params.k, 2..=12)),
+                ("ra", V::Number(&mut params.ra, Unit::None(0.2..=1.8))),
+                ("ll", V::Number(&mut params.ll, Unit::Length)),
+                ("aa", V::Number(&mut params.aa, Unit::Angle)),
+                ("p0", V::Point(&mut params.p0)),
+                ("a0", V::Number(&mut params.a0, Unit::Angle)),
+            ],
+            Self::OrbitalCurve { params, r2 } => vec![
+                ("n", V::Count(&mut params.n, 1000..=6000)),
+                ("t1", V::Count(&mut params.t1, 1..=600)),
+                ("t2", V::Count(&mut params.t2, 1..=600)),
+                ("r1", V::Number(&mut params.r1, Unit::Length)),
+                ("k1", V::Count(&mut params.k1, 1..=4)),
+                ("k2", V::Count(&mut params.k2, 1..=4)),
+                ("r2", V::Expression(r2)),
+            ],
+            Self::RotatingCurve { params, s } => vec![
+                ("n", V::Count(&mut params.n, 1000..=6000)),
+                ("t1", V::Number(&mut params.t1, Unit::None(0.5..=600.0))),
+                ("t2", V::Number(&mut params.t2, Unit::None(0.5..=600.0))),
+                ("r1", V::Number(&mut params.r1, Unit::Length)),
+                ("k1", V::Count(&mut params.k1, 1..=4)),
+                ("k2", V::Count(&mut params.k2, 1..=4)),
+                ("r2", V::Number(&mut params.r2, Unit::Length)),
+                ("h1", V::Count(&mut params.h1, 1..=4)),
+                ("h2", V::Count(&mut params.h2, 1..=4)),
+                ("s", V::Expression(s)),
+            ],
+            Self::SpiralCurve(params) => vec![
+                ("n", V::Count(&mut params.n, 1000..=9000)),
+                ("t", V::Count(&mut params.t, 40..=60)),
+                ("r", V::Number(&mut params.r, Unit::None(0.1..=2.0))),
+                ("l", V::Number(&mut params.l, Unit::None(0.1..=2.0))),
+                (
+                    "an_factor",
+                    V::Number(&mut params.an_factor, Unit::None(1.0..=4.0)),
+                ),
+            ],
+            Self::LinearBipartite(params) => vec![
+                ("n", V::Count(&mut params.n, 10..=20)),
+                ("a", V::Point(&mut params.a)),
+                ("b", V::Point(&mut params.b)),
+                ("c", V::Point(&mut params.c)),
+                ("d", V::Point(&mut params.d)),
+            ],
+            Self::LinearModulo { params, k2, y } => vec![
+                ("n", V::Count(&mut params.n, 10..=400)),
+                ("m", V::Count(&mut params.m, 10..=400)),
+                ("k1", V::Number(&mut params.k1, Unit::None(1.0..=5.0))),
+                ("k2", V::Number(k2, Unit::None(1.0..=5.0))),
+                ("h", V::Count(&mut params.h, 1..=10)),
+                ("i1_factor", V::Count(&mut params.i1_factor, 1..=8)),
+                ("y", V::Expression(y)),
+            ],
+            Self::LinearStick { params, r1, r2 } => vec![
+                ("n", V::Count(&mut params.n, 10..=600)),
+                ("m", V::Count(&mut params.m, 1..=6)),
+                ("k", V::Count(&mut params.k, 1..=7)),
+                ("r1", V::Expression(r1)),
+                ("r2", V::Expression(r2)),
+            ],
+            Self::RegularSimpleFractal(params) => vec![
+                ("m", V::Count(&mut params.m, 1..=4)),
+                ("n", V::Count(&mut params.n, 2..=5)),
+                ("k", V::Count(&mut params.k, 1..=6)),
+                ("positions", V::Points(&mut params.positions)),
+                (
+                    "lengths",
+                    V::Numbers(&mut params.lengths, Unit::None(0.0..=1.0)),
+                ),
+                ("angles", V::Numbers(&mut params.angles, Unit::Angle)),
+            ],
+            Self::RoundedSimpleFractal(params) => vec![
+                ("m", V::Count(&mut params.m, 1..=2)),
+                ("n", V::Count(&mut params.n, 4..=13)),
+                ("k", V::Count(&mut params.k, 1..=5)),
+                ("s", V::Count(&mut params.s, 1..=10)),
+                ("positions", V::Points(&mut params.positions)),
+                (
+                    "lengths",
+                    V::Numbers(&mut params.lengths, Unit::None(0.0..=1.0)),
+                ),
+                ("angles", V::Numbers(&mut params.angles, Unit::Angle)),
+            ],
+            Self::DeformedSimpleFractal(params) => vec![
+                (
+                    "deformation",
+                    V::Choice(Choice::Deformation(&mut params.deformation)),
+                ),
+                ("m", V::Choice(Choice::Count(&mut params.m))),
+                ("n", V::Choice(Choice::Count(&mut params.n))),
+                ("k", V::Count(&mut params.k, 1..=6)),
+                ("positions", V::Points(&mut params.positions)),
+                (
+                    "lengths",
+                    V::Numbers(&mut params.lengths, Unit::None(0.0..=1.0)),
+                ),
+                ("angles", V::Numbers(&mut params.angles, Unit::Angle)),
+            ],
+            Self::PolarCurve { params, a, r } => vec![
+                ("n", V::Count(&mut params.n, 400..=6000)),
+                ("a", V::Expression(a)),
+                ("r", V::Expression(r)),
+            ],
+        }
+    }
+
+    // `name` is a variable name, optionally followed by a path into it (e.g. "p0.x" or
+    // "positions[2].y"), as the app's `set_variable` takes it
+    pub fn set_variable(&mut self, name: &str, value: &str) -> anyhow::Result<()> {
+        let variable_name_end = name.find(['.', '[']).unwrap_or(name.len());
+        let (variable_name, path) = name.split_at(variable_name_end);
+        let variant = self.variant();
+
+        // the deformed fractal starts over from the program's curve when its deformation goes
+        // from the triangle to the square or back, as in the app
+        let square = match self {
+            Self::DeformedSimpleFractal(params) => params.deformation.starts_from_square(),
+            _ => false,
+        };
+
+        let mut variables = self.variables();
+        match variables
+            .iter_mut()
+            .find(|(name, _)| *name == variable_name)
+        {
+            Some((_, variable)) => variable
+                .set(path, value)
+                .with_context(|| format!("could not set {name}"))?,
+            None => bail!("the {} has no variable {variable_name}", variant.name()),
+        }
+
+        if let Self::DeformedSimpleFractal(params) = self {
+            if params.deformation.starts_from_square() != square {
+                *params = Deformed::new(params.deformation, params.k);
+            }
+        }
+
+        Ok(())
+    }
+
+    // `name = value` pairs that `set_variable` takes back, as the app lists them
+    pub fn values(&self) -> Vec<(String, String)> {
+        self.clone()
+            .variables()
+            .iter()
+            .flat_map(|(name, variable)| variable.values(name))
+            .collect()
+    }
+
+    pub fn calculate_shapes(&self) -> anyhow::Result<Shapes> {
+        let shapes = match self {
+            Self::Polygon(params) => params.calculate_shapes(),
+            Self::Star(params) => params.calculate_shapes(),
+            Self::Composition1(params) => params.calculate_shapes(),
+            Self::Composition2(params) => params.calculate_shapes(),
+            Self::Jolygon(params) => params.calculate_shapes(),
+            Self::RawShape(params) => params.calculate_shapes(),
+            Self::Dragon { params, l0, a0 } => {
+                let n = params.n;
+                let ctx = [("n", n as f32)];
+                let mut params = params.clone();
+                // one turning rule per level, the app starts them over when the depth changes
+                if params.rules.len() != n as usize + 1 {
+                    params.rules = vec![0; n as usize + 1];
+                }
+                params.l0 = evaluate(l0, &ctx)?;
+                params.a0 = evaluate(a0, &ctx)?;
+                params.calculate_shapes()
+            }
+            Self::FractalStar(params) => params.calculate_shapes(),
+            Self::OrbitalCurve { params, r2 } => {
+                params.calculate_shapes(function(r2, &[("n", params.n as f32)])?)
+            }
+            Self::RotatingCurve { params, s } => {
+                params.calculate_shapes(function(s, &[("n", params.n as f32)])?)
+            }
+            Self::SpiralCurve(params) => params.calculate_shapes(),
+            Self::LinearBipartite(params) => params.calculate_shapes(),
+            Self::LinearModulo { params, k2, y } => {
+                let ctx = [("n", params.n as f32), ("k2", *k2)];
+                params.calculate_shapes(function(y, &ctx)?)
+            }
+            Self::LinearStick { params, r1, r2 } => {
+                let ctx = [("n", params.n as f32), ("k", params.k as f32)];
+                params.calculate_shapes(function(r1, &ctx)?, function(r2, &ctx)?)
+            }
+            // the app starts the starting curve and the sub-segments over when their count
+            // changes
+            Self::RegularSimpleFractal(params) => {
+                let (m, n) = (params.m, params.n as usize);
+                let mut params = params.clone();
+                if params.positions.len() != m as usize + 1 {
+                    params.positions = Regular::calculate_positions(m);
+                }
+                if params.lengths.len() != n {
+                    params.lengths = Regular::calculate_lengths(m as f32, n);
+                }
+                if params.angles.len() != n {
+                    params.angles = Regular::calculate_angles(m as f32, n)
+                        .into_iter()
+                        .map(|angle| angle * PI)
+                        .collect();
+                }
+                params.calculate_shapes()
+            }
+            Self::RoundedSimpleFractal(params) => {
+                let (m, n) = (params.m, params.n as usize);
+                let mut params = params.clone();
+                if params.positions.len() != m as usize + 1 {
+                    params.positions = Rounded::calculate_positions(m);
+                }
+                if params.lengths.len() != n {
+                    params.lengths = Rounded::calculate_lengths(m as f32, n);
+                }
+                if params.angles.len() != n {
+                    params.angles = Rounded::calculate_angles(n)
+                        .into_iter()
+                        .map(|angle| angle * PI)
+                        .collect();
+                }
+                params.calculate_shapes()
+            }
+            Self::DeformedSimpleFractal(params) => {
+                let (m, n) = (params.m as usize, params.n as usize);
+                if params.positions.len() != m + 1
+                    || params.lengths.len() != n
+                    || params.angles.len() != n
+                {
+                    Deformed::new(params.deformation, params.k).calculate_shapes()
+                } else {
+                    params.calculate_shapes()
+                }
+            }
+            Self::PolarCurve { params, a, r } => {
+                let ctx = [("n", params.n as f32)];
+                params.calculate_shapes(function(a, &ctx)?, function(r, &ctx)?)
+            }
+        };
+
+        Ok(shapes)
+    }
+}
+
+const POLYGON: [&str; 3] = ["polygon_k", "polygon_r", "polygon_ad"];
+const STAR: [&str; 4] = ["star_k", "star_h", "star_r", "star_ad"];
+
+// under the names the compositions prefix with polygon_ and star_
+fn polygon_variables<'a>(
+    params: &'a mut Polygon,
+    [k, r, ad]: [&'static str; 3],
+) -> Vec<(&'static str, Variable<'a>)> {
+    vec![
+        (k, Variable::Count(&mut params.k, 3..=20)),
+        (r, Variable::Number(&mut params.r, Unit::Length)),
+        (ad, Variable::Number(&mut params.ad, Unit::Angle)),
+    ]
+}
+
+fn star_variables<'a>(
+    params: &'a mut Star,
+    [k, h, r, ad]: [&'static str; 4],
+) -> Vec<(&'static str, Variable<'a>)> {
+    vec![
+        (k, Variable::Count(&mut params.k, 5..=100)),
+        (h, Variable::Count(&mut params.h, 3..=5)),
+        (r, Variable::Number(&mut params.r, Unit::Length)),
+        (ad, Variable::Number(&mut params.ad, Unit::Angle)),
+    ]
+}
+
+impl Variable<'_> {
+    fn set(&mut self, path: &str, value: &str) -> anyhow::Result<()> {
+        match self {
+            Self::Count(count, range) => {
+                expect_end(path)?;
+                **count = parse_count(value, range)?;
+            }
+            Self::Number(number, unit) => {
+                expect_end(path)?;
+                **number = unit.parse(value)?;
+            }
+            Self::Point(point) => set_coordinate(point, path, value)?,
+            Self::Counts(counts, range) => {
+                let (count, path) = element(counts, path)?;
+                expect_end(path)?;
+                *count = parse_count(value, range)?;
+            }
+            Self::Numbers(numbers, unit) => {
+                let (number, path) = element(numbers, path)?;
+                expect_end(path)?;
+                *number = unit.parse(value)?;
+            }
+            Self::Points(points) => {
+                let (point, path) = element(points, path)?;
+                set_coordinate(point, path, value)?;
+            }
+            Self::Expression(expression) => {
+                expect_end(path)?;
+                **expression = value.to_string();
+            }
+            Self::Choice(choice) => {
+                expect_end(path)?;
+                choice.set(value)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn values(&self, name: &str) -> Vec<(String, String)> {
+        match self {
+            Self::Count(count, _) => vec![(name.to_string(), count.to_string())],
+            Self::Number(number, unit) => {
+                vec![(name.to_string(), (**number / unit.scale()).to_string())]
+            }
+            Self::Point(point) => point_values(name, point),
+            Self::Counts(counts, _) => counts
+                .iter()
+                .enumerate()
+                .map(|(index, count)| (format!("{name}[{index}]"), count.to_string()))
+                .collect(),
+            Self::Numbers(numbers, unit) => numbers
+                .iter()
+                .enumerate()
+                .map(|(index, number)| {
+                    let value = number / unit.scale();
+                    (format!("{name}[{index}]"), value.to_string())
+                })
+                .collect(),
+            Self::Points(points) => points
+                .iter()
+                .enumerate()
+                .flat_map(|(index, point)| point_values(&format!("{name}[{index}]"), point))
+                .collect(),
+            Self::Expression(expression) => vec![(name.to_string(), expression.to_string())],
+            Self::Choice(_) => vec![],
+        }
+    }
+}
+
+impl Choice<'_> {
+    fn set(&mut self, value: &str) -> anyhow::Result<()> {
+        match self {
+            Self::Count(count) => **count = parse_count(value, &(0..=u32::MAX))?,
+            Self::Deformation(deformation) => **deformation = parse_ron(value)?,
+            Self::ShapeVariant(shape_variant) => **shape_variant = parse_ron(value)?,
+            Self::Program(program) => **program = parse_ron(value)?,
+        }
+        Ok(())
+    }
+}
+
+fn point_values(name: &str, point: &Point2) -> Vec<(String, String)> {
+    let np = NP as f32;
+    vec![
+        (format!("{name}.x"), (point.x / np).to_string()),
+        (format!("{name}.y"), (point.y / np).to_string()),
+    ]
+}
+
+impl Unit {
+    // factor between the value shown on the slider and the value of the params
+    pub fn scale(&self) -> f32 {
+        match self {
+            Self::None(_) => 1.0,
+            Self::Angle => PI,
+            Self::Length | Self::Position => NP as f32,
+        }
+    }
+
+    pub fn range(&self) -> RangeInclusive<f32> {
+        let np = NP as f32;
+        match self {
+            Self::None(range) => range.clone(),
+            Self::Angle => -TAU..=TAU,
+            Self::Length => 0.0..=np,
+            Self::Position => -np..=np,
+        }
+    }
+
+    fn parse(&self, value: &str) -> anyhow::Result<f32> {
+        let range = self.range();
+        Ok((parse_number(value)? * self.scale()).clamp(*range.start(), *range.end()))
+    }
+}
+
+// numbers can be written as expressions of pi, e.g. "pi / 3"
+fn parse_number(value: &str) -> anyhow::Result<f32> {
+    evalexpr::eval_number_with_context(value, &context(&[]))
+        .map(|value| value as f32)
+        .map_err(|err| anyhow!("invalid number {value}: {err}"))
+}
+
+fn parse_count(value: &str, range: &RangeInclusive<u32>) -> anyhow::Result<u32> {
+    let count = parse_number(value)?.round().max(0.0) as u32;
+    Ok(count.clamp(*range.start(), *range.end()))
+}
+
+fn set_coordinate(point: &mut Point2, path: &str, value: &str) -> anyhow::Result<()> {
+    let value = parse_number(value)? * NP as f32;
+    match path {
+        ".x" => point.x = value,
+        ".y" => point.y = value,
+        _ => bail!("expected .x or .y instead of {path:?}"),
+    }
+    Ok(())
+}
+
+// the element at the index starting the path, with the rest of the path
+fn element<'a, 'p, T>(values: &'a mut [T], path: &'p str) -> anyhow::Result<(&'a mut T, &'p str)> {
+    let (index, rest) = path
+        .strip_prefix('[')
+        .and_then(|path| path.split_once(']'))
+        .ok_or_else(|| anyhow!("expected an index like [0] instead of {path:?}"))?;
+    let index = index
+        .parse::<usize>()
+        .map_err(|_| anyhow!("invalid index {index:?}"))?;
+
+    let len = values.len();
+    let value = values
+        .get_mut(index)
+        .ok_or_else(|| anyhow!("index {index} out of bounds (length {len})"))?;
+    Ok((value, rest))
+}
+
+fn parse_ron<T: DeserializeOwned>(value: &str) -> anyhow::Result<T> {
+    ron::from_str(value).map_err(|err| anyhow!("invalid value {value}: {err}"))
+}
+
+fn expect_end(path: &str) -> anyhow::Result<()> {
+    if !path.is_empty() {
+        bail!("unexpected {path:?} after variable name");
+    }
+    Ok(())
+}
+
+fn context(ctx: &[(&str, f32)]) -> HashMapContext {
+    let mut context = HashMapContext::new();
+    for (key, value) in [("pi", PI)].iter().chain(ctx) {
+        context
+            .set_value(key.to_string(), Value::Float(*value as f64))
+            .expect("context must be valid");
+    }
+    context
+}
+
+fn evaluate(expression: &str, ctx: &[(&str, f32)]) -> anyhow::Result<f32> {
+    evalexpr::eval_number_with_context(expression, &context(ctx))
+        .map(|value| value as f32)
+        .map_err(|err| anyhow!("invalid expression {expression}: {err}"))
+}
+
+// the expression as a function of i, the point's index, checked on the first point
+fn function(expression: &str, ctx: &[(&str, f32)]) -> anyhow::Result<impl FnMut(f32) -> f32> {
+    let node = evalexpr::build_operator_tree(expression)
+        .map_err(|err| anyhow!("invalid expression {expression}: {err}"))?;
+    let mut context = context(ctx);
+
+    let mut evaluate = move |i: f32| {
+        context
+            .set_value("i".to_string(), Value::Float(i as f64))
+            .expect("context must be valid");
+        node.eval_number_with_context(&context)
+            .map(|value| value as f32)
+    };
+    evaluate(0.0).map_err(|err| anyhow!("invalid expression {expression}: {err}"))?;
+
+    Ok(move |i| evaluate(i).unwrap_or_default())
+}
@@ -0,0 +1,114 @@
+use crate::plotter::{strokes_on_page, PlotterOptions, Units};
+use dessins_core::shapes::Shapes;
+use std::fmt::Write;
+
+// one pen down stroke per segment
+pub fn gcode(points: &Shapes, options: &PlotterOptions) -> String {
+    let units = options.units;
+    let coordinate = |value: f32| units.from_mm(value);
+
+    let mut gcode = String::new();
+
+    writeln!(gcode, "; dessins").unwrap();
+    match units {
+        Units::Millimeters => writeln!(gcode, "G21 ; millimeters"),
+        Units::Inches => writeln!(gcode, "G20 ; inches"),
+    }
+    .unwrap();
+    writeln!(gcode, "G90 ; absolute positioning").unwrap();
+    writeln!(gcode, "{}", options.pen_up).unwrap();
+
+    for stroke in strokes_on_page(points, options) {
+        let start = stroke[0];
+        writeln!(
+            gcode,
+            "G0 X{:.3} Y{:.3} F{:.0}",
+            coordinate(start.x),
+            coordinate(start.y),
+            options.travel_feed_rate
+        )
+        .unwrap();
+        writeln!(gcode, "{}", options.pen_down).unwrap();
+
+        for (index, point) in stroke.iter().enumerate().skip(1) {
+            write!(
+                gcode,
+                "G1 X{:.3} Y{:.3}",
+                coordinate(point.x),
+                coordinate(point.y)
+            )
+            .unwrap();
+            if index == 1 {
+                write!(gcode, " F{:.0}", options.draw_feed_rate).unwrap();
+            }
+            writeln!(gcode).unwrap();
+        }
+
+        writeln!(gcode, "{}", options.pen_up).unwrap();
+    }
+
+    writeln!(gcode, "G0 X0 Y0 F{:.0}", options.travel_feed_rate).unwrap();
+
+    gcode
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plotter::VIEW_HALF_SIZE;
+    use dessins_core::shapes::pt2;
+
+    fn shapes(segments: Vec<Vec<(f32, f32)>>) -> Shapes {
+        let mut shapes = Shapes::new();
+        shapes.push(
+            segments
+                .into_iter()
+                .map(|segment| segment.into_iter().map(|(x, y)| pt2(x, y)).collect())
+                .collect(),
+        );
+        shapes
+    }
+
+    #[test]
+    fn writes_one_pen_down_stroke_per_segment() {
+        let points = shapes(vec![
+            vec![
+                (0.0, 0.0),
+                (VIEW_HALF_SIZE, 0.0),
+                (VIEW_HALF_SIZE, VIEW_HALF_SIZE),
+            ],
+            vec![(0.0, 0.0)],
+        ]);
+
+        assert_eq!(
+            gcode(&points, &PlotterOptions::default()),
+            "; dessins\n\
+             G21 ; millimeters\n\
+             G90 ; absolute positioning\n\
+             G0 Z2\n\
+             G0 X105.000 Y148.500 F3000\n\
+             G1 Z0 F500\n\
+             G1 X195.000 Y148.500 F1500\n\
+             G1 X195.000 Y238.500\n\
+             G0 Z2\n\
+             G0 X0 Y0 F3000\n"
+        );
+    }
+
+    #[test]
+    fn writes_inches() {
+        let options = PlotterOptions {
+            units: Units::Inches,
+            draw_feed_rate: 60.0,
+            travel_feed_rate: 120.0,
+            ..Default::default()
+        };
+        let points = shapes(vec![vec![(0.0, 0.0), (VIEW_HALF_SIZE, 0.0)]]);
+
+        let gcode = gcode(&points, &options);
+
+        assert!(gcode.contains("G20 ; inches\n"));
+        assert!(gcode.contains("G0 X4.134 Y5.846 F120\n"));
+        assert!(gcode.contains("G1 X7.677 Y5.846 F60\n"));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::plotter::{strokes_on_page, PlotterOptions};
+use dessins_core::shapes::Shapes;
+use std::fmt::Write;
+
+const PLOTTER_UNITS_PER_MM: f32 = 40.0;
+
+// one pen down stroke per segment
+pub fn hpgl(points: &Shapes, options: &PlotterOptions) -> String {
+    let coordinate = |value: f32| (value * PLOTTER_UNITS_PER_MM).round() as i32;
+    // velocity is given in cm/s
+    let velocity = options.units.to_mm(options.draw_feed_rate) / 600.0;
+
+    let mut hpgl = String::new();
+
+    writeln!(hpgl, "IN;SP1;VS{:.1};", velocity).unwrap();
+
+    for stroke in strokes_on_page(points, options) {
+        let start = stroke[0];
+        write!(hpgl, "PU{},{};PD", coordinate(start.x), coordinate(start.y)).unwrap();
+
+        let coordinates = stroke
+            .iter()
+            .skip(1)
+            .map(|point| format!("{},{}", coordinate(point.x), coordinate(point.y)))
+            .collect::<Vec<_>>();
+        writeln!(hpgl, "{};", coordinates.join(",")).unwrap();
+    }
+
+    writeln!(hpgl, "PU;SP0;").unwrap();
+
+    hpgl
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plotter::{Units, VIEW_HALF_SIZE};
+    use dessins_core::shapes::pt2;
+
+    fn shapes(segments: Vec<Vec<(f32, f32)>>) -> Shapes {
+        let mut shapes = Shapes::new();
+        shapes.push(
+            segments
+                .into_iter()
+                .map(|segment| segment.into_iter().map(|(x, y)| pt2(x, y)).collect())
+                .collect(),
+        );
+        shapes
+    }
+
+    #[test]
+    fn writes_one_pen_down_stroke_per_segment() {
+        let points = shapes(vec![
+            vec![
+                (0.0, 0.0),
+                (VIEW_HALF_SIZE, 0.0),
+                (VIEW_HALF_SIZE, VIEW_HALF_SIZE),
+            ],
+            vec![(0.0, 0.0)],
+        ]);
+
+        assert_eq!(
+            hpgl(&points, &PlotterOptions::default()),
+            "IN;SP1;VS2.5;\n\
+             PU4200,5940;PD7800,5940,7800,9540;\n\
+             PU;SP0;\n"
+        );
+    }
+
+    #[test]
+    fn gives_the_velocity_in_centimeters_per_second() {
+        let options = PlotterOptions {
+            units: Units::Inches,
+            draw_feed_rate: 100.0,
+            ..Default::default()
+        };
+
+        assert!(hpgl(&Shapes::new(), &options).starts_with("IN;SP1;VS4.2;\n"));
+    }
+}
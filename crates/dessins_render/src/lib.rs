@@ -0,0 +1,8 @@
+pub mod dessin;
+pub mod gcode;
+pub mod hpgl;
+pub mod optimize;
+pub mod plotter;
+pub mod preset;
+pub mod raster;
+pub mod svg;
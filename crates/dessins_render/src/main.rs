@@ -0,0 +1,221 @@
+use anyhow::{anyhow, bail, Context};
+use dessins_render::{
+    dessin::{Dessin, DessinVariant},
+    gcode::gcode,
+    hpgl::hpgl,
+    optimize::optimize,
+    plotter::PlotterOptions,
+    preset,
+    raster::{encode, rasterize, DPI},
+    svg::{svg_document, SvgOptions},
+};
+use std::{env, fs, path::PathBuf};
+
+const USAGE: &str = "\
+usage: dessins_render [<variant>] [options]
+
+renders a dessin to a file without opening a window, animations are exported from the app
+
+options:
+  -o, --output <path>         file to write, its extension picks the format:
+                              .svg, .png, .gcode (.gc, .nc) or .hpgl (.plt)
+                              (default: <variant>.svg)
+  -p, --preset <path>         start from a preset instead of the variant's defaults, or from
+                              the parameters embedded in an exported .svg, without its
+                              animations
+  -s, --set <name>=<value>    override a variable, e.g. `--set k=7` or `--set p0.x=-0.2`,
+                              values are in the units shown on the sliders, and choices
+                              without a slider are ron, e.g. `--set deformation=Program9`
+      --plotter <path>        plotter options (paper, margin, feed rates...) as ron
+      --optimize              merge, deduplicate and reorder strokes to cut pen up travel
+      --color <hex>           svg or png stroke color, e.g. `#ff8800` (default: black)
+      --background <hex>      svg or png background color, e.g. `#ffffff` (default: none)
+      --stroke-width <n>      svg or png stroke width in view box units (default: 2)
+      --fit                   fit the svg or png view box to the drawing instead of the whole
+                              canvas
+      --size <px>             svg or png size of the longer side in pixels (default: 2000)
+      --dpi <n>               png resolution recorded for printing (default: 300)
+  -l, --list                  list the available variants
+  -h, --help                  print this message";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Format {
+    Svg,
+    Png,
+    Gcode,
+    Hpgl,
+}
+
+#[derive(Default)]
+struct Args {
+    variant: Option<DessinVariant>,
+    output: Option<PathBuf>,
+    preset: Option<PathBuf>,
+    plotter: Option<PathBuf>,
+    optimize: bool,
+    svg: SvgOptions,
+    dpi: Option<f32>,
+    overrides: Vec<(String, String)>,
+}
+
+fn main() -> anyhow::Result<()> {
+    let Some(args) = parse_args()? else {
+        return Ok(());
+    };
+
+    let mut dessin = match (&args.preset, args.variant) {
+        (Some(path), variant) => {
+            let dessin = preset::load(path)?;
+            if let Some(variant) = variant {
+                if variant != dessin.variant() {
+                    bail!(
+                        "preset {} is a {}, not a {}",
+                        path.display(),
+                        dessin.variant().name(),
+                        variant.name()
+                    );
+                }
+            }
+            dessin
+        }
+        (None, Some(variant)) => Dessin::new(variant),
+        (None, None) => bail!("no variant or preset given\n\n{USAGE}"),
+    };
+
+    for (name, value) in &args.overrides {
+        dessin.set_variable(name, value)?;
+    }
+
+    let output = args.output.unwrap_or_else(|| {
+        let name = dessin.variant().name().replace(' ', "-");
+        PathBuf::from(format!("{name}.svg"))
+    });
+
+    let extension = output
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| extension.to_lowercase());
+    let format = match extension.as_deref() {
+        Some("svg") => Format::Svg,
+        Some("png") => Format::Png,
+        Some("gcode" | "gc" | "nc") => Format::Gcode,
+        Some("hpgl" | "plt") => Format::Hpgl,
+        _ => bail!("unknown format for {}", output.display()),
+    };
+
+    let plotter = match &args.plotter {
+        Some(path) => {
+            let ron = fs::read_to_string(path)
+                .with_context(|| format!("could not read {}", path.display()))?;
+            ron::from_str(&ron)
+                .with_context(|| format!("invalid plotter options {}", path.display()))?
+        }
+        None => PlotterOptions::default(),
+    };
+
+    let mut points = dessin.calculate_shapes()?;
+    if args.optimize {
+        let (optimized, report) = optimize(&points);
+        points = optimized;
+        eprintln!("{report}");
+    }
+
+    let contents = match format {
+        Format::Svg => svg_document(&dessin, &points, &args.svg)
+            .to_string()
+            .into_bytes(),
+        Format::Png => encode(&rasterize(&points, &args.svg)?, args.dpi.unwrap_or(DPI))?,
+        Format::Gcode => gcode(&points, &plotter).into_bytes(),
+        Format::Hpgl => hpgl(&points, &plotter).into_bytes(),
+    };
+    fs::write(&output, contents)
+        .with_context(|| format!("could not write {}", output.display()))?;
+
+    println!("{}", output.display());
+
+    Ok(())
+}
+
+fn parse_args() -> anyhow::Result<Option<Args>> {
+    let mut args = Args::default();
+    let mut raw_args = env::args().skip(1);
+
+    while let Some(arg) = raw_args.next() {
+        let mut value = |option: &str| {
+            raw_args
+                .next()
+                .ok_or_else(|| anyhow!("missing value for {option}"))
+        };
+
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(None);
+            }
+            "-l" | "--list" => {
+                for (_, name) in DessinVariant::ALL {
+                    println!("{}", name.replace(' ', "-"));
+                }
+                return Ok(None);
+            }
+            "-o" | "--output" => args.output = Some(value(&arg)?.into()),
+            "-p" | "--preset" => args.preset = Some(value(&arg)?.into()),
+            "--plotter" => args.plotter = Some(value(&arg)?.into()),
+            "--optimize" => args.optimize = true,
+            "--color" => args.svg.color = parse_color(&arg, &value(&arg)?)?,
+            "--background" => args.svg.background = Some(parse_color(&arg, &value(&arg)?)?),
+            "--stroke-width" => args.svg.stroke_width = parse(&arg, &value(&arg)?)?,
+            "--fit" => args.svg.fit = true,
+            "--size" => args.svg.size = parse(&arg, &value(&arg)?)?,
+            "--dpi" => args.dpi = Some(parse(&arg, &value(&arg)?)?),
+            "-s" | "--set" => {
+                let assignment = value(&arg)?;
+                let (name, value) = assignment
+                    .split_once('=')
+                    .with_context(|| format!("expected <name>=<value> instead of {assignment}"))?;
+                args.overrides
+                    .push((name.trim().to_string(), value.trim().to_string()));
+            }
+            option if option.starts_with('-') => bail!("unknown option {option}\n\n{USAGE}"),
+            name => {
+                if args.variant.is_some() {
+                    bail!("more than one variant given\n\n{USAGE}");
+                }
+                let variant = DessinVariant::from_name(name)
+                    .with_context(|| format!("unknown variant {name}, see --list"))?;
+                args.variant = Some(variant);
+            }
+        }
+    }
+
+    Ok(Some(args))
+}
+
+fn parse<T: std::str::FromStr>(option: &str, value: &str) -> anyhow::Result<T> {
+    value
+        .parse()
+        .map_err(|_| anyhow!("invalid value {value} for {option}"))
+}
+
+// #rgb, #rgba, #rrggbb or #rrggbbaa, the # is optional, as a css hex color and an opacity
+fn parse_color(option: &str, value: &str) -> anyhow::Result<(String, f32)> {
+    let invalid = || anyhow!("invalid color {value} for {option}");
+
+    let hex = value.strip_prefix('#').unwrap_or(value);
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let hex = match hex.len() {
+        3 | 4 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 | 8 => hex.to_lowercase(),
+        _ => return Err(invalid()),
+    };
+
+    let (color, alpha) = hex.split_at(6);
+    let opacity = match alpha {
+        "" => 1.0,
+        alpha => u8::from_str_radix(alpha, 16).map_err(|_| invalid())? as f32 / 255.0,
+    };
+
+    Ok((format!("#{}", color.to_lowercase()), opacity))
+}
@@ -0,0 +1,393 @@
+use dessins_core::shapes::{Point2, Segment, Shapes};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+// points closer than this are considered the same when merging and deduplicating
+const TOLERANCE: f32 = 1e-3;
+const TWO_OPT_MAX_PASSES: usize = 8;
+// how far ahead 2-opt looks for a better connection, keeps large drawings tractable
+const TWO_OPT_WINDOW: usize = 500;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OptimizationReport {
+    pub strokes_before: usize,
+    pub strokes_after: usize,
+    pub pen_up_before: f32,
+    pub pen_up_after: f32,
+}
+
+type Key = (i64, i64);
+
+// reorders, merges and deduplicates the segments of each shape to reduce pen up travel, the
+// shapes keep their order and their segments so that they can still be grouped and coloured
+pub fn optimize(points: &Shapes) -> (Shapes, OptimizationReport) {
+    let strokes_before = strokes(points).count();
+    let pen_up_before = pen_up_distance(&strokes(points).cloned().collect::<Vec<_>>());
+
+    let mut shapes = Shapes::new();
+    // each shape starts where the previous one ended
+    let mut position = None;
+
+    for shape in points.iter() {
+        let strokes = shape
+            .iter()
+            .filter(|segment| !segment.is_empty())
+            .cloned()
+            .collect();
+        let strokes = remove_duplicates(strokes);
+        let strokes = merge(strokes);
+        let strokes = nearest_neighbour(strokes, position);
+        let strokes = two_opt(strokes);
+
+        if let Some(last) = strokes.last() {
+            position = Some(end(last));
+            shapes.push(strokes);
+        }
+    }
+
+    let report = OptimizationReport {
+        strokes_before,
+        strokes_after: strokes(&shapes).count(),
+        pen_up_before,
+        pen_up_after: pen_up_distance(&strokes(&shapes).cloned().collect::<Vec<_>>()),
+    };
+
+    (shapes, report)
+}
+
+pub fn pen_up_distance(strokes: &[Segment]) -> f32 {
+    strokes
+        .windows(2)
+        .map(|pair| end(&pair[0]).distance(start(&pair[1])))
+        .sum()
+}
+
+fn strokes(points: &Shapes) -> impl Iterator<Item = &Segment> {
+    points
+        .iter()
+        .flatten()
+        .filter(|segment| !segment.is_empty())
+}
+
+fn start(stroke: &Segment) -> Point2 {
+    stroke[0]
+}
+
+fn end(stroke: &Segment) -> Point2 {
+    stroke[stroke.len() - 1]
+}
+
+fn key(point: Point2) -> Key {
+    (
+        (point.x / TOLERANCE).round() as i64,
+        (point.y / TOLERANCE).round() as i64,
+    )
+}
+
+// drops strokes that retrace another one, in either direction
+fn remove_duplicates(strokes: Vec<Segment>) -> Vec<Segment> {
+    let mut seen = HashSet::new();
+
+    strokes
+        .into_iter()
+        .filter(|stroke| {
+            let forward: Vec<Key> = stroke.iter().copied().map(key).collect();
+            let backward: Vec<Key> = forward.iter().rev().copied().collect();
+            seen.insert(forward.min(backward))
+        })
+        .collect()
+}
+
+// chains strokes whose endpoints meet into longer strokes
+fn merge(strokes: Vec<Segment>) -> Vec<Segment> {
+    let mut endpoints: HashMap<Key, Vec<usize>> = HashMap::new();
+    for (index, stroke) in strokes.iter().enumerate() {
+        endpoints.entry(key(start(stroke))).or_default().push(index);
+        endpoints.entry(key(end(stroke))).or_default().push(index);
+    }
+
+    let mut used = vec![false; strokes.len()];
+
+    let mut take_at = |point: Point2, used: &mut Vec<bool>| -> Option<Segment> {
+        let candidates = endpoints.get_mut(&key(point))?;
+        while let Some(index) = candidates.pop() {
+            if used[index] {
+                continue;
+            }
+            used[index] = true;
+
+            let mut stroke = strokes[index].clone();
+            if key(start(&stroke)) != key(point) {
+                stroke.reverse();
+            }
+            return Some(stroke);
+        }
+        None
+    };
+
+    let mut merged = vec![];
+
+    for index in 0..strokes.len() {
+        if used[index] {
+            continue;
+        }
+        used[index] = true;
+
+        let mut stroke = strokes[index].clone();
+
+        while let Some(next) = take_at(end(&stroke), &mut used) {
+            stroke.extend(next.into_iter().skip(1));
+        }
+
+        while let Some(previous) = take_at(start(&stroke), &mut used) {
+            let mut previous: Segment = previous.into_iter().rev().collect();
+            previous.extend(stroke.into_iter().skip(1));
+            stroke = previous;
+        }
+
+        merged.push(stroke);
+    }
+
+    merged
+}
+
+// greedily plots the closest stroke next, reversing it when its end is closer than its start,
+// from `position` or the start of the first stroke
+fn nearest_neighbour(mut strokes: Vec<Segment>, position: Option<Point2>) -> Vec<Segment> {
+    let mut ordered = Vec::with_capacity(strokes.len());
+
+    let Some(first) = strokes.first() else {
+        return ordered;
+    };
+    let mut position = position.unwrap_or_else(|| start(first));
+
+    while !strokes.is_empty() {
+        let mut best = (0, false, f32::MAX);
+
+        for (index, stroke) in strokes.iter().enumerate() {
+            let to_start = position.distance_squared(start(stroke));
+            let to_end = position.distance_squared(end(stroke));
+
+            if to_start < best.2 {
+                best = (index, false, to_start);
+            }
+            if to_end < best.2 {
+                best = (index, true, to_end);
+            }
+        }
+
+        let (index, reverse, _) = best;
+        let mut stroke = strokes.swap_remove(index);
+        if reverse {
+            stroke.reverse();
+        }
+
+        position = end(&stroke);
+        ordered.push(stroke);
+    }
+
+    ordered
+}
+
+// reverses runs of strokes (and the direction of each stroke in the run) while that shortens
+// the pen up travel
+fn two_opt(mut strokes: Vec<Segment>) -> Vec<Segment> {
+    let n = strokes.len();
+    if n < 3 {
+        return strokes;
+    }
+
+    let travel = |from: Point2, to: Point2| from.distance(to);
+
+    for _ in 0..TWO_OPT_MAX_PASSES {
+        let mut improved = false;
+
+        for i in 1..n - 1 {
+            for j in (i + 1)..n.min(i + TWO_OPT_WINDOW) {
+                let before = end(&strokes[i - 1]);
+                let first = start(&strokes[i]);
+                let last = end(&strokes[j]);
+
+                let (current, candidate) = match strokes.get(j + 1) {
+                    Some(after) => {
+                        let after = start(after);
+                        (
+                            travel(before, first) + travel(last, after),
+                            travel(before, last) + travel(first, after),
+                        )
+                    }
+                    None => (travel(before, first), travel(before, last)),
+                };
+
+                if candidate + TOLERANCE < current {
+                    strokes[i..=j].reverse();
+                    strokes[i..=j]
+                        .iter_mut()
+                        .for_each(|stroke| stroke.reverse());
+                    improved = true;
+                }
+            }
+        }
+
+        if !improved {
+            break;
+        }
+    }
+
+    strokes
+}
+
+impl fmt::Display for OptimizationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} → {} strokes, pen up travel {:.0} → {:.0}",
+            self.strokes_before, self.strokes_after, self.pen_up_before, self.pen_up_after
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dessins_core::shapes::pt2;
+
+    fn stroke(points: &[(f32, f32)]) -> Segment {
+        points.iter().map(|(x, y)| pt2(*x, *y)).collect()
+    }
+
+    #[test]
+    fn remove_duplicates_drops_retraced_strokes() {
+        let strokes = vec![
+            stroke(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)]),
+            stroke(&[(1.0, 1.0), (1.0, 0.0), (0.0, 0.0)]),
+            stroke(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0 + TOLERANCE / 4.0)]),
+            stroke(&[(0.0, 0.0), (1.0, 0.0)]),
+        ];
+
+        assert_eq!(
+            remove_duplicates(strokes),
+            vec![
+                stroke(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)]),
+                stroke(&[(0.0, 0.0), (1.0, 0.0)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_chains_strokes_meeting_at_either_end() {
+        let strokes = vec![
+            stroke(&[(0.0, 0.0), (1.0, 0.0)]),
+            stroke(&[(2.0, 0.0), (1.0, 0.0)]),
+            stroke(&[(-1.0, 0.0), (0.0, 0.0)]),
+            stroke(&[(5.0, 5.0), (6.0, 6.0)]),
+        ];
+
+        assert_eq!(
+            merge(strokes),
+            vec![
+                stroke(&[(-1.0, 0.0), (0.0, 0.0), (1.0, 0.0), (2.0, 0.0)]),
+                stroke(&[(5.0, 5.0), (6.0, 6.0)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn nearest_neighbour_plots_the_closest_end_next() {
+        let strokes = vec![
+            stroke(&[(0.0, 0.0), (1.0, 0.0)]),
+            stroke(&[(10.0, 0.0), (11.0, 0.0)]),
+            stroke(&[(3.0, 0.0), (2.0, 0.0)]),
+        ];
+
+        assert_eq!(
+            nearest_neighbour(strokes.clone(), None),
+            vec![
+                stroke(&[(0.0, 0.0), (1.0, 0.0)]),
+                stroke(&[(2.0, 0.0), (3.0, 0.0)]),
+                stroke(&[(10.0, 0.0), (11.0, 0.0)]),
+            ]
+        );
+        assert_eq!(
+            nearest_neighbour(strokes, Some(pt2(12.0, 0.0))),
+            vec![
+                stroke(&[(11.0, 0.0), (10.0, 0.0)]),
+                stroke(&[(3.0, 0.0), (2.0, 0.0)]),
+                stroke(&[(1.0, 0.0), (0.0, 0.0)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn two_opt_uncrosses_the_order() {
+        let strokes = vec![
+            stroke(&[(0.0, 0.0)]),
+            stroke(&[(2.0, 0.0)]),
+            stroke(&[(1.0, 0.0)]),
+            stroke(&[(3.0, 0.0)]),
+        ];
+        assert_eq!(pen_up_distance(&strokes), 5.0);
+
+        let strokes = two_opt(strokes);
+
+        assert_eq!(
+            strokes,
+            vec![
+                stroke(&[(0.0, 0.0)]),
+                stroke(&[(1.0, 0.0)]),
+                stroke(&[(2.0, 0.0)]),
+                stroke(&[(3.0, 0.0)]),
+            ]
+        );
+        assert_eq!(pen_up_distance(&strokes), 3.0);
+    }
+
+    #[test]
+    fn pen_up_distance_sums_the_travel_between_strokes() {
+        assert_eq!(pen_up_distance(&[]), 0.0);
+        assert_eq!(pen_up_distance(&[stroke(&[(0.0, 0.0), (1.0, 0.0)])]), 0.0);
+        assert_eq!(
+            pen_up_distance(&[
+                stroke(&[(0.0, 0.0), (1.0, 0.0)]),
+                stroke(&[(4.0, 4.0), (5.0, 5.0)]),
+                stroke(&[(5.0, 5.0), (5.0, 6.0)]),
+            ]),
+            5.0
+        );
+    }
+
+    #[test]
+    fn optimize_keeps_the_shapes() {
+        let mut points = Shapes::new();
+        points.push(vec![
+            stroke(&[(0.0, 0.0), (1.0, 0.0)]),
+            stroke(&[(1.0, 0.0), (0.0, 0.0)]),
+            stroke(&[(1.0, 0.0), (2.0, 0.0)]),
+        ]);
+        points.push(vec![]);
+        points.push(vec![
+            stroke(&[(10.0, 0.0), (11.0, 0.0)]),
+            stroke(&[(3.0, 0.0), (4.0, 0.0)]),
+        ]);
+
+        let (shapes, report) = optimize(&points);
+
+        assert_eq!(shapes.len(), 2);
+        assert_eq!(
+            shapes[0],
+            vec![stroke(&[(0.0, 0.0), (1.0, 0.0), (2.0, 0.0)])]
+        );
+        assert_eq!(
+            shapes[1],
+            vec![
+                stroke(&[(3.0, 0.0), (4.0, 0.0)]),
+                stroke(&[(10.0, 0.0), (11.0, 0.0)]),
+            ]
+        );
+        assert_eq!(report.strokes_before, 5);
+        assert_eq!(report.strokes_after, 3);
+        assert!(report.pen_up_after < report.pen_up_before);
+    }
+}
@@ -0,0 +1,155 @@
+use dessins_core::shapes::{pt2, Point2, Shapes};
+use serde::{Deserialize, Serialize};
+
+// half the side of the square shown on screen and in the svg view box
+pub const VIEW_HALF_SIZE: f32 = 360.0;
+
+const MM_PER_INCH: f32 = 25.4;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlotterOptions {
+    pub paper_width: f32,  // mm
+    pub paper_height: f32, // mm
+    pub margin: f32,       // mm
+    pub units: Units,
+    pub draw_feed_rate: f32,   // units per minute, while the pen is down
+    pub travel_feed_rate: f32, // units per minute, while the pen is up
+    pub pen_up: String,        // g-code command lifting the pen
+    pub pen_down: String,      // g-code command lowering the pen
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Units {
+    Millimeters,
+    Inches,
+}
+
+// every segment of every shape, mapped onto the page in millimeters with the origin at the bottom
+// left corner of the paper, a single point is no stroke for a pen
+pub fn strokes_on_page(points: &Shapes, options: &PlotterOptions) -> Vec<Vec<Point2>> {
+    let printable_width = (options.paper_width - 2.0 * options.margin).max(0.0);
+    let printable_height = (options.paper_height - 2.0 * options.margin).max(0.0);
+    let scale = printable_width.min(printable_height) / (2.0 * VIEW_HALF_SIZE);
+    let center = pt2(options.paper_width / 2.0, options.paper_height / 2.0);
+
+    points
+        .iter()
+        .flatten()
+        .filter(|segment| segment.len() >= 2)
+        .map(|segment| {
+            segment
+                .iter()
+                .map(|point| center + *point * scale)
+                .collect()
+        })
+        .collect()
+}
+
+impl Units {
+    pub fn from_mm(&self, value: f32) -> f32 {
+        match self {
+            Self::Millimeters => value,
+            Self::Inches => value / MM_PER_INCH,
+        }
+    }
+
+    pub fn to_mm(&self, value: f32) -> f32 {
+        match self {
+            Self::Millimeters => value,
+            Self::Inches => value * MM_PER_INCH,
+        }
+    }
+}
+
+impl Default for PlotterOptions {
+    fn default() -> Self {
+        Self {
+            paper_width: 210.0,
+            paper_height: 297.0,
+            margin: 15.0,
+            units: Units::Millimeters,
+            draw_feed_rate: 1500.0,
+            travel_feed_rate: 3000.0,
+            pen_up: "G0 Z2".to_string(),
+            pen_down: "G1 Z0 F500".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shapes(segments: Vec<Vec<(f32, f32)>>) -> Shapes {
+        let mut shapes = Shapes::new();
+        shapes.push(
+            segments
+                .into_iter()
+                .map(|segment| segment.into_iter().map(|(x, y)| pt2(x, y)).collect())
+                .collect(),
+        );
+        shapes
+    }
+
+    #[test]
+    fn fits_the_view_into_the_printable_area() {
+        // a4 with 15 mm margins, the 180 mm wide printable area holds the view square
+        let points = shapes(vec![vec![
+            (0.0, 0.0),
+            (VIEW_HALF_SIZE, VIEW_HALF_SIZE),
+            (-VIEW_HALF_SIZE, -VIEW_HALF_SIZE),
+        ]]);
+
+        let strokes = strokes_on_page(&points, &PlotterOptions::default());
+
+        assert_eq!(
+            strokes,
+            vec![vec![pt2(105.0, 148.5), pt2(195.0, 238.5), pt2(15.0, 58.5)]]
+        );
+    }
+
+    #[test]
+    fn the_shorter_printable_side_sets_the_scale() {
+        let options = PlotterOptions {
+            paper_width: 420.0,
+            paper_height: 100.0,
+            margin: 10.0,
+            ..Default::default()
+        };
+        let points = shapes(vec![vec![(VIEW_HALF_SIZE, 0.0), (0.0, -VIEW_HALF_SIZE)]]);
+
+        let strokes = strokes_on_page(&points, &options);
+
+        assert_eq!(strokes, vec![vec![pt2(250.0, 50.0), pt2(210.0, 10.0)]]);
+    }
+
+    #[test]
+    fn margins_wider_than_the_paper_shrink_the_dessin_to_a_point() {
+        let options = PlotterOptions {
+            margin: 200.0,
+            ..Default::default()
+        };
+        let points = shapes(vec![vec![(VIEW_HALF_SIZE, 0.0), (0.0, -VIEW_HALF_SIZE)]]);
+
+        let strokes = strokes_on_page(&points, &options);
+
+        assert_eq!(strokes, vec![vec![pt2(105.0, 148.5), pt2(105.0, 148.5)]]);
+    }
+
+    #[test]
+    fn skips_segments_without_a_line() {
+        let points = shapes(vec![vec![], vec![(1.0, 2.0)], vec![(0.0, 0.0), (4.0, 0.0)]]);
+
+        let strokes = strokes_on_page(&points, &PlotterOptions::default());
+
+        assert_eq!(strokes, vec![vec![pt2(105.0, 148.5), pt2(106.0, 148.5)]]);
+    }
+
+    #[test]
+    fn converts_inches() {
+        assert_eq!(Units::Inches.from_mm(25.4), 1.0);
+        assert_eq!(Units::Inches.to_mm(2.0), 50.8);
+        assert_eq!(Units::Millimeters.from_mm(25.4), 25.4);
+        assert_eq!(Units::Millimeters.to_mm(2.0), 2.0);
+    }
+}
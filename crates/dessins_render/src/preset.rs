@@ -0,0 +1,276 @@
+use crate::{
+    dessin::{Choice, Dessin, DessinVariant, Unit, Variable},
+    svg::read_variables,
+};
+use anyhow::{anyhow, bail, Context as _};
+use dessins_core::shapes::Point2;
+use serde::{
+    de::{self, DeserializeOwned, DeserializeSeed, EnumAccess, MapAccess, VariantAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use std::{fmt, fs, path::Path};
+
+const VERSION: u32 = 1;
+
+// the app's presets, of which only the variables are read: the animations and osc bindings need
+// the app
+#[derive(Deserialize)]
+struct Preset {
+    version: u32,
+    variables: Variables,
+}
+
+struct Variables(Dessin);
+
+// the layouts of the app's adjustable variables, the ranges and animations being skipped
+#[derive(Deserialize)]
+struct Adjustable<T> {
+    value: T,
+}
+
+// a number's unit is read for its type: ron can't skip a `None(..)` unit
+#[derive(Deserialize)]
+struct Number {
+    value: f32,
+    #[serde(rename = "variant")]
+    _unit: Unit,
+}
+
+#[derive(Deserialize)]
+struct Numbers {
+    value: Vec<Number>,
+    #[serde(rename = "variant")]
+    _unit: Unit,
+}
+
+#[derive(Deserialize)]
+struct Expression {
+    expr: String,
+}
+
+// a .ron preset saved by the app, or the parameters embedded in an svg it exported
+pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Dessin> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("could not read preset from {}", path.display()))?;
+
+    let is_svg = path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("svg"));
+    let dessin = if is_svg {
+        from_svg(&content)
+    } else {
+        from_ron(&content)
+    };
+    dessin.with_context(|| format!("invalid preset {}", path.display()))
+}
+
+pub fn from_ron(ron: &str) -> anyhow::Result<Dessin> {
+    let preset: Preset = ron::from_str(ron)?;
+
+    if preset.version > VERSION {
+        bail!(
+            "preset version {} is newer than the supported version {}",
+            preset.version,
+            VERSION
+        );
+    }
+
+    let Variables(dessin) = preset.variables;
+    Ok(dessin)
+}
+
+pub fn from_svg(content: &str) -> anyhow::Result<Dessin> {
+    let (variant, values) = read_variables(content)?;
+    let variant =
+        DessinVariant::from_name(&variant).ok_or_else(|| anyhow!("unknown dessin {variant}"))?;
+
+    let mut dessin = Dessin::new(variant);
+    for (name, value) in values {
+        dessin.set_variable(&name, &value)?;
+    }
+
+    Ok(dessin)
+}
+
+impl<'de> Deserialize<'de> for Variables {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct VariablesVisitor;
+
+        impl<'de> Visitor<'de> for VariablesVisitor {
+            type Value = Variables;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("the variables of a dessin")
+            }
+
+            fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Variables, A::Error> {
+                let (Identifier(variant), fields) = data.variant()?;
+                let variant = DessinVariant::from_ident(&variant)
+                    .ok_or_else(|| de::Error::custom(format!("unknown dessin {variant}")))?;
+                fields.newtype_variant_seed(Fields(variant)).map(Variables)
+            }
+        }
+
+        deserializer.deserialize_enum("DessinVariables", &[], VariablesVisitor)
+    }
+}
+
+// ron reads the names of variants and fields as identifiers, not as strings
+struct Identifier(String);
+
+impl<'de> Deserialize<'de> for Identifier {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct IdentifierVisitor;
+
+        impl<'de> Visitor<'de> for IdentifierVisitor {
+            type Value = Identifier;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a dessin name")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Identifier, E> {
+                Ok(Identifier(value.to_string()))
+            }
+        }
+
+        deserializer.deserialize_identifier(IdentifierVisitor)
+    }
+}
+
+// the fields of the variant's variables, every one of them a variable of the dessin
+struct Fields(DessinVariant);
+
+impl<'de> DeserializeSeed<'de> for Fields {
+    type Value = Dessin;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Dessin, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Fields {
+    type Value = Dessin;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "the variables of the {}", self.0.name())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Dessin, A::Error> {
+        let mut dessin = Dessin::new(self.0);
+        while let Some(Identifier(name)) = map.next_key()? {
+            let mut variables = dessin.variables();
+            let Some((_, variable)) = variables
+                .iter_mut()
+                .find(|(variable_name, _)| *variable_name == name)
+            else {
+                return Err(de::Error::custom(format!(
+                    "the {} has no variable {name}",
+                    self.0.name()
+                )));
+            };
+            read_variable(variable, &mut map)?;
+        }
+        Ok(dessin)
+    }
+}
+
+// the values are stored as the dessin's params take them, not in the units of the sliders
+fn read_variable<'de, A: MapAccess<'de>>(
+    variable: &mut Variable,
+    map: &mut A,
+) -> Result<(), A::Error> {
+    match variable {
+        Variable::Count(count, _) => **count = value(map)?,
+        Variable::Number(number, _) => **number = map.next_value::<Number>()?.value,
+        Variable::Point(point) => **point = value(map)?,
+        Variable::Counts(counts, _) => {
+            **counts = value::<Vec<Adjustable<u32>>, _>(map)?
+                .into_iter()
+                .map(|count| count.value)
+                .collect();
+        }
+        Variable::Numbers(numbers, _) => {
+            **numbers = map
+                .next_value::<Numbers>()?
+                .value
+                .into_iter()
+                .map(|number| number.value)
+                .collect();
+        }
+        Variable::Points(points) => {
+            **points = value::<Vec<Adjustable<Point2>>, _>(map)?
+                .into_iter()
+                .map(|point| point.value)
+                .collect();
+        }
+        Variable::Expression(expression) => **expression = map.next_value::<Expression>()?.expr,
+        Variable::Choice(Choice::Count(count)) => **count = value(map)?,
+        Variable::Choice(Choice::Deformation(deformation)) => **deformation = map.next_value()?,
+        Variable::Choice(Choice::ShapeVariant(shape_variant)) => {
+            **shape_variant = map.next_value()?;
+        }
+        Variable::Choice(Choice::Program(program)) => **program = map.next_value()?,
+    }
+    Ok(())
+}
+
+fn value<'de, T: DeserializeOwned, A: MapAccess<'de>>(map: &mut A) -> Result<T, A::Error> {
+    map.next_value::<Adjustable<T>>()
+        .map(|adjustable| adjustable.value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dessins_core::{
+        dessin_variant::raw_shape::{RawShape, RawShapeProgram, RawShapeVariant},
+        modifier::Deformation,
+    };
+
+    #[test]
+    fn reads_the_choices_the_app_has_no_slider_for() {
+        let ron =
+            "(version: 1, variables: RawShape((shape_variant: Lion, program_variant: Program7)))";
+        let expected = Dessin::RawShape(RawShape {
+            shape_variant: RawShapeVariant::Lion,
+            program_variant: RawShapeProgram::Program7,
+        });
+        assert_eq!(from_ron(ron).unwrap(), expected);
+
+        let ron = "(version: 1, variables: DeformedSimpleFractal((
+            deformation: Program9,
+            m: (value: 4, range: (start: 1, end: 4), animation: None),
+            k: (value: 2, range: (start: 1, end: 6), animation: None),
+            angles: (value: [(value: 0.5, variant: Angle, animation: None)], variant: Angle),
+        )))";
+        let Dessin::DeformedSimpleFractal(deformed) = from_ron(ron).unwrap() else {
+            panic!("expected a deformed simple fractal");
+        };
+        assert_eq!(deformed.deformation, Deformation::Program9);
+        assert_eq!((deformed.m, deformed.k), (4, 2));
+        assert_eq!(deformed.angles, vec![0.5]);
+    }
+
+    #[test]
+    fn reads_expressions_and_skips_animations() {
+        let ron = r#"(version: 1, variables: PolarCurve((
+            n: (value: 1500, range: (start: 400, end: 6000), animation: Some((variant: Sine))),
+            r: (expr: "0.5", default_expr: "i/n", ctx: ({"n": 2000.0}), ctx_ext: {"i": ()}, value: 0.0, range: (start: 0.0, end: 480.0), step: 0.1),
+        )))"#;
+        let Dessin::PolarCurve { params, a, r } = from_ron(ron).unwrap() else {
+            panic!("expected a polar curve");
+        };
+        assert_eq!(params.n, 1500);
+        assert_eq!(a, dessins_core::dessin_variant::PolarCurve::A);
+        assert_eq!(r, "0.5");
+    }
+
+    #[test]
+    fn fails_on_unknown_variables() {
+        let ron = "(version: 1, variables: Polygon((q: (value: 3))))";
+        let err = from_ron(ron).unwrap_err();
+        assert!(err.to_string().contains("no variable q"), "{err}");
+    }
+}
@@ -0,0 +1,208 @@
+use crate::svg::{view_box, SvgOptions};
+use anyhow::{anyhow, Context as _};
+use dessins_core::shapes::{Point2, Shapes};
+use tiny_skia::{Color, LineCap, LineJoin, Paint, PathBuilder, Pixmap, Stroke, Transform};
+
+pub const DPI: f32 = 300.0;
+
+const INCHES_PER_METER: f32 = 39.370_08;
+
+// the same drawing as `svg_document`, `size` pixels on the longer side
+pub fn rasterize(points: &Shapes, options: &SvgOptions) -> anyhow::Result<Pixmap> {
+    let view_box = view_box(points, options.fit, options.stroke_width);
+    let (_, _, view_width, view_height) = view_box;
+    let scale = options.size / view_width.max(view_height);
+    let width = (view_width * scale).round() as u32;
+    let height = (view_height * scale).round() as u32;
+
+    let background = match &options.background {
+        Some((hex, opacity)) => Some(css_color(hex, *opacity)?),
+        None => None,
+    };
+    let mut pixmap = pixmap(width, height, background)?;
+    let transform = transform(view_box, width, height);
+
+    let (hex, opacity) = &options.color;
+    let mut paint = Paint {
+        anti_alias: true,
+        ..Default::default()
+    };
+    paint.set_color(css_color(hex, *opacity)?);
+
+    let stroke = Stroke {
+        width: options.stroke_width,
+        line_cap: LineCap::Round,
+        line_join: LineJoin::Round,
+        ..Default::default()
+    };
+
+    for segment in points.iter().flatten() {
+        stroke_points(&mut pixmap, segment, &paint, &stroke, transform);
+    }
+
+    Ok(pixmap)
+}
+
+// a transparent image unless a background is given
+pub fn pixmap(width: u32, height: u32, background: Option<Color>) -> anyhow::Result<Pixmap> {
+    let mut pixmap = Pixmap::new(width, height)
+        .with_context(|| format!("invalid image size {width}x{height}"))?;
+    if let Some(background) = background {
+        pixmap.fill(background);
+    }
+    Ok(pixmap)
+}
+
+// the view box, in svg coordinates, is fitted into the image and centered
+pub fn transform(view_box: (f32, f32, f32, f32), width: u32, height: u32) -> Transform {
+    let (x, y, view_width, view_height) = view_box;
+    let scale = (width as f32 / view_width).min(height as f32 / view_height);
+    let (center_x, center_y) = (x + view_width / 2.0, y + view_height / 2.0);
+
+    // dessin coordinates have y pointing up
+    Transform::from_row(
+        scale,
+        0.0,
+        0.0,
+        -scale,
+        width as f32 / 2.0 - scale * center_x,
+        height as f32 / 2.0 - scale * center_y,
+    )
+}
+
+pub fn stroke_points(
+    pixmap: &mut Pixmap,
+    points: &[Point2],
+    paint: &Paint,
+    stroke: &Stroke,
+    transform: Transform,
+) {
+    let Some((first, rest)) = points.split_first() else {
+        return;
+    };
+
+    let mut builder = PathBuilder::new();
+    builder.move_to(first.x, first.y);
+    for point in rest {
+        builder.line_to(point.x, point.y);
+    }
+
+    if let Some(path) = builder.finish() {
+        pixmap.stroke_path(&path, paint, stroke, transform, None);
+    }
+}
+
+pub fn encode(pixmap: &Pixmap, dpi: f32) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, pixmap.width(), pixmap.height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let pixels_per_meter = (dpi * INCHES_PER_METER).round() as u32;
+    encoder.set_pixel_dims(Some(png::PixelDimensions {
+        xppu: pixels_per_meter,
+        yppu: pixels_per_meter,
+        unit: png::Unit::Meter,
+    }));
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&rgba(pixmap))?;
+    writer.finish().context("could not encode png")?;
+
+    Ok(bytes)
+}
+
+// straight alpha rgba bytes, tiny-skia keeps pixels premultiplied
+pub fn rgba(pixmap: &Pixmap) -> Vec<u8> {
+    pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect()
+}
+
+// a #rrggbb css color, as written in the svg options
+fn css_color(hex: &str, opacity: f32) -> anyhow::Result<Color> {
+    let invalid = || anyhow!("invalid color {hex}");
+
+    let digits = hex.strip_prefix('#').unwrap_or(hex);
+    if digits.len() != 6 || !digits.is_ascii() {
+        return Err(invalid());
+    }
+    let component = |index: usize| u8::from_str_radix(&digits[index..index + 2], 16);
+    let (red, green, blue) = match (component(0), component(2), component(4)) {
+        (Ok(red), Ok(green), Ok(blue)) => (red, green, blue),
+        _ => return Err(invalid()),
+    };
+
+    let mut color = Color::from_rgba8(red, green, blue, 255);
+    color.set_alpha(opacity.clamp(0.0, 1.0));
+    Ok(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{plotter::VIEW_HALF_SIZE, svg::VIEW_BOX};
+    use dessins_core::shapes::pt2;
+    use tiny_skia::Point;
+
+    fn map(transform: Transform, x: f32, y: f32) -> (f32, f32) {
+        let mut point = Point::from_xy(x, y);
+        transform.map_point(&mut point);
+        (point.x, point.y)
+    }
+
+    #[test]
+    fn transform_centers_the_view_and_flips_y() {
+        let transform = transform(VIEW_BOX, 1440, 720);
+
+        assert_eq!(map(transform, 0.0, 0.0), (720.0, 360.0));
+        assert_eq!(
+            map(transform, VIEW_HALF_SIZE, VIEW_HALF_SIZE),
+            (1080.0, 0.0)
+        );
+        assert_eq!(
+            map(transform, -VIEW_HALF_SIZE, -VIEW_HALF_SIZE),
+            (360.0, 720.0)
+        );
+    }
+
+    #[test]
+    fn rasterize_draws_the_strokes_over_the_background() {
+        let mut points = Shapes::new();
+        points.push(vec![vec![
+            pt2(-VIEW_HALF_SIZE, 0.0),
+            pt2(VIEW_HALF_SIZE, 0.0),
+        ]]);
+        let options = SvgOptions {
+            size: 100.0,
+            stroke_width: 20.0,
+            color: ("#ff0000".to_string(), 1.0),
+            background: Some(("#0000ff".to_string(), 0.5)),
+            ..Default::default()
+        };
+
+        let pixmap = rasterize(&points, &options).unwrap();
+        let pixels = rgba(&pixmap);
+        let pixel = |x: usize, y: usize| &pixels[(y * 100 + x) * 4..][..4];
+
+        assert_eq!((pixmap.width(), pixmap.height()), (100, 100));
+        assert_eq!(pixel(50, 50), [255, 0, 0, 255]);
+        assert_eq!(pixel(50, 5), [0, 0, 255, 128]);
+    }
+
+    #[test]
+    fn encode_records_the_dpi() {
+        let pixmap = pixmap(3, 2, None).unwrap();
+        let bytes = encode(&pixmap, 254.0).unwrap();
+
+        let reader = png::Decoder::new(bytes.as_slice()).read_info().unwrap();
+        let info = reader.info();
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(info.pixel_dims.unwrap().xppu, 10_000);
+    }
+}
@@ -0,0 +1,196 @@
+use crate::{dessin::Dessin, plotter::VIEW_HALF_SIZE};
+use anyhow::bail;
+use dessins_core::shapes::{Point2, Shapes};
+use svg::{
+    node::{
+        element::{path::Data, tag::Type, Element, Group, Path, Rectangle, Title, SVG},
+        Text,
+    },
+    parser::Event,
+    Node,
+};
+
+pub const NAMESPACE: &str = "https://github.com/supiket/dessins";
+pub const DESSIN_TAG: &str = "dessins:dessin";
+pub const VARIABLE_TAG: &str = "dessins:variable";
+
+// the on screen square as (x, y, width, height)
+pub const VIEW_BOX: (f32, f32, f32, f32) = (
+    -VIEW_HALF_SIZE,
+    -VIEW_HALF_SIZE,
+    2.0 * VIEW_HALF_SIZE,
+    2.0 * VIEW_HALF_SIZE,
+);
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgOptions {
+    pub size: f32, // in pixels, of the longer side
+    pub fit: bool, // shrink the view box to the drawing instead of the on screen square
+    pub stroke_width: f32,
+    pub color: (String, f32),              // css hex and opacity
+    pub background: Option<(String, f32)>, // css hex and opacity
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            size: 2000.0,
+            fit: false,
+            stroke_width: 2.0,
+            color: ("#000000".to_string(), 1.0),
+            background: None,
+        }
+    }
+}
+
+// a single dessin in one group, holding one group per shape
+pub fn svg_document(dessin: &Dessin, points: &Shapes, options: &SvgOptions) -> SVG {
+    let view_box = view_box(points, options.fit, options.stroke_width);
+    let mut document = document(view_box, options.size, "px");
+
+    let mut metadata = Element::new("metadata");
+    metadata.append(dessin_metadata(dessin.variant().name(), &dessin.values()));
+    document = document.add(metadata);
+
+    if let Some((color, opacity)) = &options.background {
+        document = document.add(background(view_box, color, *opacity));
+    }
+
+    let (stroke, stroke_opacity) = &options.color;
+    let mut group = Group::new()
+        .set("id", "layer-1")
+        .set("fill", "none")
+        .set("stroke", stroke.as_str())
+        .set("stroke-opacity", *stroke_opacity)
+        .set("stroke-width", options.stroke_width)
+        .set("stroke-linecap", "round")
+        .set("stroke-linejoin", "round")
+        .add(Title::new(dessin.variant().name()));
+
+    for shape in points.iter() {
+        let mut shape_group = Group::new();
+        for segment in shape {
+            shape_group = shape_group.add(path(segment));
+        }
+        group = group.add(shape_group);
+    }
+
+    document.add(group)
+}
+
+// the view box as (x, y, width, height), scaled to `size` on its longer side
+pub fn document(view_box: (f32, f32, f32, f32), size: f32, units: &str) -> SVG {
+    let (x, y, width, height) = view_box;
+    let scale = size / width.max(height);
+
+    SVG::new()
+        .set("viewBox", (x, y, width, height))
+        .set("width", format!("{}{}", width * scale, units))
+        .set("height", format!("{}{}", height * scale, units))
+}
+
+pub fn background(view_box: (f32, f32, f32, f32), color: &str, opacity: f32) -> Rectangle {
+    let (x, y, width, height) = view_box;
+
+    Rectangle::new()
+        .set("x", x)
+        .set("y", y)
+        .set("width", width)
+        .set("height", height)
+        .set("fill", color)
+        .set("fill-opacity", opacity)
+}
+
+pub fn path(points: &[Point2]) -> Path {
+    let mut data = Data::new();
+    for (index, point) in points.iter().enumerate() {
+        data = match index {
+            0 => data.move_to((point.x, -point.y)),
+            _ => data.line_to((point.x, -point.y)),
+        };
+    }
+    Path::new().set("d", data)
+}
+
+// a readable list of the variables, which `read_variables` takes back
+pub fn dessin_metadata(variant: &str, values: &[(String, String)]) -> Element {
+    let mut dessin = Element::new(DESSIN_TAG);
+    dessin.assign("xmlns:dessins", NAMESPACE);
+    dessin.assign("variant", variant);
+
+    for (name, value) in values {
+        let mut variable = Element::new(VARIABLE_TAG);
+        variable.assign("name", name.as_str());
+        variable.append(Text::new(value.as_str()));
+        dessin.append(variable);
+    }
+
+    dessin
+}
+
+// the variant and the variables listed by `dessin_metadata`, the bottom layer's ones for several
+// layers
+pub fn read_variables(content: &str) -> anyhow::Result<(String, Vec<(String, String)>)> {
+    let mut variant = None;
+    let mut variables = vec![];
+    let mut name = None;
+
+    for event in svg::read(content)? {
+        match event {
+            Event::Error(err) => bail!("invalid svg: {}", err),
+            Event::Tag(DESSIN_TAG, Type::Start, attributes) if variant.is_none() => {
+                if let Some(value) = attributes.get("variant") {
+                    variant = Some(unescape(value));
+                }
+            }
+            Event::Tag(DESSIN_TAG, Type::End, _) if variant.is_some() => break,
+            Event::Tag(VARIABLE_TAG, Type::Start, attributes) if variant.is_some() => {
+                name = attributes.get("name").map(|name| unescape(name));
+            }
+            Event::Text(value) => {
+                if let Some(name) = name.take() {
+                    variables.push((name, unescape(value)));
+                }
+            }
+            _ => name = None,
+        }
+    }
+
+    match variant {
+        Some(variant) => Ok((variant, variables)),
+        None => bail!(
+            "no dessins parameters in this svg, it was exported without them or by another program"
+        ),
+    }
+}
+
+// undoes the escaping of svg text nodes and attributes
+pub fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+// in svg coordinates, y points down, `padding` keeps the stroke inside a fitted view box
+pub fn view_box(points: &Shapes, fit: bool, padding: f32) -> (f32, f32, f32, f32) {
+    if !fit {
+        return VIEW_BOX;
+    }
+
+    let mut all_points = points.iter().flatten().flatten();
+    let Some(first) = all_points.next() else {
+        return VIEW_BOX;
+    };
+    let (min, max) = all_points.fold((*first, *first), |(min, max), point| {
+        (min.min(*point), max.max(*point))
+    });
+
+    (
+        min.x - padding,
+        -max.y - padding,
+        max.x - min.x + 2.0 * padding,
+        max.y - min.y + 2.0 * padding,
+    )
+}
@@ -0,0 +1,177 @@
+// compares the shapes of every dessin variant against the snapshots in tests/snapshots
+//
+// DESSINS_BLESS=1 cargo test -p dessins_render --test golden
+// records missing snapshots and rewrites all of them after an intended change to a drawing,
+// they should be committed with the change
+
+use dessins_core::shapes::Shapes;
+use dessins_render::dessin::{Dessin, DessinVariant};
+use std::{fmt::Write, fs, path::PathBuf};
+
+// coordinates are rounded to 1 / PRECISION before hashing
+const PRECISION: f32 = 100.0;
+
+// extra `name = value` sets on top of the defaults, per variant
+fn cases(
+    variant: DessinVariant,
+) -> &'static [(&'static str, &'static [(&'static str, &'static str)])] {
+    match variant {
+        DessinVariant::Polygon => &[("k7", &[("k", "7"), ("ad", "0.25")])],
+        DessinVariant::Star => &[("k11", &[("k", "11"), ("h", "4")])],
+        DessinVariant::Composition1 => &[("k8", &[("polygon_k", "8"), ("star_k", "40")])],
+        DessinVariant::Composition2 => &[("n10", &[("n", "10"), ("rr", "0.5")])],
+        DessinVariant::Jolygon => &[("k500", &[("k", "500"), ("an", "0.3")])],
+        // the default is program 1
+        DessinVariant::RawShape => &[
+            ("program2", &[("program_variant", "Program2")]),
+            ("program3", &[("program_variant", "Program3")]),
+            ("program4", &[("program_variant", "Program4")]),
+            ("program5", &[("program_variant", "Program5")]),
+            ("program6", &[("program_variant", "Program6")]),
+            ("program7", &[("program_variant", "Program7")]),
+            ("program8", &[("program_variant", "Program8")]),
+            ("program9", &[("program_variant", "Program9")]),
+            ("program10", &[("program_variant", "Program10")]),
+            ("program11", &[("program_variant", "Program11")]),
+            ("program12", &[("program_variant", "Program12")]),
+            ("program13", &[("program_variant", "Program13")]),
+        ],
+        DessinVariant::Dragon => &[("n9", &[("n", "9")])],
+        DessinVariant::FractalStar => &[("n3", &[("n", "3"), ("k", "4")])],
+        DessinVariant::OrbitalCurve => &[("t3", &[("t1", "3"), ("k1", "2")])],
+        DessinVariant::RotatingCurve => &[("h2", &[("h1", "2"), ("k2", "3")])],
+        DessinVariant::SpiralCurve => &[("t50", &[("t", "50")])],
+        DessinVariant::LinearBipartite => &[("n20", &[("n", "20"), ("a.x", "0.5")])],
+        DessinVariant::LinearModulo => &[("h5", &[("h", "5"), ("i1_factor", "3")])],
+        DessinVariant::LinearStick => &[("k3", &[("k", "3"), ("m", "2")])],
+        DessinVariant::RegularSimpleFractal => &[("k2", &[("k", "2")])],
+        DessinVariant::RoundedSimpleFractal => &[("s8", &[("s", "8")])],
+        DessinVariant::DeformedSimpleFractal => &[
+            ("k2", &[("k", "2")]),
+            // the default is program 1
+            ("program2", &[("deformation", "Program2")]),
+            ("program3", &[("deformation", "Program3")]),
+            ("program4", &[("deformation", "Program4")]),
+            ("program5", &[("deformation", "Program5")]),
+            ("program6", &[("deformation", "Program6")]),
+            ("program7", &[("deformation", "Program7")]),
+            ("program8", &[("deformation", "Program8")]),
+            ("program9", &[("deformation", "Program9")]),
+            ("program10", &[("deformation", "Program10")]),
+            ("program11", &[("deformation", "Program11")]),
+            ("program12", &[("deformation", "Program12")]),
+        ],
+        DessinVariant::PolarCurve => &[("n1000", &[("n", "1000")])],
+    }
+}
+
+#[test]
+fn golden() {
+    let bless = std::env::var_os("DESSINS_BLESS").is_some();
+    let mut failures = vec![];
+
+    for (variant, name) in DessinVariant::ALL {
+        let defaults: &[(&str, &[(&str, &str)])] = &[("default", &[])];
+        for (case, values) in defaults.iter().chain(cases(*variant)) {
+            let mut dessin = Dessin::new(*variant);
+            for (variable, value) in *values {
+                dessin
+                    .set_variable(variable, value)
+                    .unwrap_or_else(|err| panic!("{name} {case}: {err}"));
+            }
+            let shapes = dessin
+                .calculate_shapes()
+                .unwrap_or_else(|err| panic!("{name} {case}: {err}"));
+            let summary = summary(&shapes);
+
+            let path = snapshot_path(name, case);
+            match fs::read_to_string(&path) {
+                Ok(expected) if expected == summary => {}
+                Ok(expected) if !bless => failures.push(format!(
+                    "{}\n--- expected\n{expected}--- actual\n{summary}",
+                    path.display()
+                )),
+                Err(_) if !bless => {
+                    failures.push(format!("{}\nmissing, actual\n{summary}", path.display()))
+                }
+                _ => {
+                    fs::create_dir_all(path.parent().unwrap()).unwrap();
+                    fs::write(&path, &summary).unwrap();
+                    eprintln!("wrote {}", path.display());
+                }
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} snapshot(s) differ or are missing, rerun with DESSINS_BLESS=1 if the change is \
+         intended\n\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+fn snapshot_path(name: &str, case: &str) -> PathBuf {
+    let name = name.replace(' ', "_");
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("snapshots")
+        .join(format!("{name}.{case}.txt"))
+}
+
+fn summary(shapes: &Shapes) -> String {
+    let segments = shapes.iter().map(|shape| shape.len()).sum::<usize>();
+    let points = shapes.iter().flatten().flatten().collect::<Vec<_>>();
+
+    // adding 0.0 turns -0.0 into 0.0, both draw the same
+    let round = |value: f32| (value * PRECISION).round() / PRECISION + 0.0;
+    let (min, max) = points.iter().fold(
+        (
+            (f32::INFINITY, f32::INFINITY),
+            (f32::NEG_INFINITY, f32::NEG_INFINITY),
+        ),
+        |(min, max), point| {
+            (
+                (min.0.min(point.x), min.1.min(point.y)),
+                (max.0.max(point.x), max.1.max(point.y)),
+            )
+        },
+    );
+
+    // fnv-1a, std's hasher is not guaranteed to be stable between releases
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for shape in shapes.iter() {
+        for segment in shape {
+            for point in segment {
+                for value in [round(point.x), round(point.y)] {
+                    for byte in value.to_le_bytes() {
+                        hash ^= byte as u64;
+                        hash = hash.wrapping_mul(0x100000001b3);
+                    }
+                }
+            }
+            // keep segment boundaries in the hash
+            hash ^= 0xff;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    let mut summary = String::new();
+    writeln!(summary, "shapes: {}", shapes.len()).unwrap();
+    writeln!(summary, "segments: {segments}").unwrap();
+    writeln!(summary, "points: {}", points.len()).unwrap();
+    if !points.is_empty() {
+        writeln!(
+            summary,
+            "bounds: ({}, {}) ({}, {})",
+            round(min.0),
+            round(min.1),
+            round(max.0),
+            round(max.1)
+        )
+        .unwrap();
+    }
+    writeln!(summary, "hash: {hash:016x}").unwrap();
+    summary
+}